@@ -0,0 +1,82 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Throughput of the noise modules, sampled over fixed grids of points so the
+//! timings are comparable between runs.
+
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{Criterion, black_box};
+use noise::modules::{BasicMulti, Billow, Fbm, HybridMulti, Perlin, RidgedMulti};
+use noise::NoiseModule;
+
+/// The number of points along each axis of the sampled grids.
+const GRID_SIZE: usize = 16;
+
+/// Spacing of the grid points, chosen so they don't fall on lattice points.
+const GRID_STEP: f64 = 0.37;
+
+fn grid2() -> Vec<[f64; 2]> {
+    let mut points = Vec::with_capacity(GRID_SIZE * GRID_SIZE);
+    for y in 0..GRID_SIZE {
+        for x in 0..GRID_SIZE {
+            points.push([x as f64 * GRID_STEP, y as f64 * GRID_STEP]);
+        }
+    }
+    points
+}
+
+fn grid3() -> Vec<[f64; 3]> {
+    grid2().into_iter().map(|p| [p[0], p[1], (p[0] - p[1]) * 0.5]).collect()
+}
+
+fn grid4() -> Vec<[f64; 4]> {
+    grid2().into_iter().map(|p| [p[0], p[1], (p[0] - p[1]) * 0.5, p[0] * 0.25]).collect()
+}
+
+/// Benchmarks sampling the module at every point of the grid.
+fn bench_grid<M, P>(c: &mut Criterion, name: &str, module: M, points: Vec<P>)
+    where M: NoiseModule<P, Output = f64> + 'static,
+          P: Copy + 'static,
+{
+    c.bench_function(name, move |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for &point in &points {
+                sum += module.get(black_box(point));
+            }
+            sum
+        })
+    });
+}
+
+fn bench_perlin(c: &mut Criterion) {
+    bench_grid(c, "perlin 2d", Perlin::new(0), grid2());
+    bench_grid(c, "perlin 3d", Perlin::new(0), grid3());
+    bench_grid(c, "perlin 4d", Perlin::new(0), grid4());
+}
+
+fn bench_fractals(c: &mut Criterion) {
+    bench_grid(c, "fbm 3d", Fbm::new(), grid3());
+    bench_grid(c, "billow 3d", Billow::new(), grid3());
+    bench_grid(c, "basicmulti 3d", BasicMulti::new(), grid3());
+    bench_grid(c, "hybridmulti 3d", HybridMulti::new(), grid3());
+    bench_grid(c, "ridgedmulti 3d", RidgedMulti::new(), grid3());
+}
+
+criterion_group!(benches, bench_perlin, bench_fractals);
+criterion_main!(benches);
@@ -12,151 +12,227 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! The gradient vectors used by the gradient noise functions.
+//!
+//! Each gradient noise function hashes the integer coordinates of a lattice
+//! point with the `PermutationTable`, and uses the resulting index to select a
+//! gradient vector from one of the tables below. For example, the
+//! 2-dimensional index of the lattice point `[x, y]` is derived as
+//!
+//! ```text
+//! index = perm[perm[x & 255] ^ (y & 255)]
+//! gradient = GRADIENTS_2D[index % GRADIENTS_2D.len()]
+//! ```
+//!
+//! and likewise in higher dimensions, with one further lookup per extra
+//! coordinate (see `PermutationTable::get2`, `get3` and `get4`). The tables
+//! are exposed so that other implementations, such as GPU shaders, can
+//! reproduce the output of this crate exactly.
+
 use num_traits::Float;
 
 use math;
 
-#[inline(always)]
+/// The 2-dimensional gradient vectors.
+///
+/// The first 4 vectors point along the axes, and the last 4 point along the
+/// diagonals. All the vectors are normalized.
 #[cfg_attr(rustfmt, rustfmt_skip)]
-pub fn get2<T: Float>(index: usize) -> math::Vector2<T> {
-    let zero = T::zero();
-    let one = T::one();
-    // Vectors are combinations of -1, 0, and 1, precompute the normalized element
-    let norm = math::cast(0.7071067811865475);
+pub const GRADIENTS_2D: [[f64; 2]; 8] = [
+    [                1.0,                 0.0],
+    [               -1.0,                 0.0],
+    [                0.0,                 1.0],
+    [                0.0,                -1.0],
+    [ 0.7071067811865475,  0.7071067811865475],
+    [-0.7071067811865475,  0.7071067811865475],
+    [ 0.7071067811865475, -0.7071067811865475],
+    [-0.7071067811865475, -0.7071067811865475],
+];
 
-    match index % 8 {
-        0 => [ one,   zero],
-        1 => [-one,   zero],
-        2 => [ zero,  one],
-        3 => [ zero, -one],
-        4 => [ norm,  norm],
-        5 => [-norm,  norm],
-        6 => [ norm, -norm],
-        7 => [-norm, -norm],
-        _ => panic!("Attempt to access gradient {} of 8", index % 8),
-    }
+/// The 3-dimensional gradient vectors.
+///
+/// The first 24 vectors are the 12 midpoints of the edges of a cube, repeated
+/// twice, and the last 8 are its corners. Repeating the edges makes them more
+/// likely to be selected, reducing directional artifacts. All the vectors are
+/// normalized.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+pub const GRADIENTS_3D: [[f64; 3]; 32] = [
+    [ 0.7071067811865475,  0.7071067811865475,                 0.0],
+    [-0.7071067811865475,  0.7071067811865475,                 0.0],
+    [ 0.7071067811865475, -0.7071067811865475,                 0.0],
+    [-0.7071067811865475, -0.7071067811865475,                 0.0],
+    [ 0.7071067811865475,                 0.0,  0.7071067811865475],
+    [-0.7071067811865475,                 0.0,  0.7071067811865475],
+    [ 0.7071067811865475,                 0.0, -0.7071067811865475],
+    [-0.7071067811865475,                 0.0, -0.7071067811865475],
+    [                0.0,  0.7071067811865475,  0.7071067811865475],
+    [                0.0, -0.7071067811865475,  0.7071067811865475],
+    [                0.0,  0.7071067811865475, -0.7071067811865475],
+    [                0.0, -0.7071067811865475, -0.7071067811865475],
+    [ 0.7071067811865475,  0.7071067811865475,                 0.0],
+    [-0.7071067811865475,  0.7071067811865475,                 0.0],
+    [ 0.7071067811865475, -0.7071067811865475,                 0.0],
+    [-0.7071067811865475, -0.7071067811865475,                 0.0],
+    [ 0.7071067811865475,                 0.0,  0.7071067811865475],
+    [-0.7071067811865475,                 0.0,  0.7071067811865475],
+    [ 0.7071067811865475,                 0.0, -0.7071067811865475],
+    [-0.7071067811865475,                 0.0, -0.7071067811865475],
+    [                0.0,  0.7071067811865475,  0.7071067811865475],
+    [                0.0, -0.7071067811865475,  0.7071067811865475],
+    [                0.0,  0.7071067811865475, -0.7071067811865475],
+    [                0.0, -0.7071067811865475, -0.7071067811865475],
+    [ 0.5773502691896258,  0.5773502691896258,  0.5773502691896258],
+    [-0.5773502691896258,  0.5773502691896258,  0.5773502691896258],
+    [ 0.5773502691896258, -0.5773502691896258,  0.5773502691896258],
+    [-0.5773502691896258, -0.5773502691896258,  0.5773502691896258],
+    [ 0.5773502691896258,  0.5773502691896258, -0.5773502691896258],
+    [-0.5773502691896258,  0.5773502691896258, -0.5773502691896258],
+    [ 0.5773502691896258, -0.5773502691896258, -0.5773502691896258],
+    [-0.5773502691896258, -0.5773502691896258, -0.5773502691896258],
+];
+
+/// The 4-dimensional gradient vectors.
+///
+/// The first 32 vectors are the midpoints of the edges of a tesseract, and
+/// the last 32 are its 16 corners, repeated twice. All the vectors are
+/// normalized.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+pub const GRADIENTS_4D: [[f64; 4]; 64] = [
+    [                0.0,  0.5773502691896258,  0.5773502691896258,  0.5773502691896258],
+    [                0.0,  0.5773502691896258,  0.5773502691896258, -0.5773502691896258],
+    [                0.0,  0.5773502691896258, -0.5773502691896258,  0.5773502691896258],
+    [                0.0,  0.5773502691896258, -0.5773502691896258, -0.5773502691896258],
+    [                0.0, -0.5773502691896258,  0.5773502691896258,  0.5773502691896258],
+    [                0.0, -0.5773502691896258,  0.5773502691896258, -0.5773502691896258],
+    [                0.0, -0.5773502691896258, -0.5773502691896258,  0.5773502691896258],
+    [                0.0, -0.5773502691896258, -0.5773502691896258, -0.5773502691896258],
+    [ 0.5773502691896258,                 0.0,  0.5773502691896258,  0.5773502691896258],
+    [ 0.5773502691896258,                 0.0,  0.5773502691896258, -0.5773502691896258],
+    [ 0.5773502691896258,                 0.0, -0.5773502691896258,  0.5773502691896258],
+    [ 0.5773502691896258,                 0.0, -0.5773502691896258, -0.5773502691896258],
+    [-0.5773502691896258,                 0.0,  0.5773502691896258,  0.5773502691896258],
+    [-0.5773502691896258,                 0.0,  0.5773502691896258, -0.5773502691896258],
+    [-0.5773502691896258,                 0.0, -0.5773502691896258,  0.5773502691896258],
+    [-0.5773502691896258,                 0.0, -0.5773502691896258, -0.5773502691896258],
+    [ 0.5773502691896258,  0.5773502691896258,                 0.0,  0.5773502691896258],
+    [ 0.5773502691896258,  0.5773502691896258,                 0.0, -0.5773502691896258],
+    [ 0.5773502691896258, -0.5773502691896258,                 0.0,  0.5773502691896258],
+    [ 0.5773502691896258, -0.5773502691896258,                 0.0, -0.5773502691896258],
+    [-0.5773502691896258,  0.5773502691896258,                 0.0,  0.5773502691896258],
+    [-0.5773502691896258,  0.5773502691896258,                 0.0, -0.5773502691896258],
+    [-0.5773502691896258, -0.5773502691896258,                 0.0,  0.5773502691896258],
+    [-0.5773502691896258, -0.5773502691896258,                 0.0, -0.5773502691896258],
+    [ 0.5773502691896258,  0.5773502691896258,  0.5773502691896258,                 0.0],
+    [ 0.5773502691896258,  0.5773502691896258, -0.5773502691896258,                 0.0],
+    [ 0.5773502691896258, -0.5773502691896258,  0.5773502691896258,                 0.0],
+    [ 0.5773502691896258, -0.5773502691896258, -0.5773502691896258,                 0.0],
+    [-0.5773502691896258,  0.5773502691896258,  0.5773502691896258,                 0.0],
+    [-0.5773502691896258,  0.5773502691896258, -0.5773502691896258,                 0.0],
+    [-0.5773502691896258, -0.5773502691896258,  0.5773502691896258,                 0.0],
+    [-0.5773502691896258, -0.5773502691896258, -0.5773502691896258,                 0.0],
+    [                0.5,                 0.5,                 0.5,                 0.5],
+    [               -0.5,                 0.5,                 0.5,                 0.5],
+    [                0.5,                -0.5,                 0.5,                 0.5],
+    [               -0.5,                -0.5,                 0.5,                 0.5],
+    [                0.5,                 0.5,                -0.5,                 0.5],
+    [               -0.5,                 0.5,                -0.5,                 0.5],
+    [                0.5,                 0.5,                 0.5,                -0.5],
+    [               -0.5,                 0.5,                 0.5,                -0.5],
+    [                0.5,                -0.5,                -0.5,                 0.5],
+    [               -0.5,                -0.5,                -0.5,                 0.5],
+    [                0.5,                -0.5,                 0.5,                -0.5],
+    [               -0.5,                -0.5,                 0.5,                -0.5],
+    [                0.5,                 0.5,                -0.5,                -0.5],
+    [               -0.5,                 0.5,                -0.5,                -0.5],
+    [                0.5,                -0.5,                -0.5,                -0.5],
+    [               -0.5,                -0.5,                -0.5,                -0.5],
+    [                0.5,                 0.5,                 0.5,                 0.5],
+    [               -0.5,                 0.5,                 0.5,                 0.5],
+    [                0.5,                -0.5,                 0.5,                 0.5],
+    [               -0.5,                -0.5,                 0.5,                 0.5],
+    [                0.5,                 0.5,                -0.5,                 0.5],
+    [               -0.5,                 0.5,                -0.5,                 0.5],
+    [                0.5,                 0.5,                 0.5,                -0.5],
+    [               -0.5,                 0.5,                 0.5,                -0.5],
+    [                0.5,                -0.5,                -0.5,                 0.5],
+    [               -0.5,                -0.5,                -0.5,                 0.5],
+    [                0.5,                -0.5,                 0.5,                -0.5],
+    [               -0.5,                -0.5,                 0.5,                -0.5],
+    [                0.5,                 0.5,                -0.5,                -0.5],
+    [               -0.5,                 0.5,                -0.5,                -0.5],
+    [                0.5,                -0.5,                -0.5,                -0.5],
+    [               -0.5,                -0.5,                -0.5,                -0.5],
+];
+
+/// Returns the 2-dimensional gradient vector for the given index, which wraps
+/// around the length of `GRADIENTS_2D`.
+#[inline(always)]
+pub fn get2<T: Float>(index: usize) -> math::Vector2<T> {
+    get2_from(&GRADIENTS_2D, index)
 }
 
+/// Returns the 3-dimensional gradient vector for the given index, which wraps
+/// around the length of `GRADIENTS_3D`.
 #[inline(always)]
-#[cfg_attr(rustfmt, rustfmt_skip)]
 pub fn get3<T: Float>(index: usize) -> math::Vector3<T> {
-    let zero = T::zero();
-    // Vectors are combinations of -1, 0, and 1, precompute the normalized elements
-    let norm = math::cast(0.7071067811865475);
-    let norm2 = math::cast(0.5773502691896258);
-
-    match index % 32 {
-        // 12 edges repeated twice then 8 corners
-        0  => [ norm,  norm,  zero],
-        1  => [-norm,  norm,  zero],
-        2  => [ norm, -norm,  zero],
-        3  => [-norm, -norm,  zero],
-        4  => [ norm,  zero,  norm],
-        5  => [-norm,  zero,  norm],
-        6  => [ norm,  zero, -norm],
-        7  => [-norm,  zero, -norm],
-        8  => [ zero,  norm,  norm],
-        9  => [ zero, -norm,  norm],
-        10 => [ zero,  norm, -norm],
-        11 => [ zero, -norm, -norm],
-        12 => [ norm,  norm,  zero],
-        13 => [-norm,  norm,  zero],
-        14 => [ norm, -norm,  zero],
-        15 => [-norm, -norm,  zero],
-        16 => [ norm,  zero,  norm],
-        17 => [-norm,  zero,  norm],
-        18 => [ norm,  zero, -norm],
-        19 => [-norm,  zero, -norm],
-        20 => [ zero,  norm,  norm],
-        21 => [ zero, -norm,  norm],
-        22 => [ zero,  norm, -norm],
-        23 => [ zero, -norm, -norm],
-        24 => [ norm2,  norm2,  norm2],
-        25 => [-norm2,  norm2,  norm2],
-        26 => [ norm2, -norm2,  norm2],
-        27 => [-norm2, -norm2,  norm2],
-        28 => [ norm2,  norm2, -norm2],
-        29 => [-norm2,  norm2, -norm2],
-        30 => [ norm2, -norm2, -norm2],
-        31 => [-norm2, -norm2, -norm2],
-        _ => panic!("Attempt to access gradient {} of 32", index % 32),
-    }
+    get3_from(&GRADIENTS_3D, index)
 }
 
+/// Returns the 4-dimensional gradient vector for the given index, which wraps
+/// around the length of `GRADIENTS_4D`.
 #[inline(always)]
-#[cfg_attr(rustfmt, rustfmt_skip)]
 pub fn get4<T: Float>(index: usize) -> math::Vector4<T> {
-    let zero = T::zero();
-    // Vectors are combinations of -1, 0, and 1, precompute the normalized elements
-    let norm = math::cast(0.5773502691896258);
-    let norm2 = math::cast(0.5);
+    get4_from(&GRADIENTS_4D, index)
+}
+
+/// Returns the gradient vector for the given index from a custom table of
+/// 2-dimensional gradient vectors. The index wraps around the length of the
+/// table, which must not be empty.
+#[inline(always)]
+pub fn get2_from<T: Float>(gradients: &[[f64; 2]], index: usize) -> math::Vector2<T> {
+    let gradient = gradients[index % gradients.len()];
+    [math::cast(gradient[0]), math::cast(gradient[1])]
+}
+
+/// Returns the gradient vector for the given index from a custom table of
+/// 3-dimensional gradient vectors. The index wraps around the length of the
+/// table, which must not be empty.
+#[inline(always)]
+pub fn get3_from<T: Float>(gradients: &[[f64; 3]], index: usize) -> math::Vector3<T> {
+    let gradient = gradients[index % gradients.len()];
+    [math::cast(gradient[0]), math::cast(gradient[1]), math::cast(gradient[2])]
+}
+
+/// Returns the gradient vector for the given index from a custom table of
+/// 4-dimensional gradient vectors. The index wraps around the length of the
+/// table, which must not be empty.
+#[inline(always)]
+pub fn get4_from<T: Float>(gradients: &[[f64; 4]], index: usize) -> math::Vector4<T> {
+    let gradient = gradients[index % gradients.len()];
+    [math::cast(gradient[0]),
+     math::cast(gradient[1]),
+     math::cast(gradient[2]),
+     math::cast(gradient[3])]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GRADIENTS_2D, GRADIENTS_3D, GRADIENTS_4D, get2, get3, get4};
+
+    #[test]
+    fn test_get_matches_tables() {
+        for i in 0..512 {
+            assert_eq!(get2::<f64>(i), GRADIENTS_2D[i % 8]);
+            assert_eq!(get3::<f64>(i), GRADIENTS_3D[i % 32]);
+            assert_eq!(get4::<f64>(i), GRADIENTS_4D[i % 64]);
+        }
+    }
 
-    match index % 64 {
-        // 32 edges then 16 corners repeated twice
-        0  => [ zero,  norm,  norm,  norm],
-        1  => [ zero,  norm,  norm, -norm],
-        2  => [ zero,  norm, -norm,  norm],
-        3  => [ zero,  norm, -norm, -norm],
-        4  => [ zero, -norm,  norm,  norm],
-        5  => [ zero, -norm,  norm, -norm],
-        6  => [ zero, -norm, -norm,  norm],
-        7  => [ zero, -norm, -norm, -norm],
-        8  => [ norm,  zero,  norm,  norm],
-        9  => [ norm,  zero,  norm, -norm],
-        10 => [ norm,  zero, -norm,  norm],
-        11 => [ norm,  zero, -norm, -norm],
-        12 => [-norm,  zero,  norm,  norm],
-        13 => [-norm,  zero,  norm, -norm],
-        14 => [-norm,  zero, -norm,  norm],
-        15 => [-norm,  zero, -norm, -norm],
-        16 => [ norm,  norm,  zero,  norm],
-        17 => [ norm,  norm,  zero, -norm],
-        18 => [ norm, -norm,  zero,  norm],
-        19 => [ norm, -norm,  zero, -norm],
-        20 => [-norm,  norm,  zero,  norm],
-        21 => [-norm,  norm,  zero, -norm],
-        22 => [-norm, -norm,  zero,  norm],
-        23 => [-norm, -norm,  zero, -norm],
-        24 => [ norm,  norm,  norm,  zero],
-        25 => [ norm,  norm, -norm,  zero],
-        26 => [ norm, -norm,  norm,  zero],
-        27 => [ norm, -norm, -norm,  zero],
-        28 => [-norm,  norm,  norm,  zero],
-        29 => [-norm,  norm, -norm,  zero],
-        30 => [-norm, -norm,  norm,  zero],
-        31 => [-norm, -norm, -norm,  zero],
-        32 => [ norm2,  norm2,  norm2,  norm2],
-        33 => [-norm2,  norm2,  norm2,  norm2],
-        34 => [ norm2, -norm2,  norm2,  norm2],
-        35 => [-norm2, -norm2,  norm2,  norm2],
-        36 => [ norm2,  norm2, -norm2,  norm2],
-        37 => [-norm2,  norm2, -norm2,  norm2],
-        38 => [ norm2,  norm2,  norm2, -norm2],
-        39 => [-norm2,  norm2,  norm2, -norm2],
-        40 => [ norm2, -norm2, -norm2,  norm2],
-        41 => [-norm2, -norm2, -norm2,  norm2],
-        42 => [ norm2, -norm2,  norm2, -norm2],
-        43 => [-norm2, -norm2,  norm2, -norm2],
-        44 => [ norm2,  norm2, -norm2, -norm2],
-        45 => [-norm2,  norm2, -norm2, -norm2],
-        46 => [ norm2, -norm2, -norm2, -norm2],
-        47 => [-norm2, -norm2, -norm2, -norm2],
-        48 => [ norm2,  norm2,  norm2,  norm2],
-        49 => [-norm2,  norm2,  norm2,  norm2],
-        50 => [ norm2, -norm2,  norm2,  norm2],
-        51 => [-norm2, -norm2,  norm2,  norm2],
-        52 => [ norm2,  norm2, -norm2,  norm2],
-        53 => [-norm2,  norm2, -norm2,  norm2],
-        54 => [ norm2,  norm2,  norm2, -norm2],
-        55 => [-norm2,  norm2,  norm2, -norm2],
-        56 => [ norm2, -norm2, -norm2,  norm2],
-        57 => [-norm2, -norm2, -norm2,  norm2],
-        58 => [ norm2, -norm2,  norm2, -norm2],
-        59 => [-norm2, -norm2,  norm2, -norm2],
-        60 => [ norm2,  norm2, -norm2, -norm2],
-        61 => [-norm2,  norm2, -norm2, -norm2],
-        62 => [ norm2, -norm2, -norm2, -norm2],
-        63 => [-norm2, -norm2, -norm2, -norm2],
-        _ => panic!("Attempt to access gradient {} of 64", index % 64),
+    #[test]
+    fn test_gradients_are_normalized() {
+        for g in GRADIENTS_3D.iter() {
+            let length = (g[0] * g[0] + g[1] * g[1] + g[2] * g[2]).sqrt();
+            assert!((length - 1.0).abs() < 1e-12);
+        }
     }
 }
@@ -26,11 +26,21 @@
 
 #![deny(missing_copy_implementations)]
 
+#[cfg(feature = "image")]
+extern crate image;
 extern crate num_traits;
 extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "disk_cache", all(test, feature = "serde")))]
+extern crate serde_json;
+
+use num_traits::Float;
 
 pub use permutationtable::PermutationTable;
-pub use math::{Point2, Point3, Point4};
+pub use math::{Point1, Point2, Point3, Point4};
 pub use perlin::{perlin2, perlin3, perlin4};
 pub use value::{value2, value3, value4};
 pub use open_simplex::{open_simplex2, open_simplex3, open_simplex4};
@@ -45,9 +55,11 @@ pub use cell::{cell2_manhattan, cell3_manhattan, cell4_manhattan};
 pub use cell::{cell2_manhattan_inv, cell3_manhattan_inv, cell4_manhattan_inv};
 pub use cell::{cell2_manhattan_value, cell3_manhattan_value, cell4_manhattan_value};
 
-mod gradient;
-mod math;
+pub mod gradient;
+pub mod math;
 mod permutationtable;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
 
 mod brownian;
 mod perlin;
@@ -56,6 +68,10 @@ mod open_simplex;
 mod cell;
 
 pub mod modules;
+pub mod utils;
+
+#[cfg(test)]
+mod test_utils;
 
 /// A trait alias for a 2-dimensional noise function.
 ///
@@ -117,13 +133,108 @@ pub trait NoiseModule<T> {
     type Output;
 
     fn get(&self, point: T) -> Self::Output;
+
+    /// Samples the module at each of `points`, writing the results to the
+    /// matching elements of `out`.
+    ///
+    /// The default implementation calls `get` for each point in turn. Modules
+    /// that can evaluate several points at once, such as with SIMD, can
+    /// override it, so callers sampling many points at a time, like a row of
+    /// a map, should prefer it over `get`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    fn get_many(&self, points: &[T], out: &mut [Self::Output])
+        where T: Copy,
+    {
+        assert_eq!(points.len(), out.len(), "points and out differ in length");
+        for (&point, value) in points.iter().zip(out.iter_mut()) {
+            *value = self.get(point);
+        }
+    }
+
+    /// Returns a rough estimate of the cost of a single call to `get()`, in
+    /// "noise operations".
+    ///
+    /// Generators count one operation per noise sample, so fractal modules
+    /// count one per octave, and modules with source modules add up the
+    /// estimates of their sources. This can be used to warn when a module
+    /// graph is too expensive to sample in real time. The default estimate is
+    /// 1.
+    fn complexity_estimate(&self) -> usize {
+        1
+    }
+
+    /// Returns the range the output values of the module lie within, as
+    /// `(lower, upper)`.
+    ///
+    /// This allows the output to be remapped, such as to pixel intensities,
+    /// without sampling the module first to calibrate it. The default is the
+    /// nominal range of -1.0 to 1.0, which the generators, including Perlin
+    /// noise, stay within. Modules with other ranges, such as fractals without
+    /// normalization, report them, and modifiers and combiners transform the
+    /// ranges of their source modules. Modules that don't know their range
+    /// keep the default, so it is a guide rather than a guarantee.
+    fn bounds(&self) -> (Self::Output, Self::Output)
+        where Self::Output: Float,
+    {
+        (math::cast(-1.0), math::cast(1.0))
+    }
 }
 
-impl<'a, T, M: NoiseModule<T>> NoiseModule<T> for &'a M {
+impl<'a, T, M: NoiseModule<T> + ?Sized> NoiseModule<T> for &'a M {
     type Output = M::Output;
 
     #[inline]
     fn get(&self, point: T) -> M::Output {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn get_many(&self, points: &[T], out: &mut [M::Output])
+        where T: Copy,
+    {
+        M::get_many(*self, points, out)
+    }
+
+    #[inline]
+    fn complexity_estimate(&self) -> usize {
+        M::complexity_estimate(*self)
+    }
+
+    #[inline]
+    fn bounds(&self) -> (M::Output, M::Output)
+        where M::Output: Float,
+    {
+        M::bounds(*self)
+    }
+}
+
+impl<T, M: NoiseModule<T> + ?Sized> NoiseModule<T> for Box<M> {
+    type Output = M::Output;
+
+    #[inline]
+    fn get(&self, point: T) -> M::Output {
+        M::get(&**self, point)
+    }
+
+    #[inline]
+    fn get_many(&self, points: &[T], out: &mut [M::Output])
+        where T: Copy,
+    {
+        M::get_many(&**self, points, out)
+    }
+
+    #[inline]
+    fn complexity_estimate(&self) -> usize {
+        M::complexity_estimate(&**self)
+    }
+
+    #[inline]
+    fn bounds(&self) -> (M::Output, M::Output)
+        where M::Output: Float,
+    {
+        M::bounds(&**self)
+    }
 }
@@ -12,23 +12,64 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! An ultra-light private math library to make our short lives easier as we
-//! implement super-complex noise stuff.
+//! An ultra-light math library for the fixed-size arrays used as points and
+//! vectors, to make our short lives easier as we implement super-complex noise
+//! stuff.
+//!
+//! The point and vector types, `cast`, the component-wise operations
+//! (`mapN`, `zip_withN`, `foldN`, `addN`, `subN`, `mulN`, `dotN`, `constN`,
+//! `oneN` and `castN`) and the `interp` functions are part of the stable API,
+//! so custom noise modules can manipulate points the same way the built-in
+//! ones do. The remaining helpers are used internally, are hidden from the
+//! documentation, and may change without notice.
+//!
+//! # Example
+//!
+//! ```rust
+//! use noise::NoiseModule;
+//! use noise::math::{self, Point3};
+//!
+//! /// Samples the source at the point mirrored through a center point.
+//! struct Mirror<Source> {
+//!     source: Source,
+//!     center: Point3<f64>,
+//! }
+//!
+//! impl<Source> NoiseModule<Point3<f64>> for Mirror<Source>
+//!     where Source: NoiseModule<Point3<f64>, Output = f64>,
+//! {
+//!     type Output = f64;
+//!
+//!     fn get(&self, point: Point3<f64>) -> f64 {
+//!         let offset = math::sub3(self.center, point);
+//!         self.source.get(math::add3(self.center, offset))
+//!     }
+//! }
+//! ```
 
-use num_traits::{self, Float, NumCast};
+use num_traits::{self, Float, NumCast, PrimInt, Signed};
 use std::ops::{Add, Mul, Sub};
 
 /// Cast a numeric type without having to unwrap - we don't expect any overflow
 /// errors...
+///
+/// # Panics
+///
+/// Panics if the value can't be represented in the target type.
 pub fn cast<T: NumCast, U: NumCast>(x: T) -> U {
     num_traits::cast(x).unwrap()
 }
 
 /// Raises the number to the power of `4`
+#[doc(hidden)]
 pub fn pow4<T: Float>(x: T) -> T {
     x * x * x * x
 }
 
+/// A 1-dimensional point. This is a fixed sized array, so that it can be
+/// distinguished from a scalar.
+pub type Point1<T> = [T; 1];
+
 /// A 2-dimensional point. This is a fixed sized array, so should be compatible
 /// with most linear algebra libraries.
 pub type Point2<T> = [T; 2];
@@ -41,13 +82,14 @@ pub type Point3<T> = [T; 3];
 /// with most linear algebra libraries.
 pub type Point4<T> = [T; 4];
 
-/// A 2-dimensional vector, for internal use.
+/// A 2-dimensional vector, such as the offset between two points.
 pub type Vector2<T> = [T; 2];
-/// A 3-dimensional vector, for internal use.
+/// A 3-dimensional vector, such as the offset between two points.
 pub type Vector3<T> = [T; 3];
-/// A 4-dimensional vector, for internal use.
+/// A 4-dimensional vector, such as the offset between two points.
 pub type Vector4<T> = [T; 4];
 
+/// Applies the function to each component of the vector.
 pub fn map2<T, U, F>(a: Vector2<T>, f: F) -> Vector2<U>
     where T: Copy,
           F: Fn(T) -> U,
@@ -70,6 +112,7 @@ pub fn map4<T, U, F>(a: Vector4<T>, f: F) -> Vector4<U>
     [f(ax), f(ay), f(az), f(aw)]
 }
 
+/// Combines the matching components of two vectors with the function.
 pub fn zip_with2<T, U, V, F>(a: Vector2<T>, b: Vector2<U>, f: F) -> Vector2<V>
     where T: Copy,
           U: Copy,
@@ -98,6 +141,8 @@ pub fn zip_with4<T, U, V, F>(a: Vector4<T>, b: Vector4<U>, f: F) -> Vector4<V>
     [f(ax, bx), f(ay, by), f(az, bz), f(aw, bw)]
 }
 
+/// Reduces the components of the vector to a single value with the function,
+/// from the first component to the last.
 pub fn fold2<T, F>(a: Vector2<T>, f: F) -> T
     where T: Copy,
           F: Fn(T, T) -> T,
@@ -120,6 +165,7 @@ pub fn fold4<T, F>(a: Vector4<T>, f: F) -> T
     f(f(f(ax, ay), az), aw)
 }
 
+/// Offsets a point by a vector.
 pub fn add2<T>(a: Point2<T>, b: Vector2<T>) -> Point2<T>
     where T: Copy + Add<T, Output = T>,
 {
@@ -136,6 +182,7 @@ pub fn add4<T>(a: Point4<T>, b: Vector4<T>) -> Point4<T>
     zip_with4(a, b, Add::add)
 }
 
+/// Returns the vector from the second point to the first.
 pub fn sub2<T>(a: Point2<T>, b: Point2<T>) -> Vector2<T>
     where T: Copy + Sub<T, Output = T>,
 {
@@ -152,6 +199,7 @@ pub fn sub4<T>(a: Point4<T>, b: Point4<T>) -> Vector4<T>
     zip_with4(a, b, Sub::sub)
 }
 
+/// Multiplies each component of the vector by a scalar.
 pub fn mul2<T>(a: Vector2<T>, b: T) -> Vector2<T>
     where T: Copy + Mul<T, Output = T>,
 {
@@ -168,6 +216,7 @@ pub fn mul4<T>(a: Vector4<T>, b: T) -> Vector4<T>
     zip_with4(a, const4(b), Mul::mul)
 }
 
+/// Returns the dot product of two vectors.
 pub fn dot2<T: Float>(a: Vector2<T>, b: Vector2<T>) -> T {
     fold2(zip_with2(a, b, Mul::mul), Add::add)
 }
@@ -178,6 +227,7 @@ pub fn dot4<T: Float>(a: Vector4<T>, b: Vector4<T>) -> T {
     fold4(zip_with4(a, b, Mul::mul), Add::add)
 }
 
+/// Returns a vector with every component set to the value.
 pub fn const2<T: Copy>(x: T) -> Vector2<T> {
     [x, x]
 }
@@ -188,6 +238,7 @@ pub fn const4<T: Copy>(x: T) -> Vector4<T> {
     [x, x, x, x]
 }
 
+/// Returns a vector with every component set to one.
 pub fn one2<T: Copy + NumCast>() -> Vector2<T> {
     cast2(const2(1))
 }
@@ -198,6 +249,7 @@ pub fn one4<T: Copy + NumCast>() -> Vector4<T> {
     cast4(const4(1))
 }
 
+/// Casts each component of the point to another numeric type. See `cast`.
 pub fn cast2<T, U>(x: Point2<T>) -> Point2<U>
     where T: NumCast + Copy,
           U: NumCast + Copy,
@@ -217,6 +269,41 @@ pub fn cast4<T, U>(x: Point4<T>) -> Point4<U>
     map4(x, cast)
 }
 
+/// Wraps each component of the point into the range [0, b), so that lattice
+/// points repeat with a period of `b` along the corresponding axis. Components
+/// with a period of 0 are left unchanged.
+#[doc(hidden)]
+pub fn mod2<T: PrimInt + Signed>(a: Point2<T>, b: Point2<T>) -> Point2<T> {
+    [modulo(a[0], b[0]), modulo(a[1], b[1])]
+}
+#[doc(hidden)]
+pub fn mod3<T: PrimInt + Signed>(a: Point3<T>, b: Point3<T>) -> Point3<T> {
+    [modulo(a[0], b[0]), modulo(a[1], b[1]), modulo(a[2], b[2])]
+}
+#[doc(hidden)]
+pub fn mod4<T: PrimInt + Signed>(a: Point4<T>, b: Point4<T>) -> Point4<T> {
+    [modulo(a[0], b[0]), modulo(a[1], b[1]), modulo(a[2], b[2]), modulo(a[3], b[3])]
+}
+
+fn modulo<T: PrimInt + Signed>(a: T, b: T) -> T {
+    if b == T::zero() {
+        return a;
+    }
+    let r = a % b;
+    if r < T::zero() { r + b } else { r }
+}
+
+/// Scrambles a 64-bit value with the SplitMix64 finalizer. Nearby inputs
+/// produce uncorrelated outputs, which makes it handy for deriving seeds.
+#[doc(hidden)]
+pub fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Interpolation between values.
 pub mod interp {
     use num_traits::Float;
     use math;
@@ -253,6 +340,26 @@ pub mod interp {
 
     /// Maps a value onto a quintic S-curve.
     pub fn s_curve5<T: Float>(x: T) -> T {
-        x * x * x * (x * (x * math::cast(6.0) - math::cast(15.0) + math::cast(10.0)))
+        x * x * x * (x * (x * math::cast(6.0) - math::cast(15.0)) + math::cast(10.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interp;
+
+    #[test]
+    fn test_s_curve5() {
+        assert_eq!(interp::s_curve5(0.0), 0.0);
+        assert_eq!(interp::s_curve5(0.5), 0.5);
+        assert_eq!(interp::s_curve5(1.0), 1.0);
+
+        // The curve rises monotonically from 0 to 1, without overshooting.
+        let mut previous = 0.0;
+        for i in 1..101 {
+            let value = interp::s_curve5(i as f64 / 100.0);
+            assert!(value >= previous && value <= 1.0, "{} out of order", value);
+            previous = value;
+        }
     }
 }
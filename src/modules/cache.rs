@@ -15,21 +15,27 @@
 use num_traits::Float;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::Reseedable;
 use std::cell::{Cell, RefCell};
 
 /// Noise module that caches the last output value generated by the source
 /// module.
 ///
-/// If the input coordinates pased to `Cache::get` are equal to the previous
+/// If the input coordinates passed to `Cache::get` are equal to the previous
 /// call, the module returns the cached result of the previous call to
 /// `Source::get`. Otherwise, `Source::get` is called with the new coordinates,
 /// overwriting the cache with the result, and returning the result to the
 /// caller.
 ///
+/// Only the single most recent point is cached, matching libnoise's `Cache`.
+/// Sampling points in any other order, such as alternating between two
+/// points, recomputes every value.
+///
 /// Caching a noise module is useful if it is used as a source module for
 /// multiple noise modules. If a source module is not cached, the source
 /// module will redundantly calculate the same output value once for each
-/// noise module in which it is included.
+/// noise module in which it is included. The cache uses interior mutability,
+/// so a `Cache` cannot be shared between threads.
 #[derive(Clone, Debug)]
 pub struct Cache<Source, T>
     where T: Float,
@@ -54,6 +60,16 @@ impl<Source, T> Cache<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for Cache<Source, T>
+    where Source: Reseedable,
+          T: Float,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+        self.value.set(None);
+    }
+}
+
 impl<Source, T> NoiseModule<Point2<T>> for Cache<Source, T>
     where Source: NoiseModule<Point2<T>, Output = T>,
           T: Float,
@@ -75,6 +91,14 @@ impl<Source, T> NoiseModule<Point2<T>> for Cache<Source, T>
             },
         }
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point3<T>> for Cache<Source, T>
@@ -98,6 +122,14 @@ impl<Source, T> NoiseModule<Point3<T>> for Cache<Source, T>
             },
         }
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point4<T>> for Cache<Source, T>
@@ -121,4 +153,33 @@ impl<Source, T> NoiseModule<Point4<T>> for Cache<Source, T>
             },
         }
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+    use test_utils::{Counting, Sum};
+    use NoiseModule;
+
+    #[test]
+    fn test_caches_most_recent_point() {
+        let source = Counting::new(Sum);
+        let cache = Cache::new(&source);
+
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(source.samples(), 1);
+
+        assert_eq!(cache.get([2.0, 2.0]), 4.0);
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(source.samples(), 3);
+    }
 }
@@ -0,0 +1,105 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use NoiseModule;
+use modules::{Reseedable, source_seed};
+
+/// Noise module that outputs the angle of the vector formed by the output
+/// values from two source modules.
+///
+/// The output value is `atan2(y, x)`, where _y_ and _x_ are the output values
+/// from `source_y` and `source_x`, divided by π so it lies in the range -1.0
+/// to 1.0. Where both output values are zero, the angle is undefined, and this
+/// noise module outputs 0.0.
+///
+/// This is useful for swirl and vortex patterns, and as a control module for
+/// radial blends.
+pub struct Atan2<SourceY, SourceX> {
+    /// Outputs the _y_ component of the vector.
+    pub source_y: SourceY,
+
+    /// Outputs the _x_ component of the vector.
+    pub source_x: SourceX,
+}
+
+impl<SourceY, SourceX> Atan2<SourceY, SourceX> {
+    pub fn new(source_y: SourceY, source_x: SourceX) -> Atan2<SourceY, SourceX> {
+        Atan2 {
+            source_y: source_y,
+            source_x: source_x,
+        }
+    }
+}
+
+impl<SourceY, SourceX> Reseedable for Atan2<SourceY, SourceX>
+    where SourceY: Reseedable,
+          SourceX: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source_y.reseed(source_seed(seed, 0));
+        self.source_x.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<SourceY, SourceX, T, U> NoiseModule<T> for Atan2<SourceY, SourceX>
+    where SourceY: NoiseModule<T, Output = U>,
+          SourceX: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        let y = self.source_y.get(point);
+        let x = self.source_x.get(point);
+
+        if y == U::zero() && x == U::zero() {
+            U::zero()
+        } else {
+            y.atan2(x) / math::cast(::std::f64::consts::PI)
+        }
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source_y.complexity_estimate() + self.source_x.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Atan2;
+    use modules::Constant;
+    use NoiseModule;
+
+    fn angle(y: f64, x: f64) -> f64 {
+        Atan2::new(Constant::new(y), Constant::new(x)).get([0.0, 0.0])
+    }
+
+    #[test]
+    fn test_angle() {
+        assert_eq!(angle(0.0, 0.0), 0.0);
+        assert_eq!(angle(0.0, -0.0), 0.0);
+        assert!((angle(1.0, 1.0) - 0.25).abs() < 1e-12);
+
+        // Negating y mirrors the angle about the x axis, and negating x
+        // mirrors it about the y axis.
+        for &(y, x) in [(0.3, 0.8), (0.9, -0.2), (0.5, 0.5)].iter() {
+            assert!((angle(-y, x) + angle(y, x)).abs() < 1e-12);
+            assert!((angle(y, -x) - (1.0 - angle(y, x))).abs() < 1e-12);
+        }
+    }
+}
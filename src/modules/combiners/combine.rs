@@ -0,0 +1,102 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use NoiseModule;
+use modules::{Reseedable, source_seed};
+
+/// Noise module that combines the output values from two source modules with
+/// an arbitrary function.
+///
+/// This generalizes `Add`, `Max`, `Min` and `Multiply`, and covers any other
+/// combination without defining a new noise module:
+///
+/// ```rust
+/// use noise::NoiseModule;
+/// use noise::modules::{Combine, Perlin};
+///
+/// let max = Combine::new(Perlin::new(0), Perlin::new(1), |a: f64, b: f64| a.max(b));
+/// let point = [0.3, 0.7];
+/// assert_eq!(max.get(point), Perlin::new(0).get(point).max(Perlin::new(1).get(point)));
+/// ```
+pub struct Combine<Source1, Source2, F> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Function applied to the output values from the two source modules.
+    pub function: F,
+}
+
+impl<Source1, Source2, F> Combine<Source1, Source2, F> {
+    pub fn new(source1: Source1, source2: Source2, function: F) -> Combine<Source1, Source2, F> {
+        Combine {
+            source1: source1,
+            source2: source2,
+            function: function,
+        }
+    }
+}
+
+impl<Source1, Source2, F> Reseedable for Combine<Source1, Source2, F>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<Source1, Source2, F, T, U> NoiseModule<T> for Combine<Source1, Source2, F>
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          F: Fn(U, U) -> U,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        (self.function)(self.source1.get(point), self.source2.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Combine;
+    use modules::{Add, Min, Perlin};
+    use std::ops;
+    use test_utils::assert_modules_equal;
+
+    #[test]
+    fn test_matches_add_and_min() {
+        let region = ([-3.0, -3.0], [3.0, 3.0]);
+        assert_modules_equal(&Combine::new(Perlin::new(0), Perlin::new(1), ops::Add::add),
+                             &Add::new(Perlin::new(0), Perlin::new(1)),
+                             region,
+                             0.0);
+        assert_modules_equal(&Combine::new(Perlin::new(0), Perlin::new(1), f64::min),
+                             &Min::new(Perlin::new(0), Perlin::new(1)),
+                             region,
+                             0.0);
+    }
+}
@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::{Reseedable, source_seed};
 
 /// Noise module that outputs the larger of the two output values from two source
 /// modules.
@@ -34,6 +35,16 @@ impl<Source1, Source2> Max<Source1, Source2> {
     }
 }
 
+impl<Source1, Source2> Reseedable for Max<Source1, Source2>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
 impl<Source1, Source2, T, U> NoiseModule<T> for Max<Source1, Source2>
     where Source1: NoiseModule<T, Output = U>,
           Source2: NoiseModule<T, Output = U>,
@@ -45,4 +56,14 @@ impl<Source1, Source2, T, U> NoiseModule<T> for Max<Source1, Source2>
     fn get(&self, point: T) -> Self::Output {
         (self.source1.get(point)).max(self.source2.get(point))
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+        (lower1.max(lower2), upper1.max(upper2))
+    }
 }
@@ -13,13 +13,23 @@
 // limitations under the License.
 
 pub use self::add::*;
+pub use self::atan2::*;
+pub use self::combine::*;
 pub use self::max::*;
 pub use self::min::*;
+pub use self::multi_sample::*;
 pub use self::multiply::*;
 pub use self::power::*;
+pub use self::subtract::*;
+pub use self::weighted_add::*;
 
 mod add;
+mod atan2;
+mod combine;
 mod max;
 mod min;
+mod multi_sample;
 mod multiply;
 mod power;
+mod subtract;
+mod weighted_add;
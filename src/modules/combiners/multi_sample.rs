@@ -0,0 +1,69 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use NoiseModule;
+
+/// Noise module that samples several source modules at the same point.
+///
+/// The output value is a `Vec` holding the output value of each source
+/// module, in order. This is useful for generating correlated channels, such
+/// as height, temperature and moisture, in a single pass over a grid.
+pub struct MultiSample<T, U> {
+    /// Outputs values.
+    pub sources: Vec<Box<dyn NoiseModule<T, Output = U>>>,
+}
+
+impl<T, U> MultiSample<T, U> {
+    pub fn new(sources: Vec<Box<dyn NoiseModule<T, Output = U>>>) -> MultiSample<T, U> {
+        MultiSample { sources: sources }
+    }
+}
+
+impl<T, U> NoiseModule<T> for MultiSample<T, U>
+    where T: Copy,
+{
+    type Output = Vec<U>;
+
+    fn get(&self, point: T) -> Self::Output {
+        self.sources.iter().map(|source| source.get(point)).collect()
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.sources.iter().map(|source| source.complexity_estimate()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiSample;
+    use modules::{Billow, Fbm, Perlin};
+    use NoiseModule;
+
+    #[test]
+    fn test_multi_sample_matches_sources() {
+        let perlin = Perlin::new(3);
+        let fbm = Fbm::new().set_seed(4);
+        let billow = Billow::new().set_seed(5);
+
+        let multi: MultiSample<[f64; 3], f64> =
+            MultiSample::new(vec![Box::new(perlin), Box::new(fbm.clone()), Box::new(billow.clone())]);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.41 - 2.0, i as f64 * 0.17, 1.3];
+            assert_eq!(multi.get(point),
+                       vec![perlin.get(point), fbm.get(point), billow.get(point)]);
+        }
+    }
+}
@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::{Reseedable, source_seed};
 
 /// Noise module that raises the output vaule from the first source module
 /// to the power of the output value of the second source module.
@@ -34,6 +35,16 @@ impl<Source1, Source2> Power<Source1, Source2> {
     }
 }
 
+impl<Source1, Source2> Reseedable for Power<Source1, Source2>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
 impl<Source1, Source2, T, U> NoiseModule<T> for Power<Source1, Source2>
     where Source1: NoiseModule<T, Output = U>,
           Source2: NoiseModule<T, Output = U>,
@@ -45,4 +56,26 @@ impl<Source1, Source2, T, U> NoiseModule<T> for Power<Source1, Source2>
     fn get(&self, point: T) -> Self::Output {
         (self.source1.get(point)).powf(self.source2.get(point))
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+
+        // Negative bases give NaN for fractional exponents, so the output is
+        // unbounded. Otherwise the power is monotonic in both the base and the
+        // exponent, so its extremes are at the corners.
+        if lower1 < U::zero() {
+            return (U::neg_infinity(), U::infinity());
+        }
+        let powers = [lower1.powf(lower2),
+                      lower1.powf(upper2),
+                      upper1.powf(lower2),
+                      upper1.powf(upper2)];
+        powers.iter().fold((powers[0], powers[0]),
+                           |(lower, upper), &power| (lower.min(power), upper.max(power)))
+    }
 }
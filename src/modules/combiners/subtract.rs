@@ -0,0 +1,88 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::Float;
+use NoiseModule;
+use modules::{Reseedable, source_seed};
+
+/// Noise module that outputs the difference of the two output values from two
+/// source modules, subtracting the second from the first.
+pub struct Subtract<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+}
+
+impl<Source1, Source2> Subtract<Source1, Source2> {
+    pub fn new(source1: Source1, source2: Source2) -> Subtract<Source1, Source2> {
+        Subtract {
+            source1: source1,
+            source2: source2,
+        }
+    }
+}
+
+impl<Source1, Source2> Reseedable for Subtract<Source1, Source2>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<Source1, Source2, T, U> NoiseModule<T> for Subtract<Source1, Source2>
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        self.source1.get(point) - self.source2.get(point)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+        (lower1 - upper2, upper1 - lower2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Subtract;
+    use modules::{Constant, Perlin};
+    use NoiseModule;
+
+    #[test]
+    fn test_subtract_order() {
+        let subtract = Subtract::new(Constant::new(0.5), Constant::new(0.75));
+        assert_eq!(subtract.get([0.0, 0.0]), -0.25);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let subtract = Subtract::new(Perlin::new(0), Constant::new(0.25));
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&subtract), (-1.25, 0.75));
+    }
+}
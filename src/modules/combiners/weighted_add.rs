@@ -0,0 +1,127 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use NoiseModule;
+use modules::{Reseedable, source_seed};
+
+/// Noise module that outputs the weighted sum of the output values from two
+/// source modules.
+///
+/// This is useful for layering a detail module onto a base module at a
+/// different strength, without wrapping each source in a `ScaleBias` module.
+/// With the default weights, the output is the same as that of `Add`.
+pub struct WeightedAdd<Source1, Source2, T> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Weight applied to the output value from the first source module.
+    /// The default value is 1.0.
+    pub weight1: T,
+
+    /// Weight applied to the output value from the second source module.
+    /// The default value is 1.0.
+    pub weight2: T,
+}
+
+impl<Source1, Source2, T> WeightedAdd<Source1, Source2, T>
+    where T: Float,
+{
+    pub fn new(source1: Source1, source2: Source2) -> WeightedAdd<Source1, Source2, T> {
+        WeightedAdd {
+            source1: source1,
+            source2: source2,
+            weight1: T::one(),
+            weight2: T::one(),
+        }
+    }
+
+    /// Sets the weights applied to the output values of the first and second
+    /// source modules.
+    pub fn set_weights(self, weight1: T, weight2: T) -> WeightedAdd<Source1, Source2, T> {
+        WeightedAdd {
+            weight1: weight1,
+            weight2: weight2,
+            ..self
+        }
+    }
+}
+
+impl<Source1, Source2, T> Reseedable for WeightedAdd<Source1, Source2, T>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<Source1, Source2, T, U> NoiseModule<T> for WeightedAdd<Source1, Source2, U>
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        self.source1.get(point) * self.weight1 + self.source2.get(point) * self.weight2
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+        let (lower1, upper1) = (lower1 * self.weight1, upper1 * self.weight1);
+        let (lower2, upper2) = (lower2 * self.weight2, upper2 * self.weight2);
+        (lower1.min(upper1) + lower2.min(upper2), lower1.max(upper1) + lower2.max(upper2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeightedAdd;
+    use modules::{Add, Constant, Perlin};
+    use test_utils::assert_modules_equal;
+    use NoiseModule;
+
+    #[test]
+    fn test_default_weights_match_add() {
+        assert_modules_equal(&WeightedAdd::new(Perlin::new(0), Perlin::new(1)),
+                             &Add::new(Perlin::new(0), Perlin::new(1)),
+                             ([-3.0, -3.0], [3.0, 3.0]),
+                             0.0);
+    }
+
+    #[test]
+    fn test_set_weights() {
+        let module = WeightedAdd::new(Constant::new(2.0), Constant::new(-4.0))
+            .set_weights(0.75, 0.125);
+        assert_eq!(module.get([0.3, 0.7]), 1.0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let module = WeightedAdd::new(Perlin::new(0), Constant::new(2.0)).set_weights(-0.5, 0.25);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&module), (0.0, 1.0));
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use modules::{Abs, Add, Blend, Clamp, MapValue, Ridge, ScaleBias, Typed};
+
+/// Extension methods for chaining noise modules.
+///
+/// Each method wraps `self` in the corresponding noise module, so a pipeline
+/// reads top-to-bottom instead of inside-out:
+///
+/// ```rust
+/// use noise::modules::{NoiseModuleExt, Perlin, RidgedMulti};
+///
+/// let terrain = RidgedMulti::<f64>::new()
+///     .add(Perlin::new(1))
+///     .scale_bias(0.5, 0.0)
+///     .clamp(-1.0, 1.0);
+/// ```
+///
+/// The trait is implemented for every type, since a blanket implementation
+/// over `NoiseModule<T>` would leave `T` unconstrained. The resulting modules
+/// only implement `NoiseModule` when `self` does.
+pub trait NoiseModuleExt: Sized {
+    /// Wraps this module in an `Abs` module.
+    fn abs(self) -> Abs<Self> {
+        Abs::new(self)
+    }
+
+    /// Wraps this module in a `Ridge` module with the default offset.
+    fn ridge<T: Float>(self) -> Ridge<Self, T> {
+        Ridge::new(self)
+    }
+
+    /// Wraps this module in a `Clamp` module with the given bounds.
+    fn clamp<T: Float>(self, lower: T, upper: T) -> Clamp<Self, T> {
+        Clamp::new(self).set_bounds(lower, upper)
+    }
+
+    /// Wraps this module in a `ScaleBias` module with the given scale and
+    /// bias.
+    fn scale_bias<T: Float>(self, scale: T, bias: T) -> ScaleBias<Self, T> {
+        ScaleBias::new(self).set_scale(scale).set_bias(bias)
+    }
+
+    /// Wraps this module in a `MapValue` module that passes its output value
+    /// through the function.
+    fn map_value<F>(self, function: F) -> MapValue<Self, F> {
+        MapValue::new(self, function)
+    }
+
+    /// Adds the output value of another module to this one.
+    fn add<Other>(self, other: Other) -> Add<Self, Other> {
+        Add::new(self, other)
+    }
+
+    /// Blends between this module and another, weighted by the output value
+    /// of the control module.
+    fn blend<Other, Control>(self, other: Other, control: Control) -> Blend<Self, Other, Control> {
+        Blend::new(self, other, control)
+    }
+
+    /// Wraps this module in a `Typed` module, fixing the float type of the
+    /// points it accepts.
+    fn typed<T>(self) -> Typed<Self, T> {
+        Typed::new(self)
+    }
+}
+
+impl<M> NoiseModuleExt for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::NoiseModuleExt;
+    use modules::{Abs, Add, Clamp, Constant, Perlin, ScaleBias};
+    use test_utils::assert_modules_equal;
+
+    #[test]
+    fn test_chain_matches_nested() {
+        let chained = Perlin::new(0)
+            .abs()
+            .scale_bias(2.0, -0.5)
+            .add(Constant::new(0.25))
+            .clamp(-0.5, 0.5);
+        let nested = Clamp::new(Add::new(ScaleBias::new(Abs::new(Perlin::new(0)))
+                                             .set_scale(2.0)
+                                             .set_bias(-0.5),
+                                         Constant::new(0.25)))
+            .set_bounds(-0.5, 0.5);
+
+        assert_modules_equal(&chained, &nested, ([-3.0, -2.0], [4.0, 5.0]), 0.0);
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that outputs the value of a closure over integer cell
+/// coordinates.
+///
+/// The input point is floored to the coordinates of the unit cell containing
+/// it, and the closure is called with those coordinates. Every point within a
+/// cell therefore outputs the same value. This is useful for bringing
+/// deterministic, grid-keyed game logic into a noise module graph.
+pub struct CellFn<F> {
+    /// Closure called with the coordinates of the cell containing the input
+    /// point.
+    pub function: F,
+}
+
+impl<F> CellFn<F> {
+    pub fn new(function: F) -> CellFn<F> {
+        CellFn { function: function }
+    }
+}
+
+impl<F> Reseedable for CellFn<F> {
+    fn reseed(&mut self, _seed: usize) {}
+}
+
+impl<F, T, U> NoiseModule<Point2<T>> for CellFn<F>
+    where F: Fn([isize; 2]) -> U,
+          T: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let cell = [math::cast(point[0].floor()), math::cast(point[1].floor())];
+        (self.function)(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CellFn;
+    use NoiseModule;
+
+    #[test]
+    fn test_constant_within_cell() {
+        let cells = CellFn::new(|cell: [isize; 2]| cell[0] * 100 + cell[1]);
+        for &(x, y) in [(0.0, 0.0), (0.25, 0.99), (0.999, 0.5)].iter() {
+            assert_eq!(cells.get([x + 3.0, y - 2.0]), 298);
+        }
+        assert_eq!(cells.get([-0.5, -0.5]), -101);
+        assert_eq!(cells.get([-1.0, 1.0]), -99);
+    }
+}
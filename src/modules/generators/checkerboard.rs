@@ -16,6 +16,7 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that outputs a checkerboard pattern.
 ///
@@ -24,25 +25,84 @@ use NoiseModule;
 /// and 1.0.
 ///
 /// This noise module is not very useful by itself, but it can be used for
-/// debugging purposes.
+/// debugging purposes. With anti-aliasing enabled, it can also be used as a
+/// procedural texture.
 #[derive(Clone, Copy, Debug)]
 pub struct Checkerboard {
     /// Controls the size of the block in 2^(size).
     pub size: usize,
+
+    /// Width of the box filter used to anti-alias the edges of the blocks.
+    /// A width of 0.0, the default, disables anti-aliasing.
+    pub filter_width: f64,
 }
 
 impl Checkerboard {
     pub fn new(size: usize) -> Checkerboard {
-        Checkerboard { size: 1 << size }
+        Checkerboard {
+            size: 1 << size,
+            filter_width: 0.0,
+        }
     }
-}
 
-fn fast_floor<T: Float>(x: T) -> usize {
-    if x > T::zero() {
-        math::cast(x)
-    } else {
-        math::cast(x - T::one())
+    /// Enables anti-aliasing, by averaging the pattern over a box of the given
+    /// width around each input point.
+    ///
+    /// The average is integrated analytically, so it is exact. When the box
+    /// spans many blocks, the output fades towards 0.0. A width of 0.0
+    /// disables anti-aliasing.
+    pub fn set_antialias(self, filter_width: f64) -> Checkerboard {
+        Checkerboard { filter_width: filter_width.max(0.0), ..self }
+    }
+
+    fn get_aliased<T: Float>(&self, point: &[T]) -> T {
+        let size = self.size as isize;
+        let result = point.iter()
+            .map(|&a| fast_floor(a))
+            .fold(0, |a, b| (a & size) ^ (b & size));
+
+        if result > 0 {
+            -T::one()
+        } else {
+            T::one()
+        }
     }
+
+    fn get_antialiased<T: Float>(&self, point: &[T]) -> T {
+        let width: T = math::cast(self.size);
+        let filter_width: T = math::cast(self.filter_width);
+
+        // The checkerboard is the product of a square wave along each axis, so
+        // the box-filtered checkerboard is the product of the box-filtered
+        // square waves.
+        point.iter()
+            .map(|&a| filtered_square_wave(a, width, filter_width))
+            .fold(T::one(), |a, b| a * b)
+    }
+}
+
+fn fast_floor<T: Float>(x: T) -> isize {
+    math::cast(x.floor())
+}
+
+/// Integral of a square wave that is 1.0 on [0, width) and -1.0 on
+/// [width, 2 * width), repeating. This is a triangle wave.
+fn square_wave_integral<T: Float>(x: T, width: T) -> T {
+    let period = width + width;
+    let t = x - (x / period).floor() * period;
+    if t < width { t } else { period - t }
+}
+
+/// Average of the square wave over the interval of the given width centered
+/// on `x`.
+fn filtered_square_wave<T: Float>(x: T, width: T, filter_width: T) -> T {
+    let half = filter_width * math::cast(0.5);
+    (square_wave_integral(x + half, width) - square_wave_integral(x - half, width)) /
+    filter_width
+}
+
+impl Reseedable for Checkerboard {
+    fn reseed(&mut self, _seed: usize) {}
 }
 
 // These impl's should be made generic over Point, but there is no higher Point type.
@@ -51,14 +111,10 @@ impl<T: Float> NoiseModule<Point2<T>> for Checkerboard {
     type Output = T;
 
     fn get(&self, point: Point2<T>) -> Self::Output {
-        let result = point.iter()
-            .map(|&a| fast_floor(a))
-            .fold(0, |a, b| (a & self.size) ^ (b & self.size));
-
-        if result > 0 {
-            -T::one()
+        if self.filter_width > 0.0 {
+            self.get_antialiased(&point)
         } else {
-            T::one()
+            self.get_aliased(&point)
         }
     }
 }
@@ -67,14 +123,10 @@ impl<T: Float> NoiseModule<Point3<T>> for Checkerboard {
     type Output = T;
 
     fn get(&self, point: Point3<T>) -> Self::Output {
-        let result = point.iter()
-            .map(|&a| fast_floor(a))
-            .fold(0, |a, b| (a & self.size) ^ (b & self.size));
-
-        if result > 0 {
-            -T::one()
+        if self.filter_width > 0.0 {
+            self.get_antialiased(&point)
         } else {
-            T::one()
+            self.get_aliased(&point)
         }
     }
 }
@@ -83,14 +135,36 @@ impl<T: Float> NoiseModule<Point4<T>> for Checkerboard {
     type Output = T;
 
     fn get(&self, point: Point4<T>) -> Self::Output {
-        let result = point.iter()
-            .map(|&a| fast_floor(a))
-            .fold(0, |a, b| (a & self.size) ^ (b & self.size));
-
-        if result > 0 {
-            -T::one()
+        if self.filter_width > 0.0 {
+            self.get_antialiased(&point)
         } else {
-            T::one()
+            self.get_aliased(&point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checkerboard;
+    use test_utils::sample_points2;
+    use NoiseModule;
+
+    #[test]
+    fn test_antialias_wide_filter_is_gray() {
+        let checkerboard = Checkerboard::new(0).set_antialias(64.5);
+        for &point in sample_points2(16).iter() {
+            let value = checkerboard.get(point);
+            assert!(value.abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_antialias_narrow_filter_inside_block() {
+        let aliased = Checkerboard::new(1);
+        let antialiased = aliased.set_antialias(0.1);
+        for &point in [[0.5, 0.5], [2.5, 0.5], [-1.0, 3.0], [-2.5, -0.5]].iter() {
+            let expected: f64 = aliased.get(point);
+            assert!((antialiased.get(point) - expected).abs() < 1e-12);
         }
     }
 }
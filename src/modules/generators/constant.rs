@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that outputs a constant value.
 ///
@@ -32,6 +33,14 @@ impl<T: Float> Constant<T> {
     pub fn new(value: T) -> Constant<T> {
         Constant { value: value }
     }
+
+    pub fn set_value(self, value: T) -> Constant<T> {
+        Constant { value: value }
+    }
+}
+
+impl<T: Float> Reseedable for Constant<T> {
+    fn reseed(&mut self, _seed: usize) {}
 }
 
 impl<T, U> NoiseModule<U> for Constant<T>
@@ -43,4 +52,8 @@ impl<T, U> NoiseModule<U> for Constant<T>
     fn get(&self, _point: U) -> Self::Output {
         self.value
     }
+
+    fn bounds(&self) -> (T, T) {
+        (self.value, self.value)
+    }
 }
@@ -16,13 +16,18 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that outputs concentric rings, cylinders, or spheres.
 ///
 /// This noise module outputs concentric rings, cylinders, or spheres centered
 /// on the origin. The cylinders are oriented along the z axis similar to the
-/// concentric rings of a tree. Each cylinder extends infinitely along the y
-/// axis.
+/// concentric rings of a tree. Each cylinder extends infinitely along the z
+/// axis, so the output only depends on the distance from the origin in the
+/// xy plane.
+///
+/// The output value is 1.0 on cylinders of integer radius (divided by the
+/// frequency), and falls to -1.0 halfway between them.
 #[derive(Clone, Copy, Debug)]
 pub struct Cylinders<T: Float> {
     /// Frequency of the concentric objects.
@@ -33,6 +38,16 @@ impl<T: Float> Cylinders<T> {
     pub fn new(v: T) -> Cylinders<T> {
         Cylinders { frequency: v }
     }
+
+    /// Sets the frequency of the concentric cylinders. Increasing the
+    /// frequency moves the cylinders closer together.
+    pub fn set_frequency(self, frequency: T) -> Cylinders<T> {
+        Cylinders { frequency: frequency }
+    }
+}
+
+impl<T: Float> Reseedable for Cylinders<T> {
+    fn reseed(&mut self, _seed: usize) {}
 }
 
 impl<T: Float> NoiseModule<Point2<T>> for Cylinders<T> {
@@ -40,8 +55,10 @@ impl<T: Float> NoiseModule<Point2<T>> for Cylinders<T> {
 
     fn get(&self, point: Point2<T>) -> Self::Output {
         let x = point[0] * self.frequency;
+        let y = point[1] * self.frequency;
 
-        let dist_from_smaller_sphere = x - x.floor();
+        let dist_from_center = (x * x + y * y).sqrt();
+        let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
         let dist_from_larger_sphere = T::one() - dist_from_smaller_sphere;
         let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
         T::one() - (nearest_dist * math::cast(4.0))
@@ -78,3 +95,53 @@ impl<T: Float> NoiseModule<Point4<T>> for Cylinders<T> {
         T::one() - (nearest_dist * math::cast(4.0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Cylinders;
+    use NoiseModule;
+
+    #[test]
+    fn test_peaks_on_integer_radii() {
+        let cylinders = Cylinders::<f64>::new(1.0);
+        for radius in 1..5 {
+            for i in 0..8 {
+                let angle = i as f64 * 0.8;
+                let (sin, cos) = angle.sin_cos();
+                let on = [radius as f64 * cos, radius as f64 * sin];
+                let between = [(radius as f64 + 0.5) * cos, (radius as f64 + 0.5) * sin];
+                assert!((cylinders.get(on) - 1.0).abs() < 1e-12);
+                assert!((cylinders.get(between) + 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_frequency_scales_spacing() {
+        let cylinders = Cylinders::<f64>::new(1.0).set_frequency(4.0);
+        assert!((cylinders.get([0.25, 0.0]) - 1.0).abs() < 1e-12);
+        assert!((cylinders.get([0.0, 0.375]) + 1.0).abs() < 1e-12);
+
+        let unit = Cylinders::<f64>::new(1.0);
+        for i in 0..16 {
+            let point = [i as f64 * 0.21 - 1.3, i as f64 * -0.17 + 0.9];
+            let scaled = [point[0] * 4.0, point[1] * 4.0];
+            assert!((cylinders.get(point) - unit.get(scaled)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_depends_on_xy_distance() {
+        let cylinders = Cylinders::<f64>::new(1.3);
+        for i in 0..16 {
+            let (x, y) = (i as f64 * 0.21 - 1.3, i as f64 * -0.17 + 0.9);
+            let distance = (x * x + y * y).sqrt();
+
+            // The output only depends on the distance from the z axis.
+            let value = cylinders.get([x, y]);
+            assert!((value - cylinders.get([distance, 0.0])).abs() < 1e-12);
+            assert!((value - cylinders.get([0.0, -distance])).abs() < 1e-12);
+            assert_eq!(cylinders.get([x, y, i as f64 * 3.7 - 20.0]), value);
+        }
+    }
+}
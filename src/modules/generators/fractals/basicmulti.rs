@@ -16,6 +16,8 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, Seedable};
+use super::{Normalization, Periodic};
 use modules::Perlin;
 
 /// Default noise seed for the BasicMulti noise module.
@@ -28,6 +30,8 @@ pub const DEFAULT_BASICMULTI_FREQUENCY: f32 = 2.0;
 pub const DEFAULT_BASICMULTI_LACUNARITY: f32 = 2.0;
 /// Default persistence for the BasicMulti noise module.
 pub const DEFAULT_BASICMULTI_PERSISTENCE: f32 = 0.5;
+/// Default amplitude cutoff for the BasicMulti noise module.
+pub const DEFAULT_BASICMULTI_AMPLITUDE_CUTOFF: f32 = 0.0;
 /// Maximum number of octaves for the BasicMulti noise module.
 pub const BASICMULTI_MAX_OCTAVES: usize = 32;
 
@@ -41,11 +45,18 @@ pub const BASICMULTI_MAX_OCTAVES: usize = 32;
 /// smooth. As the value moves further away from zero, higher frequencies will
 /// not be as damped and thus will grow more jagged as iteration progresses.
 ///
+///
+/// Like `Fbm`, the source of the octaves can be changed from `Perlin` with the
+/// `Source` type parameter.
 #[derive(Clone, Debug)]
-pub struct BasicMulti<T> {
+pub struct BasicMulti<T, Source = Perlin> {
     /// Seed.
     pub seed: usize,
 
+    /// Explicit seeds for the first octaves. Octaves beyond the end of this
+    /// list are seeded with `seed` plus the octave index.
+    pub seeds: Vec<usize>,
+
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -74,33 +85,82 @@ pub struct BasicMulti<T> {
     /// persistence produces "rougher" noise.
     pub persistence: T,
 
-    sources: Vec<Perlin>,
+    /// The amplitude below which octaves are no longer generated.
+    ///
+    /// Octaves whose amplitude falls below this value contribute little to
+    /// the output but still cost a full noise sample, so skipping them speeds
+    /// up modules with many octaves. A cutoff of zero generates every octave.
+    pub amplitude_cutoff: T,
+
+    /// Strategy used to bring the output value into the [-1,1] range. The
+    /// default, `Normalization::None`, halves the result.
+    pub normalization: Normalization<T>,
+
+    /// Number of units after which the noise repeats along each axis, if any.
+    /// See `set_period`.
+    pub period: Option<Point4<usize>>,
+
+    sources: Vec<Source>,
 }
 
 impl<T: Float> BasicMulti<T> {
     pub fn new() -> BasicMulti<T> {
+        BasicMulti::default()
+    }
+}
+
+impl<T, Source> Default for BasicMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn default() -> BasicMulti<T, Source> {
         BasicMulti {
             seed: DEFAULT_BASICMULTI_SEED,
+            seeds: Vec::new(),
             octaves: DEFAULT_BASICMULTI_OCTAVES,
             frequency: math::cast(DEFAULT_BASICMULTI_FREQUENCY),
             lacunarity: math::cast(DEFAULT_BASICMULTI_LACUNARITY),
             persistence: math::cast(DEFAULT_BASICMULTI_PERSISTENCE),
-            sources: super::build_sources(DEFAULT_BASICMULTI_SEED, DEFAULT_BASICMULTI_OCTAVES),
+            amplitude_cutoff: math::cast(DEFAULT_BASICMULTI_AMPLITUDE_CUTOFF),
+            normalization: Normalization::None,
+            period: None,
+            sources: super::build_sources(DEFAULT_BASICMULTI_SEED, &[], DEFAULT_BASICMULTI_OCTAVES),
         }
     }
+}
 
-    pub fn set_seed(self, seed: usize) -> BasicMulti<T> {
+impl<T, Source> BasicMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    pub fn set_seed(self, seed: usize) -> BasicMulti<T, Source> {
         if self.seed == seed {
             return self;
         }
         BasicMulti {
             seed: seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, &self.seeds, self.octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_octaves(self, mut octaves: usize) -> BasicMulti<T> {
+    /// Sets explicit seeds for the octaves, starting from the first.
+    ///
+    /// Octaves without an explicit seed fall back to `seed` plus the octave
+    /// index. By default every octave uses that scheme, so fractals with
+    /// nearby seeds share most of their octaves; explicit seeds avoid the
+    /// resulting correlation.
+    pub fn set_seeds(self, seeds: &[usize]) -> BasicMulti<T, Source> {
+        BasicMulti {
+            seeds: seeds.to_vec(),
+            sources: super::build_sources(self.seed, seeds, self.octaves),
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_octaves(self, mut octaves: usize) -> BasicMulti<T, Source> {
         if self.octaves == octaves {
             return self;
         } else if octaves > BASICMULTI_MAX_OCTAVES {
@@ -110,116 +170,208 @@ impl<T: Float> BasicMulti<T> {
         }
         BasicMulti {
             octaves: octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::resize_sources(self.sources, self.seed, &self.seeds, octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_frequency(self, frequency: T) -> BasicMulti<T> {
-        BasicMulti { frequency: frequency, ..self }
+    pub fn set_frequency(self, frequency: T) -> BasicMulti<T, Source> {
+        BasicMulti { frequency: frequency, ..self }.update_period()
     }
 
-    pub fn set_lacunarity(self, lacunarity: T) -> BasicMulti<T> {
-        BasicMulti { lacunarity: lacunarity, ..self }
+    pub fn set_lacunarity(self, lacunarity: T) -> BasicMulti<T, Source> {
+        BasicMulti { lacunarity: lacunarity, ..self }.update_period()
     }
 
-    pub fn set_persistence(self, persistence: T) -> BasicMulti<T> {
+    pub fn set_persistence(self, persistence: T) -> BasicMulti<T, Source> {
         BasicMulti { persistence: persistence, ..self }
     }
+
+    pub fn set_amplitude_cutoff(self, amplitude_cutoff: T) -> BasicMulti<T, Source> {
+        BasicMulti { amplitude_cutoff: amplitude_cutoff, ..self }
+    }
+
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. A period of 0 disables tiling along that
+    /// axis.
+    ///
+    /// Each octave's source wraps at the period scaled by that octave's
+    /// frequency, rounded to the nearest lattice cell. The noise therefore
+    /// only tiles exactly when every scaled period is an integer, which holds
+    /// for integer frequencies and lacunarities.
+    pub fn set_period(self, period: Point4<usize>) -> BasicMulti<T, Source> {
+        let periodic = period.iter().any(|&p| p > 0);
+        BasicMulti {
+            period: if periodic { Some(period) } else { None },
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_normalization(self, normalization: Normalization<T>) -> BasicMulti<T, Source> {
+        BasicMulti { normalization: normalization, ..self }
+    }
+
+    fn update_period(mut self) -> BasicMulti<T, Source> {
+        super::apply_period(&mut self.sources, self.period, self.frequency, self.lacunarity);
+        self
+    }
 }
 
-/// 2-dimensional BasicMulti noise
-impl<T: Float> NoiseModule<Point2<T>> for BasicMulti<T> {
-    type Output = T;
+#[cfg(feature = "serde")]
+impl_fractal_serde!(BasicMulti);
 
-    fn get(&self, mut point: Point2<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+impl_multifractal!(BasicMulti);
 
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Raise the spatial frequency.
-            point = math::mul2(point, self.lacunarity);
+impl<T, Source> Seedable for BasicMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn set_seed(self, seed: usize) -> BasicMulti<T, Source> {
+        BasicMulti::set_seed(self, seed)
+    }
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl<T, Source> Reseedable for BasicMulti<T, Source>
+    where Source: Seedable + Clone,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        super::reseed_sources(&mut self.sources, seed, &mut self.seeds);
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+impl<T: Float, Source> BasicMulti<T, Source> {
+    /// Samples the octaves at the point, which is already scaled by the
+    /// frequency, and scales the result.
+    fn sample<P>(&self, point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let mut result = T::zero();
+        super::accumulate(&self.sources,
+                          point,
+                          scale,
+                          self.lacunarity,
+                          self.persistence,
+                          self.amplitude_cutoff,
+                          |x, signal, amplitude| {
+                              // The first octave is unscaled.
+                              if x == 0 {
+                                  result = signal;
+                                  return;
+                              }
+
+                              // Scale the amplitude appropriately for this
+                              // frequency, then by the current 'altitude' of
+                              // the function.
+                              let signal = signal * amplitude * result;
+                              result = result + signal;
+                          });
+        self.scale_result(result)
+    }
 
-            // Scale the signal by the current 'altitude' of the function.
-            signal = signal * result;
+    /// Scales the accumulated result of the octaves into the [-1,1] range,
+    /// according to the normalization strategy.
+    fn scale_result(&self, result: T) -> T {
+        let scaled = result * math::cast(0.5);
+        let theoretical = result / self.max_value();
+        super::normalize(self.normalization, scaled, theoretical)
+    }
 
-            // Add signal to result.
-            result = result + signal;
-        }
+    /// Returns the largest magnitude the accumulated result can reach.
+    fn max_value(&self) -> T {
+        // Each octave scales the result by at most (1 + amplitude).
+        (1..self.octaves)
+            .map(|x| T::one() + self.persistence.abs().powi(math::cast(x)))
+            .fold(T::one(), |a, b| a * b)
+    }
 
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
+    /// Returns the range of the output values, which depends on the
+    /// normalization strategy.
+    fn output_bounds(&self) -> (T, T) {
+        super::bounds(self.normalization, self.max_value() * math::cast(0.5))
     }
 }
 
-/// 3-dimensional BasicMulti noise
-impl<T: Float> NoiseModule<Point3<T>> for BasicMulti<T> {
+/// 2-dimensional BasicMulti noise
+impl<T, Source> NoiseModule<Point2<T>> for BasicMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point2<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point3<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+    fn get(&self, point: Point2<T>) -> T {
+        self.sample(math::mul2(point, self.frequency), math::mul2)
+    }
 
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Raise the spatial frequency.
-            point = math::mul3(point, self.lacunarity);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+/// 3-dimensional BasicMulti noise
+impl<T, Source> NoiseModule<Point3<T>> for BasicMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point3<T>, Output = T>,
+{
+    type Output = T;
 
-            // Scale the signal by the current 'altitude' of the function.
-            signal = signal * result;
+    fn get(&self, point: Point3<T>) -> T {
+        self.sample(math::mul3(point, self.frequency), math::mul3)
+    }
 
-            // Add signal to result.
-            result = result + signal;
-        }
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
     }
 }
 
 /// 4-dimensional BasicMulti noise
-impl<T: Float> NoiseModule<Point4<T>> for BasicMulti<T> {
+impl<T, Source> NoiseModule<Point4<T>> for BasicMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point4<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point4<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point);
-
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Raise the spatial frequency.
-            point = math::mul4(point, self.lacunarity);
+    fn get(&self, point: Point4<T>) -> T {
+        self.sample(math::mul4(point, self.frequency), math::mul4)
+    }
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Scale the signal by the current 'altitude' of the function.
-            signal = signal * result;
+#[cfg(test)]
+mod tests {
+    use super::BasicMulti;
+    use super::super::tests::assert_pinned;
 
-            // Add signal to result.
-            result = result + signal;
-        }
+    // The default output at `PINNED_POINTS`.
+    const PINNED: [f64; 4] = [-0.013784762300635103,
+                              0.26626758954335633,
+                              -0.14192372845207787,
+                              -0.13921622682653273];
 
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
+    #[test]
+    fn test_pinned_output() {
+        assert_pinned(&BasicMulti::<f64>::new(), &BasicMulti::<f32>::new(), &PINNED);
     }
 }
@@ -16,7 +16,11 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, Seedable};
+use super::{Normalization, Periodic};
 use modules::Perlin;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Default noise seed for the Billow noise module.
 pub const DEFAULT_BILLOW_SEED: usize = 0;
@@ -28,6 +32,10 @@ pub const DEFAULT_BILLOW_FREQUENCY: f32 = 1.0;
 pub const DEFAULT_BILLOW_LACUNARITY: f32 = 2.0;
 /// Default persistence for the Billow noise module.
 pub const DEFAULT_BILLOW_PERSISTENCE: f32 = 0.5;
+/// Default bias for the Billow noise module.
+pub const DEFAULT_BILLOW_BIAS: f32 = -1.0;
+/// Default amplitude cutoff for the Billow noise module.
+pub const DEFAULT_BILLOW_AMPLITUDE_CUTOFF: f32 = 0.0;
 /// Maximum number of octaves for the Billow noise module.
 pub const BILLOW_MAX_OCTAVES: usize = 32;
 
@@ -38,11 +46,23 @@ pub const BILLOW_MAX_OCTAVES: usize = 32;
 /// This noise module is nearly identical to fBm noise, except this noise
 /// module modifes each octave with an absolute-value function. See the
 /// documentation for fBm for more information.
+///
+/// Each octave's signal _n_ is remapped to `2 * |n| + bias`. With the default
+/// bias of -1.0, this folds each octave back into the [-1,1] range, and the
+/// sum of the octaves is divided by `max_value()` so that the output stays in
+/// that range whatever the persistence.
+///
+/// Like `Fbm`, the source of the octaves can be changed from `Perlin` with the
+/// `Source` type parameter.
 #[derive(Clone, Debug)]
-pub struct Billow<T> {
+pub struct Billow<T, Source = Perlin> {
     /// Seed.
     pub seed: usize,
 
+    /// Explicit seeds for the first octaves. Octaves beyond the end of this
+    /// list are seeded with `seed` plus the octave index.
+    pub seeds: Vec<usize>,
+
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -71,150 +91,443 @@ pub struct Billow<T> {
     /// persistence produces "rougher" noise.
     pub persistence: T,
 
-    sources: Vec<Perlin>,
+    /// The amplitude below which octaves are no longer generated.
+    ///
+    /// Octaves whose amplitude falls below this value contribute little to
+    /// the output but still cost a full noise sample, so skipping them speeds
+    /// up modules with many octaves. A cutoff of zero generates every octave.
+    pub amplitude_cutoff: T,
+
+    /// The value added to each octave's doubled absolute signal. See
+    /// `set_bias`.
+    pub bias: T,
+
+    /// Strategy used to bring the output value into the [-1,1] range. The
+    /// default, `Normalization::None`, divides the sum of the octaves by
+    /// `max_value()`, the same as `Normalization::Theoretical`.
+    pub normalization: Normalization<T>,
+
+    /// Number of units after which the noise repeats along each axis, if any.
+    /// See `set_period`.
+    pub period: Option<Point4<usize>>,
+
+    max_value: T,
+
+    sources: Vec<Source>,
 }
 
 impl<T: Float> Billow<T> {
     pub fn new() -> Billow<T> {
+        Billow::default()
+    }
+}
+
+impl<T, Source> Default for Billow<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn default() -> Billow<T, Source> {
         Billow {
             seed: DEFAULT_BILLOW_SEED,
+            seeds: Vec::new(),
             octaves: DEFAULT_BILLOW_OCTAVE_COUNT,
             frequency: math::cast(DEFAULT_BILLOW_FREQUENCY),
             lacunarity: math::cast(DEFAULT_BILLOW_LACUNARITY),
             persistence: math::cast(DEFAULT_BILLOW_PERSISTENCE),
-            sources: super::build_sources(DEFAULT_BILLOW_SEED, DEFAULT_BILLOW_OCTAVE_COUNT),
+            amplitude_cutoff: math::cast(DEFAULT_BILLOW_AMPLITUDE_CUTOFF),
+            bias: math::cast(DEFAULT_BILLOW_BIAS),
+            normalization: Normalization::None,
+            period: None,
+            max_value: T::zero(),
+            sources: super::build_sources(DEFAULT_BILLOW_SEED, &[], DEFAULT_BILLOW_OCTAVE_COUNT),
         }
+        .update_max_value()
     }
+}
 
-    pub fn set_seed(self, seed: usize) -> Billow<T> {
+impl<T, Source> Billow<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    pub fn set_seed(self, seed: usize) -> Billow<T, Source> {
         if self.seed == seed {
             return self;
         }
         Billow {
             seed: seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, &self.seeds, self.octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_octaves(self, mut octaves: usize) -> Billow<T> {
+    /// Sets explicit seeds for the octaves, starting from the first.
+    ///
+    /// Octaves without an explicit seed fall back to `seed` plus the octave
+    /// index. By default every octave uses that scheme, so fractals with
+    /// nearby seeds share most of their octaves; explicit seeds avoid the
+    /// resulting correlation.
+    pub fn set_seeds(self, seeds: &[usize]) -> Billow<T, Source> {
+        Billow {
+            seeds: seeds.to_vec(),
+            sources: super::build_sources(self.seed, seeds, self.octaves),
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_octaves(self, mut octaves: usize) -> Billow<T, Source> {
         if self.octaves == octaves {
             return self;
         } else if octaves > BILLOW_MAX_OCTAVES {
             octaves = BILLOW_MAX_OCTAVES;
+        } else if octaves < 1 {
+            octaves = 1;
         }
         Billow {
             octaves: octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::resize_sources(self.sources, self.seed, &self.seeds, octaves),
             ..self
         }
+        .update_period()
+        .update_max_value()
     }
 
-    pub fn set_frequency(self, frequency: T) -> Billow<T> {
-        Billow { frequency: frequency, ..self }
+    pub fn set_frequency(self, frequency: T) -> Billow<T, Source> {
+        Billow { frequency: frequency, ..self }.update_period()
     }
 
-    pub fn set_lacunarity(self, lacunarity: T) -> Billow<T> {
-        Billow { lacunarity: lacunarity, ..self }
+    pub fn set_lacunarity(self, lacunarity: T) -> Billow<T, Source> {
+        Billow { lacunarity: lacunarity, ..self }.update_period()
     }
 
-    pub fn set_persistence(self, persistence: T) -> Billow<T> {
-        Billow { persistence: persistence, ..self }
+    pub fn set_persistence(self, persistence: T) -> Billow<T, Source> {
+        Billow { persistence: persistence, ..self }.update_max_value()
     }
-}
 
-/// 2-dimensional Billow noise
-impl<T: Float> NoiseModule<Point2<T>> for Billow<T> {
-    type Output = T;
+    pub fn set_amplitude_cutoff(self, amplitude_cutoff: T) -> Billow<T, Source> {
+        Billow { amplitude_cutoff: amplitude_cutoff, ..self }
+    }
 
-    fn get(&self, mut point: Point2<T>) -> T {
-        let mut result = T::zero();
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. A period of 0 disables tiling along that
+    /// axis.
+    ///
+    /// Each octave's source wraps at the period scaled by that octave's
+    /// frequency, rounded to the nearest lattice cell. The noise therefore
+    /// only tiles exactly when every scaled period is an integer, which holds
+    /// for integer frequencies and lacunarities.
+    pub fn set_period(self, period: Point4<usize>) -> Billow<T, Source> {
+        let periodic = period.iter().any(|&p| p > 0);
+        Billow {
+            period: if periodic { Some(period) } else { None },
+            ..self
+        }
+        .update_period()
+    }
 
-        point = math::mul2(point, self.frequency);
+    /// Sets the value added to each octave's doubled absolute signal.
+    ///
+    /// The default of -1.0 centers each octave on zero. Raising the bias
+    /// lifts the troughs between the billows, and lowering it deepens them.
+    pub fn set_bias(self, bias: T) -> Billow<T, Source> {
+        Billow { bias: bias, ..self }.update_max_value()
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+    pub fn set_normalization(self, normalization: Normalization<T>) -> Billow<T, Source> {
+        Billow { normalization: normalization, ..self }
+    }
 
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = signal.abs().mul_add(math::cast(2.0), -T::one());
+    /// Returns the largest magnitude the octaves can theoretically sum to,
+    /// which is the value the output is divided by.
+    ///
+    /// Each octave's signal lies between `bias` and `2 + bias`, so octave _i_
+    /// contributes at most `max(|bias|, |2 + bias|)` times its amplitude of
+    /// `persistence^i`. With the default parameters this is 1.96875.
+    pub fn max_value(&self) -> T {
+        self.max_value
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    fn update_max_value(self) -> Billow<T, Source> {
+        let two: T = math::cast(2.0);
+        let signal = self.bias.abs().max((two + self.bias).abs());
+        let max_value = super::amplitude_sum(self.persistence, 0..self.octaves) * signal;
+        Billow { max_value: max_value, ..self }
+    }
+
+    fn update_period(mut self) -> Billow<T, Source> {
+        super::apply_period(&mut self.sources, self.period, self.frequency, self.lacunarity);
+        self
+    }
+}
 
-            // Add the signal to the result.
-            result = result + signal;
+/// The parameters of a `Billow` module that are serialized.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BillowParams<T: Float> {
+    #[serde(flatten)]
+    fractal: super::FractalParams<T>,
+    #[serde(default = "default_bias")]
+    bias: T,
+}
 
-            // Increase the frequency for the next octave.
-            point = math::mul2(point, self.lacunarity);
-        }
+#[cfg(feature = "serde")]
+fn default_bias<T: Float>() -> T {
+    math::cast(DEFAULT_BILLOW_BIAS)
+}
 
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
+#[cfg(feature = "serde")]
+impl<T, Source> Serialize for Billow<T, Source>
+    where T: Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = BillowParams {
+            fractal: super::FractalParams {
+                seed: self.seed,
+                seeds: self.seeds.clone(),
+                octaves: self.octaves,
+                frequency: self.frequency,
+                lacunarity: self.lacunarity,
+                persistence: self.persistence,
+                amplitude_cutoff: self.amplitude_cutoff,
+                normalization: self.normalization,
+                period: self.period,
+            },
+            bias: self.bias,
+        };
+        params.serialize(serializer)
     }
 }
 
-/// 3-dimensional Billow noise
-impl<T: Float> NoiseModule<Point3<T>> for Billow<T> {
-    type Output = T;
+#[cfg(feature = "serde")]
+impl<'de, T, Source> Deserialize<'de> for Billow<T, Source>
+    where T: Float + Deserialize<'de>,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Billow<T, Source>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let params = BillowParams::deserialize(deserializer)?;
+        let fractal = params.fractal;
+        // The octaves are added with `set_octaves`, so that the count is
+        // clamped exactly as when the module is built in code.
+        let billow = Billow {
+            sources: super::build_sources(fractal.seed, &fractal.seeds, 1),
+            seed: fractal.seed,
+            seeds: fractal.seeds,
+            octaves: 1,
+            frequency: fractal.frequency,
+            lacunarity: fractal.lacunarity,
+            persistence: fractal.persistence,
+            amplitude_cutoff: fractal.amplitude_cutoff,
+            bias: params.bias,
+            normalization: fractal.normalization,
+            period: fractal.period,
+            max_value: T::zero(),
+        };
+        Ok(billow.set_octaves(fractal.octaves).update_max_value().update_period())
+    }
+}
+
+impl_multifractal!(Billow);
+
+impl<T, Source> Seedable for Billow<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn set_seed(self, seed: usize) -> Billow<T, Source> {
+        Billow::set_seed(self, seed)
+    }
 
-    fn get(&self, mut point: Point3<T>) -> T {
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl<T, Source> Reseedable for Billow<T, Source>
+    where Source: Seedable + Clone,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        super::reseed_sources(&mut self.sources, seed, &mut self.seeds);
+    }
+}
+
+impl<T: Float, Source> Billow<T, Source> {
+    /// Samples the octaves at the point, which is already scaled by the
+    /// frequency, and scales the result.
+    fn sample<P>(&self, point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
         let mut result = T::zero();
+        super::accumulate(&self.sources,
+                          point,
+                          scale,
+                          self.lacunarity,
+                          self.persistence,
+                          self.amplitude_cutoff,
+                          |_, signal, amplitude| {
+                              // Take the abs of the signal, then scale and
+                              // shift it by the bias.
+                              let signal = signal.abs().mul_add(math::cast(2.0), self.bias);
+
+                              // Scale the amplitude appropriately for this
+                              // frequency, and add the signal to the result.
+                              result = result + signal * amplitude;
+                          });
+        self.scale_result(result)
+    }
 
-        point = math::mul3(point, self.frequency);
+    /// Scales the accumulated result of the octaves into the [-1,1] range,
+    /// according to the normalization strategy.
+    fn scale_result(&self, result: T) -> T {
+        let scaled = result / self.max_value;
+        super::normalize(self.normalization, scaled, scaled)
+    }
 
+    /// Returns the range of the output values, which depends on the bias and
+    /// the normalization strategy.
+    fn output_bounds(&self) -> (T, T) {
+        let two: T = math::cast(2.0);
+        let mut lower = T::zero();
+        let mut upper = T::zero();
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+            // Skip the octaves that `accumulate` skips.
+            let amplitude = self.persistence.powi(math::cast(x));
+            if x > 0 && amplitude.abs() < self.amplitude_cutoff {
+                break;
+            }
+
+            // Each octave's signal lies between `bias` and `2 + bias`.
+            let (a, b) = (self.bias * amplitude, (two + self.bias) * amplitude);
+            lower = lower + a.min(b);
+            upper = upper + a.max(b);
+        }
+        super::scaled_bounds(self.normalization, lower / self.max_value, upper / self.max_value)
+    }
+}
+
+/// 2-dimensional Billow noise
+impl<T, Source> NoiseModule<Point2<T>> for Billow<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point2<T>, Output = T>,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> T {
+        self.sample(math::mul2(point, self.frequency), math::mul2)
+    }
 
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = signal.abs().mul_add(math::cast(2.0), -T::one());
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Add the signal to the result.
-            result = result + signal;
+/// 3-dimensional Billow noise
+impl<T, Source> NoiseModule<Point3<T>> for Billow<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point3<T>, Output = T>,
+{
+    type Output = T;
 
-            // Increase the frequency for the next octave.
-            point = math::mul3(point, self.lacunarity);
-        }
+    fn get(&self, point: Point3<T>) -> T {
+        self.sample(math::mul3(point, self.frequency), math::mul3)
+    }
 
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
     }
 }
 
 /// 4-dimensional Billow noise
-impl<T: Float> NoiseModule<Point4<T>> for Billow<T> {
+impl<T, Source> NoiseModule<Point4<T>> for Billow<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point4<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point4<T>) -> T {
-        let mut result = T::zero();
+    fn get(&self, point: Point4<T>) -> T {
+        self.sample(math::mul4(point, self.frequency), math::mul4)
+    }
 
-        point = math::mul4(point, self.frequency);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = signal.abs().mul_add(math::cast(2.0), -T::one());
+#[cfg(test)]
+mod tests {
+    use super::{BILLOW_MAX_OCTAVES, Billow};
+    use super::super::Normalization;
+    use super::super::tests::{assert_in_range, assert_pinned, points};
+    use NoiseModule;
+
+    // The default output at `PINNED_POINTS`.
+    const PINNED: [f64; 4] = [-0.5598078454243486,
+                              -0.46767058526285504,
+                              -0.21716711013851822,
+                              -0.514954276804102];
+
+    #[test]
+    fn test_pinned_output() {
+        assert_pinned(&Billow::<f64>::new(), &Billow::<f32>::new(), &PINNED);
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    #[test]
+    fn test_normalization() {
+        let billow = Billow::new();
+        assert_eq!(billow.max_value(), 1.96875);
+        assert_in_range(&billow);
+        assert_in_range(&billow.clone().set_persistence(1.0));
+        assert_in_range(&billow.clone().set_octaves(2).set_persistence(1.5));
+        assert_in_range(&billow.clone().set_bias(-0.5));
+        assert_in_range(&billow.clone().set_bias(-3.0));
+
+        let raised = billow.clone().set_bias(0.0);
+        assert_eq!(raised.max_value(), billow.max_value() * 2.0);
+        for &point in points().iter() {
+            assert!(raised.get(point) >= 0.0);
+        }
+    }
 
-            // Add the signal to the output value.
-            result = result + signal;
+    #[test]
+    fn test_octaves_clamped() {
+        let billow = Billow::new().set_octaves(0);
+        assert_eq!(billow.octaves, 1);
+        assert_in_range(&billow);
+        assert_eq!(Billow::<f64>::new().set_octaves(1000).octaves, BILLOW_MAX_OCTAVES);
+    }
 
-            // Increase the frequency for the next octave.
-            point = math::mul4(point, self.lacunarity);
+    #[test]
+    fn test_bounds() {
+        let bounds = |billow: &Billow<f64>| NoiseModule::<[f64; 3]>::bounds(billow);
+        let billow = Billow::new();
+        let (lower, upper) = bounds(&billow);
+        assert!((lower + 1.0).abs() < 1e-12 && (upper - 1.0).abs() < 1e-12);
+
+        // Raising the bias lifts the lower bound with the troughs.
+        let raised = billow.clone().set_bias(0.0);
+        let (lower, upper) = bounds(&raised);
+        assert!(lower.abs() < 1e-12 && (upper - 1.0).abs() < 1e-12);
+
+        let observed = billow.set_bias(-0.5).set_normalization(Normalization::Observed(0.0, 1.0));
+        let (lower, upper) = bounds(&observed);
+        for &point in points().iter() {
+            let value = observed.get(point);
+            assert!(value >= lower && value <= upper);
         }
-
-        // Scale the result to the [-1,1] range.
-        result * math::cast(0.5)
     }
 }
@@ -16,6 +16,8 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, Seedable};
+use super::{Normalization, Periodic};
 use modules::Perlin;
 
 // Default noise seed for the fBm noise module.
@@ -28,6 +30,8 @@ pub const DEFAULT_FBM_FREQUENCY: f32 = 1.0;
 pub const DEFAULT_FBM_LACUNARITY: f32 = 2.0;
 // Default Hurst exponent for the fBm noise module
 pub const DEFAULT_FBM_PERSISTENCE: f32 = 0.5;
+// Default amplitude cutoff for the fBm noise module.
+pub const DEFAULT_FBM_AMPLITUDE_CUTOFF: f32 = 0.0;
 // Maximum number of octaves for the fBm noise module.
 pub const FBM_MAX_OCTAVES: usize = 32;
 
@@ -45,11 +49,19 @@ pub const FBM_MAX_OCTAVES: usize = 32;
 ///
 /// fBm is the result of several noise functions of ever-increasing frequency
 /// and ever-decreasing amplitude.
+///
+/// Each octave is generated by `Perlin` noise by default. Any generator that
+/// implements `Seedable`, `Periodic`, `Default` and `Clone` can be used
+/// instead, e.g. `Fbm::<f64, Value>::default()`.
 #[derive(Clone, Debug)]
-pub struct Fbm<T> {
+pub struct Fbm<T, Source = Perlin> {
     /// Seed.
     pub seed: usize,
 
+    /// Explicit seeds for the first octaves. Octaves beyond the end of this
+    /// list are seeded with `seed` plus the octave index.
+    pub seeds: Vec<usize>,
+
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -78,33 +90,83 @@ pub struct Fbm<T> {
     /// persistence produces "rougher" noise.
     pub persistence: T,
 
-    sources: Vec<Perlin>,
+    /// The amplitude below which octaves are no longer generated.
+    ///
+    /// Octaves whose amplitude falls below this value contribute little to
+    /// the output but still cost a full noise sample, so skipping them speeds
+    /// up modules with many octaves. A cutoff of zero generates every octave.
+    pub amplitude_cutoff: T,
+
+    /// Strategy used to bring the output value into the [-1,1] range. The
+    /// default, `Normalization::None`, leaves the sum of the octaves unscaled,
+    /// so the output value may exceed [-1,1].
+    pub normalization: Normalization<T>,
+
+    /// Number of units after which the noise repeats along each axis, if any.
+    /// See `set_period`.
+    pub period: Option<Point4<usize>>,
+
+    sources: Vec<Source>,
 }
 
 impl<T: Float> Fbm<T> {
     pub fn new() -> Fbm<T> {
+        Fbm::default()
+    }
+}
+
+impl<T, Source> Default for Fbm<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn default() -> Fbm<T, Source> {
         Fbm {
             seed: DEFAULT_FBM_SEED,
+            seeds: Vec::new(),
             octaves: DEFAULT_FBM_OCTAVE_COUNT,
             frequency: math::cast(DEFAULT_FBM_FREQUENCY),
             lacunarity: math::cast(DEFAULT_FBM_LACUNARITY),
             persistence: math::cast(DEFAULT_FBM_PERSISTENCE),
-            sources: super::build_sources(DEFAULT_FBM_SEED, DEFAULT_FBM_OCTAVE_COUNT),
+            amplitude_cutoff: math::cast(DEFAULT_FBM_AMPLITUDE_CUTOFF),
+            normalization: Normalization::None,
+            period: None,
+            sources: super::build_sources(DEFAULT_FBM_SEED, &[], DEFAULT_FBM_OCTAVE_COUNT),
         }
     }
+}
 
-    pub fn set_seed(self, seed: usize) -> Fbm<T> {
+impl<T, Source> Fbm<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    pub fn set_seed(self, seed: usize) -> Fbm<T, Source> {
         if self.seed == seed {
             return self;
         }
         Fbm {
             seed: seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, &self.seeds, self.octaves),
+            ..self
+        }
+        .update_period()
+    }
+
+    /// Sets explicit seeds for the octaves, starting from the first.
+    ///
+    /// Octaves without an explicit seed fall back to `seed` plus the octave
+    /// index. By default every octave uses that scheme, so fractals with
+    /// nearby seeds share most of their octaves; explicit seeds avoid the
+    /// resulting correlation.
+    pub fn set_seeds(self, seeds: &[usize]) -> Fbm<T, Source> {
+        Fbm {
+            seeds: seeds.to_vec(),
+            sources: super::build_sources(self.seed, seeds, self.octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_octaves(self, mut octaves: usize) -> Fbm<T> {
+    pub fn set_octaves(self, mut octaves: usize) -> Fbm<T, Source> {
         if self.octaves == octaves {
             return self;
         } else if octaves > FBM_MAX_OCTAVES {
@@ -114,101 +176,336 @@ impl<T: Float> Fbm<T> {
         }
         Fbm {
             octaves: octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::resize_sources(self.sources, self.seed, &self.seeds, octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_frequency(self, frequency: T) -> Fbm<T> {
-        Fbm { frequency: frequency, ..self }
+    pub fn set_frequency(self, frequency: T) -> Fbm<T, Source> {
+        Fbm { frequency: frequency, ..self }.update_period()
     }
 
-    pub fn set_lacunarity(self, lacunarity: T) -> Fbm<T> {
-        Fbm { lacunarity: lacunarity, ..self }
+    pub fn set_lacunarity(self, lacunarity: T) -> Fbm<T, Source> {
+        Fbm { lacunarity: lacunarity, ..self }.update_period()
     }
 
-    pub fn set_persistence(self, persistence: T) -> Fbm<T> {
+    pub fn set_persistence(self, persistence: T) -> Fbm<T, Source> {
         Fbm { persistence: persistence, ..self }
     }
+
+    pub fn set_amplitude_cutoff(self, amplitude_cutoff: T) -> Fbm<T, Source> {
+        Fbm { amplitude_cutoff: amplitude_cutoff, ..self }
+    }
+
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. A period of 0 disables tiling along that
+    /// axis.
+    ///
+    /// Each octave's source wraps at the period scaled by that octave's
+    /// frequency, rounded to the nearest lattice cell. The noise therefore
+    /// only tiles exactly when every scaled period is an integer, which holds
+    /// for integer frequencies and lacunarities.
+    pub fn set_period(self, period: Point4<usize>) -> Fbm<T, Source> {
+        let periodic = period.iter().any(|&p| p > 0);
+        Fbm {
+            period: if periodic { Some(period) } else { None },
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_normalization(self, normalization: Normalization<T>) -> Fbm<T, Source> {
+        Fbm { normalization: normalization, ..self }
+    }
+
+    fn update_period(mut self) -> Fbm<T, Source> {
+        super::apply_period(&mut self.sources, self.period, self.frequency, self.lacunarity);
+        self
+    }
+
+    /// Returns a copy of this module that only generates the first `octaves`
+    /// octaves.
+    ///
+    /// The octaves that are kept are identical to the corresponding octaves of
+    /// this module, so the copy outputs the low-frequency "shape" of the
+    /// noise. The copy is a module in its own right, so under
+    /// `Normalization::Theoretical` it is scaled by its own, smaller,
+    /// `max_value`. Use `get_coarse` for the exact contribution of the octaves
+    /// to the output of this module.
+    pub fn coarse(&self, octaves: usize) -> Fbm<T, Source> {
+        let octaves = octaves.max(1).min(self.octaves);
+        Fbm {
+            seed: self.seed,
+            seeds: self.seeds.clone(),
+            octaves: octaves,
+            frequency: self.frequency,
+            lacunarity: self.lacunarity,
+            persistence: self.persistence,
+            amplitude_cutoff: self.amplitude_cutoff,
+            normalization: self.normalization,
+            period: self.period,
+            sources: self.sources[..octaves].to_vec(),
+        }
+    }
+
 }
 
-/// 2-dimensional Fbm noise
-impl<T: Float> NoiseModule<Point2<T>> for Fbm<T> {
-    type Output = T;
+#[cfg(feature = "serde")]
+impl_fractal_serde!(Fbm);
 
-    fn get(&self, mut point: Point2<T>) -> T {
-        let mut result = T::zero();
+impl_multifractal!(Fbm);
 
-        point = math::mul2(point, self.frequency);
+impl<T, Source> Seedable for Fbm<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn set_seed(self, seed: usize) -> Fbm<T, Source> {
+        Fbm::set_seed(self, seed)
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+impl<T, Source> Reseedable for Fbm<T, Source>
+    where Source: Seedable + Clone,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        super::reseed_sources(&mut self.sources, seed, &mut self.seeds);
+    }
+}
 
-            // Add the signal to the result.
-            result = result + signal;
+impl<T: Float, Source> Fbm<T, Source> {
+    /// Samples only the first `octaves` octaves of this module at the given
+    /// point, for cheaply sampling distant levels of detail.
+    ///
+    /// The octaves are scaled exactly as they are in the output of `get`, so
+    /// adding the contribution of the remaining octaves gives that output.
+    /// The result is not clamped under `Normalization::Clamp`, as that would
+    /// break the sum; only the full output is.
+    pub fn get_coarse<P>(&self, point: P, octaves: usize) -> T
+        where P: Copy + AsMut<[T]>,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let octaves = octaves.max(1).min(self.sources.len());
+        let point = super::scale_point(point, self.frequency);
+        let result = self.sum_octaves(&self.sources[..octaves], point, super::scale_point);
+        self.scale_result(result, super::unclamped(self.normalization))
+    }
 
-            // Increase the frequency for the next octave.
-            point = math::mul2(point, self.lacunarity);
-        }
+    /// Samples the octaves at the point, which is already scaled by the
+    /// frequency, and scales the result.
+    fn sample<P>(&self, point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let result = self.sum_octaves(&self.sources, point, scale);
+        self.scale_result(result, self.normalization)
+    }
 
+    /// Sums the octaves of the given sources at the point, which is already
+    /// scaled by the frequency.
+    fn sum_octaves<P>(&self, sources: &[Source], point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let mut result = T::zero();
+        super::accumulate(sources,
+                          point,
+                          scale,
+                          self.lacunarity,
+                          self.persistence,
+                          self.amplitude_cutoff,
+                          |_, signal, amplitude| {
+                              // Scale the amplitude appropriately for this
+                              // frequency, and add the signal to the result.
+                              result = result + signal * amplitude;
+                          });
         result
     }
+
+    /// Scales the accumulated result of the octaves into the [-1,1] range,
+    /// according to the normalization strategy.
+    fn scale_result(&self, result: T, normalization: Normalization<T>) -> T {
+        let scaled = result;
+        let theoretical = result / self.max_value();
+        super::normalize(normalization, scaled, theoretical)
+    }
+
+    /// Returns the range of the output values, which depends on the
+    /// normalization strategy.
+    fn output_bounds(&self) -> (T, T) {
+        super::bounds(self.normalization, self.max_value())
+    }
+
+    /// Returns the largest magnitude the octaves can theoretically sum to,
+    /// which is `|persistence|^x` summed over the octaves, since each octave's
+    /// signal lies in [-1,1].
+    ///
+    /// With the default `Normalization::None` the output is the unscaled sum,
+    /// so dividing by this value maps it into [-1,1]; this is what
+    /// `Normalization::Theoretical` does. With the default parameters this
+    /// is 1.96875.
+    pub fn max_value(&self) -> T {
+        super::amplitude_sum(self.persistence, 0..self.octaves)
+    }
 }
 
-/// 3-dimensional Fbm noise
-impl<T: Float> NoiseModule<Point3<T>> for Fbm<T> {
+/// 2-dimensional Fbm noise
+impl<T, Source> NoiseModule<Point2<T>> for Fbm<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point2<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point3<T>) -> T {
-        let mut result = T::zero();
+    fn get(&self, point: Point2<T>) -> T {
+        self.sample(math::mul2(point, self.frequency), math::mul2)
+    }
 
-        point = math::mul3(point, self.frequency);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+/// 3-dimensional Fbm noise
+impl<T, Source> NoiseModule<Point3<T>> for Fbm<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point3<T>, Output = T>,
+{
+    type Output = T;
 
-            // Add the signal to the result.
-            result = result + signal;
+    fn get(&self, point: Point3<T>) -> T {
+        self.sample(math::mul3(point, self.frequency), math::mul3)
+    }
 
-            // Increase the frequency for the next octave.
-            point = math::mul3(point, self.lacunarity);
-        }
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        result
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
     }
 }
 
 /// 4-dimensional Fbm noise
-impl<T: Float> NoiseModule<Point4<T>> for Fbm<T> {
+impl<T, Source> NoiseModule<Point4<T>> for Fbm<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point4<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point4<T>) -> T {
-        let mut result = T::zero();
+    fn get(&self, point: Point4<T>) -> T {
+        self.sample(math::mul4(point, self.frequency), math::mul4)
+    }
 
-        point = math::mul4(point, self.frequency);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+#[cfg(test)]
+mod tests {
+    use super::{Fbm, Normalization};
+    use super::super::tests::{assert_pinned, points};
+    use math;
+    use modules::Perlin;
+    use test_utils::Counting;
+    use NoiseModule;
+
+    // The default output at `PINNED_POINTS`.
+    const PINNED: [f64; 4] = [-0.36319340774681974,
+                              0.057158544763901024,
+                              -0.7470805670932922,
+                              0.044579907237827815];
+
+    #[test]
+    fn test_pinned_output() {
+        assert_pinned(&Fbm::<f64>::new(), &Fbm::<f32>::new(), &PINNED);
+    }
 
-            // Add the signal to the result.
-            result = result + signal;
+    #[test]
+    fn test_max_value() {
+        let fbm = Fbm::new();
+        assert_eq!(fbm.max_value(), 1.96875);
+        assert_eq!(fbm.clone().set_octaves(3).set_persistence(-2.0).max_value(), 7.0);
 
-            // Increase the frequency for the next octave.
-            point = math::mul4(point, self.lacunarity);
+        let max_value = fbm.max_value();
+        for &point in points().iter() {
+            assert!(fbm.get(point).abs() <= max_value);
         }
+    }
 
-        result
+    #[test]
+    fn test_coarse_plus_remaining_octaves() {
+        let fbm = Fbm::<f64>::new().set_seed(7);
+        let max_value = fbm.max_value();
+
+        // The remaining octaves are scaled by the same factor as the coarse
+        // ones, which depends on the normalization.
+        let normalizations = [(Normalization::None, 1.0),
+                              (Normalization::Theoretical, 1.0 / max_value),
+                              (Normalization::Observed(-0.6, 0.9), 2.0 / 1.5)];
+        for &(normalization, factor) in &normalizations {
+            let fbm = fbm.clone().set_normalization(normalization);
+            for i in 0..16 {
+                let point = [i as f64 * 0.31 - 1.7, i as f64 * 0.53 + 0.2, -0.4];
+
+                let mut remaining = 0.0;
+                let mut frequency = fbm.frequency * fbm.lacunarity.powi(2);
+                for x in 2..fbm.octaves {
+                    let octave_point = math::mul3(point, frequency);
+                    let amplitude = fbm.persistence.powi(x as i32);
+                    remaining += Perlin::new(7 + x).get(octave_point) * amplitude;
+                    frequency = frequency * fbm.lacunarity;
+                }
+
+                let full = fbm.get(point);
+                let coarse = fbm.get_coarse(point, 2);
+                assert!((coarse + remaining * factor - full).abs() < 1e-12,
+                        "{:?}: {} + {} != {}", normalization, coarse, remaining * factor, full);
+            }
+        }
+    }
+
+    /// Returns the number of times the fractal samples its sources at a point.
+    fn count_samples(fbm: &Fbm<f64, Counting<Perlin>>) -> usize {
+        fbm.get([0.3, 0.7, -1.1]);
+        fbm.sources.iter().map(|source| source.samples()).sum()
+    }
+
+    #[test]
+    fn test_amplitude_cutoff() {
+        let full = Fbm::<f64, Counting<Perlin>>::default().set_octaves(32);
+        let cutoff = full.clone().set_amplitude_cutoff(1e-6);
+
+        // 0.5^19 is the last amplitude above the cutoff, so only the first 20
+        // octaves should be sampled.
+        assert_eq!(count_samples(&full), 32);
+        assert_eq!(count_samples(&cutoff), 20);
+
+        // The skipped octaves contribute little to the output.
+        for i in 0..16 {
+            let point = [i as f64 * 0.31 - 1.7, i as f64 * 0.53 + 0.2, -0.4];
+            assert!((cutoff.get(point) - full.get(point)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_complexity_estimate() {
+        let fine = Fbm::<f64>::new().set_octaves(8);
+        let coarse = Fbm::<f64>::new().set_octaves(2);
+        assert!(NoiseModule::<[f64; 3]>::complexity_estimate(&fine) >
+                NoiseModule::<[f64; 3]>::complexity_estimate(&coarse));
     }
 }
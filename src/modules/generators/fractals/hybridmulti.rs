@@ -16,7 +16,11 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, Seedable};
+use super::{Normalization, Periodic};
 use modules::Perlin;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Default noise seed for the BasicMulti noise module.
 pub const DEFAULT_HYBRIDMULTI_SEED: usize = 0;
@@ -28,6 +32,10 @@ pub const DEFAULT_HYBRIDMULTI_FREQUENCY: f32 = 2.0;
 pub const DEFAULT_HYBRIDMULTI_LACUNARITY: f32 = 2.0;
 /// Default persistence for the BasicMulti noise module.
 pub const DEFAULT_HYBRIDMULTI_PERSISTENCE: f32 = 0.25;
+/// Default offset for the HybridMulti noise module.
+pub const DEFAULT_HYBRIDMULTI_OFFSET: f32 = 0.0;
+/// Default amplitude cutoff for the HybridMulti noise module.
+pub const DEFAULT_HYBRIDMULTI_AMPLITUDE_CUTOFF: f32 = 0.0;
 /// Maximum number of octaves for the BasicMulti noise module.
 pub const HYBRIDMULTI_MAX_OCTAVES: usize = 32;
 
@@ -35,11 +43,18 @@ pub const HYBRIDMULTI_MAX_OCTAVES: usize = 32;
 ///
 /// The result of this multifractal noise is that valleys in the noise should
 /// have smooth bottoms at all altitudes.
+///
+/// Like `Fbm`, the source of the octaves can be changed from `Perlin` with the
+/// `Source` type parameter.
 #[derive(Clone, Debug)]
-pub struct HybridMulti<T> {
+pub struct HybridMulti<T, Source = Perlin> {
     /// Seed.
     pub seed: usize,
 
+    /// Explicit seeds for the first octaves. Octaves beyond the end of this
+    /// list are seeded with `seed` plus the octave index.
+    pub seeds: Vec<usize>,
+
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -68,33 +83,91 @@ pub struct HybridMulti<T> {
     /// persistence produces "rougher" noise.
     pub persistence: T,
 
-    sources: Vec<Perlin>,
+    /// The amplitude below which octaves are no longer generated.
+    ///
+    /// Octaves whose amplitude falls below this value contribute little to
+    /// the output but still cost a full noise sample, so skipping them speeds
+    /// up modules with many octaves. A cutoff of zero generates every octave.
+    pub amplitude_cutoff: T,
+
+    /// The value added to each octave's signal before it is weighted. See
+    /// `set_offset`.
+    pub offset: T,
+
+    /// Strategy used to bring the output value into the [-1,1] range. The
+    /// default, `Normalization::None`, triples the result.
+    pub normalization: Normalization<T>,
+
+    /// Number of units after which the noise repeats along each axis, if any.
+    /// See `set_period`.
+    pub period: Option<Point4<usize>>,
+
+    max_value: T,
+
+    sources: Vec<Source>,
 }
 
 impl<T: Float> HybridMulti<T> {
     pub fn new() -> HybridMulti<T> {
+        HybridMulti::default()
+    }
+}
+
+impl<T, Source> Default for HybridMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn default() -> HybridMulti<T, Source> {
         HybridMulti {
             seed: DEFAULT_HYBRIDMULTI_SEED,
+            seeds: Vec::new(),
             octaves: DEFAULT_HYBRIDMULTI_OCTAVES,
             frequency: math::cast(DEFAULT_HYBRIDMULTI_FREQUENCY),
             lacunarity: math::cast(DEFAULT_HYBRIDMULTI_LACUNARITY),
             persistence: math::cast(DEFAULT_HYBRIDMULTI_PERSISTENCE),
-            sources: super::build_sources(DEFAULT_HYBRIDMULTI_SEED, DEFAULT_HYBRIDMULTI_OCTAVES),
+            amplitude_cutoff: math::cast(DEFAULT_HYBRIDMULTI_AMPLITUDE_CUTOFF),
+            offset: math::cast(DEFAULT_HYBRIDMULTI_OFFSET),
+            normalization: Normalization::None,
+            period: None,
+            max_value: T::zero(),
+            sources: super::build_sources(DEFAULT_HYBRIDMULTI_SEED, &[], DEFAULT_HYBRIDMULTI_OCTAVES),
         }
+        .update_max_value()
     }
+}
 
-    pub fn set_seed(self, seed: usize) -> HybridMulti<T> {
+impl<T, Source> HybridMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    pub fn set_seed(self, seed: usize) -> HybridMulti<T, Source> {
         if self.seed == seed {
             return self;
         }
         HybridMulti {
             seed: seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, &self.seeds, self.octaves),
+            ..self
+        }
+        .update_period()
+    }
+
+    /// Sets explicit seeds for the octaves, starting from the first.
+    ///
+    /// Octaves without an explicit seed fall back to `seed` plus the octave
+    /// index. By default every octave uses that scheme, so fractals with
+    /// nearby seeds share most of their octaves; explicit seeds avoid the
+    /// resulting correlation.
+    pub fn set_seeds(self, seeds: &[usize]) -> HybridMulti<T, Source> {
+        HybridMulti {
+            seeds: seeds.to_vec(),
+            sources: super::build_sources(self.seed, seeds, self.octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_octaves(self, mut octaves: usize) -> HybridMulti<T> {
+    pub fn set_octaves(self, mut octaves: usize) -> HybridMulti<T, Source> {
         if self.octaves == octaves {
             return self;
         } else if octaves > HYBRIDMULTI_MAX_OCTAVES {
@@ -104,134 +177,336 @@ impl<T: Float> HybridMulti<T> {
         }
         HybridMulti {
             octaves: octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::resize_sources(self.sources, self.seed, &self.seeds, octaves),
             ..self
         }
+        .update_period()
+        .update_max_value()
     }
 
-    pub fn set_frequency(self, frequency: T) -> HybridMulti<T> {
-        HybridMulti { frequency: frequency, ..self }
+    pub fn set_frequency(self, frequency: T) -> HybridMulti<T, Source> {
+        HybridMulti { frequency: frequency, ..self }.update_period()
     }
 
-    pub fn set_lacunarity(self, lacunarity: T) -> HybridMulti<T> {
-        HybridMulti { lacunarity: lacunarity, ..self }
+    pub fn set_lacunarity(self, lacunarity: T) -> HybridMulti<T, Source> {
+        HybridMulti { lacunarity: lacunarity, ..self }.update_period()
     }
 
-    pub fn set_persistence(self, persistence: T) -> HybridMulti<T> {
-        HybridMulti { persistence: persistence, ..self }
+    pub fn set_persistence(self, persistence: T) -> HybridMulti<T, Source> {
+        HybridMulti { persistence: persistence, ..self }.update_max_value()
     }
-}
 
-/// 2-dimensional HybridMulti noise
-impl<T: Float> NoiseModule<Point2<T>> for HybridMulti<T> {
-    type Output = T;
+    pub fn set_amplitude_cutoff(self, amplitude_cutoff: T) -> HybridMulti<T, Source> {
+        HybridMulti { amplitude_cutoff: amplitude_cutoff, ..self }
+    }
 
-    fn get(&self, mut point: Point2<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
-        let mut weight = result;
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. A period of 0 disables tiling along that
+    /// axis.
+    ///
+    /// Each octave's source wraps at the period scaled by that octave's
+    /// frequency, rounded to the nearest lattice cell. The noise therefore
+    /// only tiles exactly when every scaled period is an integer, which holds
+    /// for integer frequencies and lacunarities.
+    pub fn set_period(self, period: Point4<usize>) -> HybridMulti<T, Source> {
+        let periodic = period.iter().any(|&p| p > 0);
+        HybridMulti {
+            period: if periodic { Some(period) } else { None },
+            ..self
+        }
+        .update_period()
+    }
 
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Prevent divergence.
-            if weight > T::one() {
-                weight = T::one();
-            }
+    /// Sets the offset added to each octave's signal before it is weighted,
+    /// as in Musgrave's original hybrid multifractal.
+    ///
+    /// The running weight is the product of the offset signals, so a larger
+    /// offset makes the weight saturate at 1.0 sooner, adding more detail to
+    /// the peaks, while a smaller one keeps the valleys smoother. The default
+    /// offset of 0.0 leaves the signals unchanged. Musgrave suggests 0.7 for
+    /// Perlin noise.
+    pub fn set_offset(self, offset: T) -> HybridMulti<T, Source> {
+        HybridMulti { offset: offset, ..self }.update_max_value()
+    }
 
-            // Raise the spatial frequency.
-            point = math::mul2(point, self.lacunarity);
+    pub fn set_normalization(self, normalization: Normalization<T>) -> HybridMulti<T, Source> {
+        HybridMulti { normalization: normalization, ..self }
+    }
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+    /// Returns the largest magnitude the octaves can theoretically sum to,
+    /// which is the value `Normalization::Theoretical` divides the output by.
+    ///
+    /// Each offset signal is at most `1 + |offset|` in magnitude, and is
+    /// scaled by the octave's amplitude and by a weight whose magnitude
+    /// saturates at 1.0. The first octave's amplitude is the persistence, and octave _i_
+    /// after it has an amplitude of `persistence^i`. With the default
+    /// parameters this is about 0.583.
+    pub fn max_value(&self) -> T {
+        self.max_value
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    fn update_max_value(self) -> HybridMulti<T, Source> {
+        let amplitudes = self.persistence.abs() +
+                         super::amplitude_sum(self.persistence, 1..self.octaves);
+        let max_value = amplitudes * (T::one() + self.offset.abs());
+        HybridMulti { max_value: max_value, ..self }
+    }
 
-            // Add it in, weighted by previous octave's noise value.
-            result = result + (weight * signal);
+    fn update_period(mut self) -> HybridMulti<T, Source> {
+        super::apply_period(&mut self.sources, self.period, self.frequency, self.lacunarity);
+        self
+    }
+}
 
-            // Update the weighting value.
-            weight = weight * signal;
-        }
+/// The parameters of a `HybridMulti` module that are serialized.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct HybridMultiParams<T: Float> {
+    #[serde(flatten)]
+    fractal: super::FractalParams<T>,
+    #[serde(default = "default_offset")]
+    offset: T,
+}
+
+#[cfg(feature = "serde")]
+fn default_offset<T: Float>() -> T {
+    math::cast(DEFAULT_HYBRIDMULTI_OFFSET)
+}
 
-        // Scale the result to the [-1,1] range
-        result * math::cast(3.0)
+#[cfg(feature = "serde")]
+impl<T, Source> Serialize for HybridMulti<T, Source>
+    where T: Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = HybridMultiParams {
+            fractal: super::FractalParams {
+                seed: self.seed,
+                seeds: self.seeds.clone(),
+                octaves: self.octaves,
+                frequency: self.frequency,
+                lacunarity: self.lacunarity,
+                persistence: self.persistence,
+                amplitude_cutoff: self.amplitude_cutoff,
+                normalization: self.normalization,
+                period: self.period,
+            },
+            offset: self.offset,
+        };
+        params.serialize(serializer)
     }
 }
 
-/// 3-dimensional HybridMulti noise
-impl<T: Float> NoiseModule<Point3<T>> for HybridMulti<T> {
-    type Output = T;
+#[cfg(feature = "serde")]
+impl<'de, T, Source> Deserialize<'de> for HybridMulti<T, Source>
+    where T: Float + Deserialize<'de>,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<HybridMulti<T, Source>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let params = HybridMultiParams::deserialize(deserializer)?;
+        let fractal = params.fractal;
+        // The octaves are added with `set_octaves`, so that the count is
+        // clamped exactly as when the module is built in code.
+        let hybrid = HybridMulti {
+            sources: super::build_sources(fractal.seed, &fractal.seeds, 1),
+            seed: fractal.seed,
+            seeds: fractal.seeds,
+            octaves: 1,
+            frequency: fractal.frequency,
+            lacunarity: fractal.lacunarity,
+            persistence: fractal.persistence,
+            amplitude_cutoff: fractal.amplitude_cutoff,
+            offset: params.offset,
+            normalization: fractal.normalization,
+            period: fractal.period,
+            max_value: T::zero(),
+        };
+        Ok(hybrid.set_octaves(fractal.octaves).update_max_value().update_period())
+    }
+}
 
-    fn get(&self, mut point: Point3<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
-        let mut weight = result;
+impl_multifractal!(HybridMulti);
 
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Prevent divergence.
-            if weight > T::one() {
-                weight = T::one();
-            }
+impl<T, Source> Seedable for HybridMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn set_seed(self, seed: usize) -> HybridMulti<T, Source> {
+        HybridMulti::set_seed(self, seed)
+    }
 
-            // Raise the spatial frequency.
-            point = math::mul3(point, self.lacunarity);
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+impl<T, Source> Reseedable for HybridMulti<T, Source>
+    where Source: Seedable + Clone,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        super::reseed_sources(&mut self.sources, seed, &mut self.seeds);
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+impl<T: Float, Source> HybridMulti<T, Source> {
+    /// Samples the octaves at the point, which is already scaled by the
+    /// frequency, and scales the result.
+    fn sample<P>(&self, point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let mut result = T::zero();
+        let mut weight = T::zero();
+        super::accumulate(&self.sources,
+                          point,
+                          scale,
+                          self.lacunarity,
+                          self.persistence,
+                          self.amplitude_cutoff,
+                          |x, signal, amplitude| {
+                              let signal = signal + self.offset;
+
+                              // The first octave is scaled by the persistence
+                              // alone, and sets the initial weight.
+                              if x == 0 {
+                                  result = signal * self.persistence;
+                                  weight = result;
+                                  return;
+                              }
+
+                              // Prevent divergence. The weight may be
+                              // negative, so its magnitude is limited, which
+                              // keeps the result within `max_value`.
+                              weight = weight.max(-T::one()).min(T::one());
+
+                              // Scale the amplitude appropriately for this
+                              // frequency.
+                              let signal = signal * amplitude;
+
+                              // Add it in, weighted by previous octave's noise
+                              // value, and update the weighting value.
+                              result = result + (weight * signal);
+                              weight = weight * signal;
+                          });
+        self.scale_result(result)
+    }
 
-            // Add it in, weighted by previous octave's noise value.
-            result = result + (weight * signal);
+    /// Scales the accumulated result of the octaves into the [-1,1] range,
+    /// according to the normalization strategy.
+    fn scale_result(&self, result: T) -> T {
+        let scaled = result * math::cast(3.0);
+        let theoretical = result / self.max_value;
+        super::normalize(self.normalization, scaled, theoretical)
+    }
 
-            // Update the weighting value.
-            weight = weight * signal;
-        }
+    /// Returns the range of the output values, which depends on the
+    /// normalization strategy.
+    fn output_bounds(&self) -> (T, T) {
+        super::bounds(self.normalization, self.max_value * math::cast(3.0))
+    }
+}
+
+/// 2-dimensional HybridMulti noise
+impl<T, Source> NoiseModule<Point2<T>> for HybridMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point2<T>, Output = T>,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> T {
+        self.sample(math::mul2(point, self.frequency), math::mul2)
+    }
 
-        // Scale the result to the [-1,1] range
-        result * math::cast(3.0)
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
     }
 }
 
-/// 4-dimensional HybridMulti noise
-impl<T: Float> NoiseModule<Point4<T>> for HybridMulti<T> {
+/// 3-dimensional HybridMulti noise
+impl<T, Source> NoiseModule<Point3<T>> for HybridMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point3<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point4<T>) -> T {
-        // First unscaled octave of function; later octaves are scaled.
-        point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
-        let mut weight = result;
+    fn get(&self, point: Point3<T>) -> T {
+        self.sample(math::mul3(point, self.frequency), math::mul3)
+    }
 
-        // Spectral construction inner loop, where the fractal is built.
-        for x in 1..self.octaves {
-            // Prevent divergence.
-            if weight > T::one() {
-                weight = T::one();
-            }
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Raise the spatial frequency.
-            point = math::mul4(point, self.lacunarity);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Get noise value.
-            let mut signal = self.sources[x].get(point);
+/// 4-dimensional HybridMulti noise
+impl<T, Source> NoiseModule<Point4<T>> for HybridMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point4<T>, Output = T>,
+{
+    type Output = T;
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    fn get(&self, point: Point4<T>) -> T {
+        self.sample(math::mul4(point, self.frequency), math::mul4)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Add it in, weighted by previous octave's noise value.
-            result = result + (weight * signal);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Update the weighting value.
-            weight = weight * signal;
+#[cfg(test)]
+mod tests {
+    use super::HybridMulti;
+    use super::super::Normalization;
+    use super::super::tests::{assert_in_range, assert_pinned, points};
+    use NoiseModule;
+
+    // The default output at `PINNED_POINTS`.
+    const PINNED: [f64; 4] = [-0.021869408931358362,
+                              0.47848939393087864,
+                              -0.21841774248503942,
+                              -0.21246890081629088];
+
+    #[test]
+    fn test_pinned_output() {
+        assert_pinned(&HybridMulti::<f64>::new(), &HybridMulti::<f32>::new(), &PINNED);
+    }
+
+    #[test]
+    fn test_offset() {
+        let hybrid = HybridMulti::new();
+        let zero = hybrid.clone().set_offset(0.0);
+        for &point in points().iter() {
+            assert_eq!(hybrid.get(point), zero.get(point));
         }
 
-        // Scale the result to the [-1,1] range
-        result * math::cast(3.0)
+        let theoretical = Normalization::Theoretical;
+        let offset = hybrid.clone().set_offset(0.7).set_normalization(theoretical);
+        assert_eq!(offset.max_value(), hybrid.max_value() * 1.7);
+        assert_in_range(&offset);
+        assert!(points().iter().any(|&point| offset.get(point) != hybrid.get(point)));
+    }
+
+    #[test]
+    fn test_theoretical_range() {
+        let hybrid = HybridMulti::new().set_normalization(Normalization::Theoretical);
+        assert_in_range(&hybrid.clone().set_persistence(1.5));
+        assert_in_range(&hybrid.clone().set_persistence(1.5).set_offset(-0.7));
+        assert_in_range(&hybrid.clone().set_persistence(-1.2).set_offset(-2.0));
+        assert_in_range(&hybrid.set_offset(-0.7));
     }
 }
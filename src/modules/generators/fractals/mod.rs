@@ -14,22 +14,695 @@
 
 pub use self::basicmulti::*;
 pub use self::billow::*;
+pub use self::customfractal::*;
 pub use self::fbm::*;
 pub use self::hybridmulti::*;
 pub use self::ridgedmulti::*;
 
+/// Implements `Serialize` and `Deserialize` for a fractal noise module whose
+/// only parameters are those in `FractalParams`. This must be defined before
+/// the modules that use it.
+#[cfg(feature = "serde")]
+macro_rules! impl_fractal_serde {
+    ($name:ident) => {
+        impl<T, Source> ::serde::Serialize for $name<T, Source>
+            where T: Float + ::serde::Serialize,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: ::serde::Serializer,
+            {
+                let params = super::FractalParams {
+                    seed: self.seed,
+                    seeds: self.seeds.clone(),
+                    octaves: self.octaves,
+                    frequency: self.frequency,
+                    lacunarity: self.lacunarity,
+                    persistence: self.persistence,
+                    amplitude_cutoff: self.amplitude_cutoff,
+                    normalization: self.normalization,
+                    period: self.period,
+                };
+                ::serde::Serialize::serialize(&params, serializer)
+            }
+        }
+
+        impl<'de, T, Source> ::serde::Deserialize<'de> for $name<T, Source>
+            where T: Float + ::serde::Deserialize<'de>,
+                  Source: Default + Seedable + Periodic + Clone,
+        {
+            fn deserialize<D>(deserializer: D) -> Result<$name<T, Source>, D::Error>
+                where D: ::serde::Deserializer<'de>,
+            {
+                let params: super::FractalParams<T> =
+                    ::serde::Deserialize::deserialize(deserializer)?;
+                // The octaves are added with `set_octaves`, so that the count
+                // is clamped exactly as when the module is built in code.
+                Ok($name {
+                    sources: super::build_sources(params.seed, &params.seeds, 1),
+                    seed: params.seed,
+                    seeds: params.seeds,
+                    octaves: 1,
+                    frequency: params.frequency,
+                    lacunarity: params.lacunarity,
+                    persistence: params.persistence,
+                    amplitude_cutoff: params.amplitude_cutoff,
+                    normalization: params.normalization,
+                    period: params.period,
+                }
+                .set_octaves(params.octaves)
+                .update_period())
+            }
+        }
+    }
+}
+
+/// Implements `MultiFractal` for a fractal noise module by forwarding to its
+/// inherent setters. This must be defined before the modules that use it.
+macro_rules! impl_multifractal {
+    ($name:ident) => {
+        impl<T, Source> super::MultiFractal<T> for $name<T, Source>
+            where T: Float,
+                  Source: Default + Seedable + Periodic + Clone,
+        {
+            fn set_octaves(self, octaves: usize) -> $name<T, Source> {
+                $name::set_octaves(self, octaves)
+            }
+
+            fn set_frequency(self, frequency: T) -> $name<T, Source> {
+                $name::set_frequency(self, frequency)
+            }
+
+            fn set_lacunarity(self, lacunarity: T) -> $name<T, Source> {
+                $name::set_lacunarity(self, lacunarity)
+            }
+
+            fn set_persistence(self, persistence: T) -> $name<T, Source> {
+                $name::set_persistence(self, persistence)
+            }
+        }
+    }
+}
+
 mod basicmulti;
 mod billow;
+mod customfractal;
 mod fbm;
 mod hybridmulti;
 mod ridgedmulti;
 
-use modules::Perlin;
+use num_traits::Float;
+use math;
+use math::Point4;
+use modules::{Seedable, source_seed};
+use NoiseModule;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trait for noise modules that can repeat with a given period along each
+/// axis.
+///
+/// The fractal noise modules use this to make each octave's source repeat, see
+/// `Fbm::set_period`.
+pub trait Periodic {
+    /// Returns a copy of the module that repeats every `period[i]` units
+    /// along axis `i`. A period of 0 disables repetition along that axis.
+    fn set_period(self, period: Point4<usize>) -> Self;
+}
+
+/// Trait for the fractal noise modules that build their octaves from a
+/// frequency, lacunarity and persistence.
+///
+/// This allows the parameters of any of these modules to be tuned by generic
+/// code, such as presets or parameter sweeps.
+pub trait MultiFractal<T> {
+    /// Sets the number of octaves.
+    fn set_octaves(self, octaves: usize) -> Self;
+
+    /// Sets the frequency of the first octave.
+    fn set_frequency(self, frequency: T) -> Self;
+
+    /// Sets the multiplier between the frequencies of successive octaves.
+    fn set_lacunarity(self, lacunarity: T) -> Self;
+
+    /// Sets the multiplier between the amplitudes of successive octaves.
+    fn set_persistence(self, persistence: T) -> Self;
+}
+
+/// Strategy used by the fractal noise modules to bring their output values
+/// into the [-1,1] range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Normalization<T> {
+    /// Use the module's own scaling. This is the default for all the fractal
+    /// noise modules. `Billow` and `RidgedMulti` scale by their `max_value()`,
+    /// so their output stays in [-1,1]. The scaling of the other modules is
+    /// fixed, and their output may exceed [-1,1].
+    None,
+
+    /// Divide by the largest value the octaves can theoretically sum to,
+    /// which depends on the number of octaves and the persistence. The output
+    /// is guaranteed to lie in [-1,1], but rarely reaches the ends of the
+    /// range.
+    Theoretical,
+
+    /// Use the module's own fixed scaling, then clamp the output to [-1,1].
+    Clamp,
+
+    /// Use the module's own fixed scaling, then map the given observed
+    /// minimum and maximum output values onto -1 and 1. If the maximum is not
+    /// greater than the minimum, the range is empty and the output is 0, as
+    /// with `utils::calibrate`.
+    Observed(T, T),
+}
+
+/// Applies the normalization strategy, given the output value with the
+/// module's own fixed scaling and the output value with theoretical scaling.
+fn normalize<T: Float>(normalization: Normalization<T>, scaled: T, theoretical: T) -> T {
+    match normalization {
+        Normalization::None => scaled,
+        Normalization::Theoretical => theoretical,
+        Normalization::Clamp => scaled.max(-T::one()).min(T::one()),
+        Normalization::Observed(min, max) if max > min => {
+            ((scaled - min) / (max - min)).mul_add(math::cast(2.0), -T::one())
+        }
+        Normalization::Observed(..) => T::zero(),
+    }
+}
+
+/// Returns the normalization strategy without its clamping, if any, so that
+/// it is affine in the accumulated result. The contributions of separate
+/// groups of octaves can then be added together.
+fn unclamped<T>(normalization: Normalization<T>) -> Normalization<T> {
+    match normalization {
+        Normalization::Clamp => Normalization::None,
+        normalization => normalization,
+    }
+}
+
+/// Multiplies each coordinate of the point by the factor. Unlike `math::mul2`
+/// and its siblings, this works for points of any dimension, for methods that
+/// are generic over the point type.
+fn scale_point<T, P>(mut point: P, factor: T) -> P
+    where T: Float,
+          P: Copy + AsMut<[T]>,
+{
+    for x in point.as_mut().iter_mut() {
+        *x = *x * factor;
+    }
+    point
+}
+
+/// Returns the output range for the normalization strategy, given the largest
+/// magnitude of the output value with the module's own fixed scaling.
+fn bounds<T: Float>(normalization: Normalization<T>, scaled_max: T) -> (T, T) {
+    match normalization {
+        Normalization::None => (-scaled_max, scaled_max),
+        _ => (-T::one(), T::one()),
+    }
+}
+
+/// Returns the output range for the normalization strategy, given the range
+/// of the output value with the module's own scaling, for the modules whose
+/// own scaling is also their theoretical one. Every strategy is monotonic, so
+/// the ends of the range map to the ends of the output range.
+fn scaled_bounds<T: Float>(normalization: Normalization<T>, lower: T, upper: T) -> (T, T) {
+    (normalize(normalization, lower, lower), normalize(normalization, upper, upper))
+}
+
+/// The amplitude of each of the given number of octaves, for the given
+/// persistence.
+fn octave_amplitudes<T: Float>(persistence: T, octaves: usize) -> Vec<T> {
+    (0..octaves).map(|x| persistence.powi(math::cast(x))).collect()
+}
+
+/// Sum of the amplitudes of the given octaves, for the given persistence.
+fn amplitude_sum<T: Float>(persistence: T, octaves: ::std::ops::Range<usize>) -> T {
+    octaves.map(|x| persistence.abs().powi(math::cast(x))).fold(T::zero(), |a, b| a + b)
+}
+
+/// Returns the seed of the given octave: the explicit seed if there is one,
+/// and otherwise `seed` plus the octave index.
+fn octave_seed(seed: usize, seeds: &[usize], octave: usize) -> usize {
+    seeds.get(octave).cloned().unwrap_or(seed.wrapping_add(octave))
+}
+
+/// Runs the octave loop shared by the fractal noise modules.
+///
+/// Samples each source at the point, which should already be scaled by the
+/// frequency of the first octave, and then scales the point by the lacunarity
+/// for the next octave. For each octave, `octave` is called with the octave
+/// index, the signal from the source and the amplitude of the octave, and
+/// accumulates the result. The loop stops early once the amplitude falls
+/// below the amplitude cutoff, but the first octave is always generated.
+fn accumulate<T, P, Source, F>(sources: &[Source],
+                               point: P,
+                               scale: fn(P, T) -> P,
+                               lacunarity: T,
+                               persistence: T,
+                               amplitude_cutoff: T,
+                               octave: F)
+    where T: Float,
+          P: Copy,
+          Source: NoiseModule<P, Output = T>,
+          F: FnMut(usize, T, T),
+{
+    accumulate_amplitudes(sources,
+                          point,
+                          scale,
+                          lacunarity,
+                          |x| persistence.powi(math::cast(x)),
+                          amplitude_cutoff,
+                          octave)
+}
+
+/// Like `accumulate`, but takes the amplitude of each octave from a function
+/// of the octave index, such as a lookup into precomputed amplitudes.
+fn accumulate_amplitudes<T, P, Source, A, F>(sources: &[Source],
+                                             mut point: P,
+                                             scale: fn(P, T) -> P,
+                                             lacunarity: T,
+                                             amplitude: A,
+                                             amplitude_cutoff: T,
+                                             mut octave: F)
+    where T: Float,
+          P: Copy,
+          Source: NoiseModule<P, Output = T>,
+          A: Fn(usize) -> T,
+          F: FnMut(usize, T, T),
+{
+    for (x, source) in sources.iter().enumerate() {
+        let amplitude = amplitude(x);
+
+        // Stop once the remaining octaves would contribute negligibly.
+        if x > 0 && amplitude.abs() < amplitude_cutoff {
+            break;
+        }
+
+        octave(x, source.get(point), amplitude);
+
+        // Increase the frequency for the next octave.
+        point = scale(point, lacunarity);
+    }
+}
 
-fn build_sources(seed: usize, octaves: usize) -> Vec<Perlin> {
+fn build_sources<Source>(seed: usize, seeds: &[usize], octaves: usize) -> Vec<Source>
+    where Source: Default + Seedable,
+{
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
-        sources.push(Perlin::new(seed + x));
+        sources.push(Source::default().set_seed(octave_seed(seed, seeds, x)));
+    }
+    sources
+}
+
+/// Grows or shrinks the sources to the given number of octaves. Existing
+/// sources are kept, and new ones are seeded exactly as `build_sources` would
+/// seed them.
+fn resize_sources<Source>(mut sources: Vec<Source>,
+                          seed: usize,
+                          seeds: &[usize],
+                          octaves: usize)
+                          -> Vec<Source>
+    where Source: Default + Seedable,
+{
+    if octaves < sources.len() {
+        sources.truncate(octaves);
+    } else {
+        for x in sources.len()..octaves {
+            sources.push(Source::default().set_seed(octave_seed(seed, seeds, x)));
+        }
     }
     sources
 }
+
+/// Reseeds the sources in place, exactly as `build_sources` would seed them.
+/// Unlike rebuilding the sources, this keeps their periods.
+///
+/// Explicit seeds are replaced by seeds derived from the new seed, the octave
+/// index and the old explicit seed, so that reseeding changes every octave,
+/// while fractals with different explicit seeds stay distinct.
+fn reseed_sources<Source>(sources: &mut [Source], seed: usize, seeds: &mut Vec<usize>)
+    where Source: Seedable + Clone,
+{
+    for (x, explicit) in seeds.iter_mut().enumerate() {
+        *explicit = source_seed(seed, x) ^ *explicit;
+    }
+    for (x, source) in sources.iter_mut().enumerate() {
+        *source = source.clone().set_seed(octave_seed(seed, seeds, x));
+    }
+}
+
+/// Returns the period of an octave's source, given the period of the fractal
+/// and the frequency of the octave. The result is rounded to the nearest
+/// lattice cell, so it is only exact when the scaled period is an integer.
+fn octave_period<T: Float>(period: Option<Point4<usize>>, frequency: T) -> Point4<usize> {
+    match period {
+        Some(period) => {
+            math::map4(period, |p| {
+                let scaled = (math::cast::<_, T>(p) * frequency.abs()).round();
+                math::cast(scaled)
+            })
+        }
+        None => [0; 4],
+    }
+}
+
+/// Sets the period of each source so that the fractal repeats with the given
+/// period, given the frequency of the first octave and the lacunarity.
+fn apply_period<T, Source>(sources: &mut [Source],
+                           period: Option<Point4<usize>>,
+                           frequency: T,
+                           lacunarity: T)
+    where T: Float,
+          Source: Periodic + Clone,
+{
+    let mut frequency = frequency;
+    for source in sources.iter_mut() {
+        *source = source.clone().set_period(octave_period(period, frequency));
+        frequency = frequency * lacunarity;
+    }
+}
+
+/// The parameters shared by the fractal noise modules that are serialized.
+/// The sources are not serialized, but rebuilt from the seeds and number of
+/// octaves.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct FractalParams<T> {
+    seed: usize,
+    seeds: Vec<usize>,
+    octaves: usize,
+    frequency: T,
+    lacunarity: T,
+    persistence: T,
+    amplitude_cutoff: T,
+    normalization: Normalization<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    period: Option<Point4<usize>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Normalization;
+    use modules::{BasicMulti, Billow, CustomFractal, Fbm, HybridMulti, Reseedable, RidgedMulti};
+    use NoiseModule;
+
+    pub fn points() -> Vec<[f64; 3]> {
+        (0..512).map(|i| [i as f64 * 0.173 - 40.0, i as f64 * 0.311 + 3.0, i as f64 * -0.07]).collect()
+    }
+
+    pub fn assert_in_range<M: NoiseModule<[f64; 3], Output = f64>>(module: &M) {
+        for &point in points().iter() {
+            let value = module.get(point);
+            assert!(value >= -1.0 && value <= 1.0, "{} out of range", value);
+        }
+    }
+
+    /// Points at which the default output of each fractal is pinned.
+    pub const PINNED_POINTS: [[f64; 3]; 4] = [
+        [0.37, 1.25, -2.75],
+        [13.37, -7.5, 0.3],
+        [-101.9, 42.42, 6.125],
+        [1000.3, 2.7, -54.1],
+    ];
+
+    /// Asserts that the `f64` and `f32` versions of a fractal output the
+    /// given values at `PINNED_POINTS`. Like the pinned Perlin values, these
+    /// guard the octave accumulation and scaling constants against accidental
+    /// changes.
+    pub fn assert_pinned<M, N>(module: &M, module_f32: &N, expected: &[f64; 4])
+        where M: NoiseModule<[f64; 3], Output = f64>,
+              N: NoiseModule<[f32; 3], Output = f32>,
+    {
+        for (&point, &expected) in PINNED_POINTS.iter().zip(expected.iter()) {
+            let value = module.get(point);
+            assert!((value - expected).abs() < 1e-12, "{} != {}", value, expected);
+
+            let value = module_f32.get([point[0] as f32, point[1] as f32, point[2] as f32]);
+            assert!((value as f64 - expected).abs() < 1e-4, "{} != {}", value, expected);
+        }
+    }
+
+    #[test]
+    fn test_normalization_theoretical() {
+        assert_in_range(&Fbm::new().set_normalization(Normalization::Theoretical));
+        assert_in_range(&Billow::new().set_normalization(Normalization::Theoretical));
+        assert_in_range(&BasicMulti::new().set_normalization(Normalization::Theoretical));
+        assert_in_range(&HybridMulti::new().set_normalization(Normalization::Theoretical));
+        assert_in_range(&RidgedMulti::new().set_normalization(Normalization::Theoretical));
+    }
+
+    #[test]
+    fn test_bounds() {
+        fn assert_within_bounds<M: NoiseModule<[f64; 3], Output = f64>>(module: &M) {
+            let (lower, upper) = module.bounds();
+            for &point in points().iter() {
+                let value = module.get(point);
+                assert!(value >= lower && value <= upper, "{} out of bounds", value);
+            }
+        }
+
+        assert_within_bounds(&Fbm::new());
+        assert_within_bounds(&BasicMulti::new());
+        assert_within_bounds(&HybridMulti::new().set_offset(0.3));
+        assert_within_bounds(&CustomFractal::new(vec![(1.0, 2.0), (3.0, -0.5)]));
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&Fbm::new()), (-1.96875, 1.96875));
+
+        let normalized = Fbm::new().set_normalization(Normalization::Theoretical);
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&normalized), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_normalization_clamp() {
+        assert_in_range(&Fbm::new().set_normalization(Normalization::Clamp));
+        assert_in_range(&HybridMulti::new().set_normalization(Normalization::Clamp));
+    }
+
+    #[test]
+    fn test_normalization_observed() {
+        let fbm = Fbm::new();
+        let values: Vec<f64> = points().iter().map(|&point| fbm.get(point)).collect();
+        let min = values.iter().cloned().fold(::std::f64::MAX, f64::min);
+        let max = values.iter().cloned().fold(::std::f64::MIN, f64::max);
+
+        let observed = fbm.clone().set_normalization(Normalization::Observed(min, max));
+        assert_in_range(&observed);
+
+        let outputs: Vec<f64> = points().iter().map(|&point| observed.get(point)).collect();
+        assert!(outputs.iter().any(|&v| (v + 1.0).abs() < 1e-12));
+        assert!(outputs.iter().any(|&v| (v - 1.0).abs() < 1e-12));
+
+        // Empty and reversed ranges output 0 rather than NaN or inverted noise.
+        for &(min, max) in &[(0.25, 0.25), (max, min)] {
+            let empty = fbm.clone().set_normalization(Normalization::Observed(min, max));
+            assert!(points().iter().all(|&point| empty.get(point) == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_set_octaves_matches_fresh_module() {
+        let fresh = RidgedMulti::new().set_octaves(9);
+        let grown = RidgedMulti::new().set_octaves(2).set_octaves(12).set_octaves(9);
+        for &point in points().iter() {
+            assert_eq!(fresh.get(point), grown.get(point));
+        }
+    }
+
+    #[test]
+    fn test_set_seeds() {
+        // Explicit seeds for the first octaves, falling back to seed + x.
+        let explicit = Fbm::new().set_seed(3).set_seeds(&[17, 4]).set_octaves(4);
+        let default = Fbm::new().set_seed(17).set_octaves(4);
+        let one_octave = |fbm: &Fbm<f64>, point| fbm.coarse(1).get(point);
+        for &point in points().iter().take(16) {
+            assert_eq!(one_octave(&explicit, point), one_octave(&default, point));
+        }
+
+        let fallback = Fbm::new().set_seed(3).set_seeds(&[5, 6, 7]).set_octaves(4);
+        let sequential = Fbm::new().set_seed(3).set_seeds(&[5, 6, 7, 6]).set_octaves(4);
+        let shifted = Fbm::new().set_seed(5).set_octaves(4);
+        let mut differs = false;
+        for &point in points().iter() {
+            assert_eq!(fallback.get(point), sequential.get(point));
+            differs |= fallback.get(point) != shifted.get(point);
+        }
+        assert!(differs);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use modules::Perlin;
+        use serde_json;
+        use test_utils::assert_modules_equal;
+
+        let region = ([-3.0, -2.0], [4.0, 5.0]);
+
+        let perlin = Perlin::new(7).with_offset(3);
+        let json = serde_json::to_string(&perlin).unwrap();
+        assert_eq!(json, r#"{"seed":7,"offset":3}"#);
+        let loaded: Perlin = serde_json::from_str(&json).unwrap();
+        assert_modules_equal(&perlin, &loaded, region, 0.0);
+
+        let perlin = perlin.set_frequency(1.5).set_wrap(64);
+        let json = serde_json::to_string(&perlin).unwrap();
+        let loaded: Perlin = serde_json::from_str(&json).unwrap();
+        assert_modules_equal(&perlin, &loaded, region, 0.0);
+
+        let fbm = Fbm::new().set_seed(5).set_seeds(&[40]).set_octaves(4).set_persistence(0.6);
+        let loaded: Fbm<f64> = serde_json::from_str(&serde_json::to_string(&fbm).unwrap()).unwrap();
+        assert_modules_equal(&fbm, &loaded, region, 0.0);
+
+        let ridged = RidgedMulti::new()
+            .set_seed(9)
+            .set_gain(1.5)
+            .set_invert_ridges(true)
+            .set_normalization(Normalization::Clamp);
+        let json = serde_json::to_string(&ridged).unwrap();
+        let loaded: RidgedMulti<f64> = serde_json::from_str(&json).unwrap();
+        assert_modules_equal(&ridged, &loaded, region, 0.0);
+        assert_eq!(loaded.max_value(), ridged.max_value());
+
+        let billow = Billow::new().set_seed(3).set_bias(-0.5);
+        let json = serde_json::to_string(&billow).unwrap();
+        let loaded: Billow<f64> = serde_json::from_str(&json).unwrap();
+        assert_modules_equal(&billow, &loaded, region, 0.0);
+        assert_eq!(loaded.max_value(), billow.max_value());
+
+        let hybrid = HybridMulti::new().set_seed(4).set_offset(0.7);
+        let json = serde_json::to_string(&hybrid).unwrap();
+        let loaded: HybridMulti<f64> = serde_json::from_str(&json).unwrap();
+        assert_modules_equal(&hybrid, &loaded, region, 0.0);
+        assert_eq!(loaded.max_value(), hybrid.max_value());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_octaves_clamped() {
+        use serde::Serialize;
+        use serde::de::DeserializeOwned;
+        use serde_json;
+
+        // Out-of-range octave counts are clamped as by `set_octaves`, rather
+        // than producing NaN or allocating a source per octave.
+        fn assert_clamped<M>(module: M, max_octaves: usize)
+            where M: Serialize + DeserializeOwned + NoiseModule<[f64; 3], Output = f64>,
+        {
+            for &(octaves, expected) in [(0, 1), (usize::max_value(), max_octaves)].iter() {
+                let mut json = serde_json::to_value(&module).unwrap();
+                json["octaves"] = octaves.into();
+                let loaded: M = serde_json::from_value(json).unwrap();
+                assert_eq!(loaded.complexity_estimate(), expected);
+                assert!(loaded.get([0.3, 0.7, -1.1]).is_finite());
+            }
+        }
+
+        assert_clamped(Fbm::new(), super::FBM_MAX_OCTAVES);
+        assert_clamped(Billow::new(), super::BILLOW_MAX_OCTAVES);
+        assert_clamped(BasicMulti::new(), super::BASICMULTI_MAX_OCTAVES);
+        assert_clamped(HybridMulti::new(), super::HYBRIDMULTI_MAX_OCTAVES);
+        assert_clamped(RidgedMulti::new(), super::RIDGED_MAX_OCTAVES);
+    }
+
+    #[test]
+    fn test_normalization_none_is_default() {
+        let fbm = Fbm::new();
+        let none = fbm.clone().set_normalization(Normalization::None);
+        for &point in points().iter() {
+            assert_eq!(fbm.get(point), none.get(point));
+        }
+    }
+
+    #[test]
+    fn test_set_period() {
+        fn assert_tiles<M: NoiseModule<[f64; 3], Output = f64>>(module: &M) {
+            for &point in points().iter() {
+                let value = module.get(point);
+                let shifted = [point[0] - 4.0, point[1] + 6.0, point[2]];
+                assert!((module.get(shifted) - value).abs() < 1e-9);
+            }
+        }
+
+        let period = [4, 3, 0, 0];
+        assert_tiles(&Fbm::new().set_period(period).set_frequency(2.0));
+        assert_tiles(&Billow::new().set_period(period));
+        assert_tiles(&BasicMulti::new().set_period(period).set_octaves(3));
+        assert_tiles(&HybridMulti::new().set_period(period));
+
+        let mut ridged = RidgedMulti::new().set_period(period);
+        ridged.reseed(5);
+        assert_tiles(&ridged);
+    }
+
+    #[test]
+    fn test_accumulate() {
+        use math;
+        use modules::Perlin;
+
+        let sources: Vec<Perlin> = super::build_sources(3, &[], 6);
+        let point = [0.37, -1.2, 0.8];
+
+        let mut octaves = Vec::new();
+        super::accumulate(&sources,
+                          point,
+                          math::mul3,
+                          2.0,
+                          0.25,
+                          0.01,
+                          |x, signal, amplitude| octaves.push((x, signal, amplitude)));
+
+        // 0.25^4 is below the cutoff, so only the first 4 octaves are sampled.
+        assert_eq!(octaves.len(), 4);
+        for &(x, signal, amplitude) in octaves.iter() {
+            let frequency = 2.0f64.powi(x as i32);
+            let octave_point = math::mul3(point, frequency);
+            assert_eq!(signal, Perlin::new(3 + x).get(octave_point));
+            assert_eq!(amplitude, 0.25f64.powi(x as i32));
+        }
+    }
+
+    #[test]
+    fn test_value_source() {
+        use modules::Value;
+
+        let fbm = Fbm::<f64, Value>::default().set_seed(2).set_octaves(3);
+        for &point in points().iter().take(32) {
+            let mut expected = 0.0;
+            for x in 0..3 {
+                let frequency = 2.0f64.powi(x as i32);
+                let octave_point = [point[0] * frequency, point[1] * frequency, point[2] * frequency];
+                let signal: f64 = Value::new(2 + x).get(octave_point);
+                expected += signal * 0.5f64.powi(x as i32);
+            }
+            assert!((fbm.get(point) - expected).abs() < 1e-12);
+        }
+
+        // Periodicity applies to any source.
+        let periodic = Fbm::<f64, Value>::default().set_period([4, 3, 0, 0]);
+        for &point in points().iter().take(32) {
+            let shifted = [point[0] + 4.0, point[1] - 3.0, point[2]];
+            assert!((periodic.get(point) - periodic.get(shifted)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_multifractal() {
+        use super::MultiFractal;
+
+        fn preset<M: MultiFractal<f64>>(module: M) -> M {
+            module.set_octaves(3).set_frequency(0.5).set_lacunarity(3.0).set_persistence(0.25)
+        }
+
+        let fbm = preset(Fbm::new());
+        let ridged = preset(RidgedMulti::new());
+        assert_eq!((fbm.octaves, fbm.frequency, fbm.lacunarity, fbm.persistence),
+                   (3, 0.5, 3.0, 0.25));
+        assert_eq!(ridged.max_value(),
+                   RidgedMulti::<f64>::new().set_octaves(3).set_persistence(0.25).max_value());
+
+        let point = [0.3, 0.7, -1.1];
+        let billow = Billow::new().set_octaves(3).set_frequency(0.5).set_lacunarity(3.0);
+        assert_eq!(preset(Billow::new()).get(point), billow.set_persistence(0.25).get(point));
+        let _ = (preset(BasicMulti::<f64>::new()), preset(HybridMulti::<f64>::new()));
+    }
+}
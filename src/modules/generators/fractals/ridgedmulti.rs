@@ -16,6 +16,10 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, Seedable};
+use super::{Normalization, Periodic};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use modules::Perlin;
 
 /// Default noise seed for the RidgedMulti noise module.
@@ -30,6 +34,8 @@ pub const DEFAULT_RIDGED_LACUNARITY: f32 = 2.0;
 pub const DEFAULT_RIDGED_PERSISTENCE: f32 = 1.0;
 /// Default gain for the RidgedMulti noise module.
 pub const DEFAULT_RIDGED_GAIN: f32 = 2.0;
+/// Default amplitude cutoff for the RidgedMulti noise module.
+pub const DEFAULT_RIDGED_AMPLITUDE_CUTOFF: f32 = 0.0;
 /// Maximum number of octaves for the RidgedMulti noise module.
 pub const RIDGED_MAX_OCTAVES: usize = 32;
 
@@ -41,19 +47,26 @@ pub const RIDGED_MAX_OCTAVES: usize = 32;
 /// an absolute-value function. Modifying the octave values in this way
 /// produces ridge-like formations.
 ///
-/// The values output from this module will usually range from -1.0 to 1.0 with
-/// default values for the parameters, but there are no guarantees that all
-/// output values will exist within this range. If the parameters are modified
-/// from their defaults, then the output will need to be scaled to remain in
-/// the [-1,1] range.
+/// The output is scaled by the range of values the octaves can theoretically
+/// sum to, given the number of octaves, the persistence and the gain, so it
+/// always lies within the range -1.0 to 1.0. This range is recomputed
+/// whenever those parameters change, and is available from `min_value` and
+/// `max_value`.
 ///
 /// Ridged-multifractal noise is often used to generate craggy mountainous
 /// terrain or marble-like textures.
+///
+/// Like `Fbm`, the source of the octaves can be changed from `Perlin` with the
+/// `Source` type parameter.
 #[derive(Clone, Debug)]
-pub struct RidgedMulti<T> {
+pub struct RidgedMulti<T, Source = Perlin> {
     /// Seed.
     pub seed: usize,
 
+    /// Explicit seeds for the first octaves. Octaves beyond the end of this
+    /// list are seeded with `seed` plus the octave index.
+    pub seeds: Vec<usize>,
+
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -85,34 +98,101 @@ pub struct RidgedMulti<T> {
     /// The gain to apply to the weight on each octave.
     pub gain: T,
 
-    sources: Vec<Perlin>,
+    /// The amplitude below which octaves are no longer generated.
+    ///
+    /// Octaves whose amplitude falls below this value contribute little to
+    /// the output but still cost a full noise sample, so skipping them speeds
+    /// up modules with many octaves. A cutoff of zero generates every octave.
+    pub amplitude_cutoff: T,
+
+    /// Strategy used to bring the output value into the [-1,1] range. The
+    /// default, `Normalization::None`, scales the result by the theoretical
+    /// maximum, so it matches `Normalization::Theoretical`.
+    pub normalization: Normalization<T>,
+
+    /// Whether to flip the profile of each octave, producing sharp valleys
+    /// and rounded plateaus instead of sharp ridges and rounded valleys.
+    pub invert_ridges: bool,
+
+    min_value: T,
+
+    max_value: T,
+
+    /// The amplitude of each octave, precomputed from the persistence.
+    amplitudes: Vec<T>,
+
+    /// Number of units after which the noise repeats along each axis, if any.
+    /// See `set_period`.
+    pub period: Option<Point4<usize>>,
+
+    sources: Vec<Source>,
 }
 
 impl<T: Float> RidgedMulti<T> {
     pub fn new() -> RidgedMulti<T> {
+        RidgedMulti::default()
+    }
+}
+
+impl<T, Source> Default for RidgedMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn default() -> RidgedMulti<T, Source> {
         RidgedMulti {
             seed: DEFAULT_RIDGED_SEED,
+            seeds: Vec::new(),
             octaves: DEFAULT_RIDGED_OCTAVE_COUNT,
             frequency: math::cast(DEFAULT_RIDGED_FREQUENCY),
             lacunarity: math::cast(DEFAULT_RIDGED_LACUNARITY),
             persistence: math::cast(DEFAULT_RIDGED_PERSISTENCE),
             gain: math::cast(DEFAULT_RIDGED_GAIN),
-            sources: super::build_sources(DEFAULT_RIDGED_SEED, DEFAULT_RIDGED_OCTAVE_COUNT),
+            amplitude_cutoff: math::cast(DEFAULT_RIDGED_AMPLITUDE_CUTOFF),
+            normalization: Normalization::None,
+            invert_ridges: false,
+            min_value: T::zero(),
+            max_value: T::zero(),
+            amplitudes: Vec::new(),
+            period: None,
+            sources: super::build_sources(DEFAULT_RIDGED_SEED, &[], DEFAULT_RIDGED_OCTAVE_COUNT),
         }
+        .update_max_value()
+        .update_amplitudes()
     }
+}
 
-    pub fn set_seed(self, seed: usize) -> RidgedMulti<T> {
+impl<T, Source> RidgedMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    pub fn set_seed(self, seed: usize) -> RidgedMulti<T, Source> {
         if self.seed == seed {
             return self;
         }
         RidgedMulti {
             seed: seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, &self.seeds, self.octaves),
             ..self
         }
+        .update_period()
     }
 
-    pub fn set_octaves(self, mut octaves: usize) -> RidgedMulti<T> {
+    /// Sets explicit seeds for the octaves, starting from the first.
+    ///
+    /// Octaves without an explicit seed fall back to `seed` plus the octave
+    /// index. By default every octave uses that scheme, so fractals with
+    /// nearby seeds share most of their octaves; explicit seeds avoid the
+    /// resulting correlation.
+    pub fn set_seeds(self, seeds: &[usize]) -> RidgedMulti<T, Source> {
+        RidgedMulti {
+            seeds: seeds.to_vec(),
+            sources: super::build_sources(self.seed, seeds, self.octaves),
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_octaves(self, mut octaves: usize) -> RidgedMulti<T, Source> {
         if self.octaves == octaves {
             return self;
         } else if octaves > RIDGED_MAX_OCTAVES {
@@ -122,177 +202,528 @@ impl<T: Float> RidgedMulti<T> {
         }
         RidgedMulti {
             octaves: octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::resize_sources(self.sources, self.seed, &self.seeds, octaves),
             ..self
         }
+        .update_max_value()
+        .update_amplitudes()
+        .update_period()
     }
 
-    pub fn set_frequency(self, frequency: T) -> RidgedMulti<T> {
-        RidgedMulti { frequency: frequency, ..self }
+    pub fn set_frequency(self, frequency: T) -> RidgedMulti<T, Source> {
+        RidgedMulti { frequency: frequency, ..self }.update_period()
     }
 
-    pub fn set_lacunarity(self, lacunarity: T) -> RidgedMulti<T> {
-        RidgedMulti { lacunarity: lacunarity, ..self }
+    pub fn set_lacunarity(self, lacunarity: T) -> RidgedMulti<T, Source> {
+        RidgedMulti { lacunarity: lacunarity, ..self }.update_period()
     }
 
-    pub fn set_persistence(self, persistence: T) -> RidgedMulti<T> {
+    pub fn set_persistence(self, persistence: T) -> RidgedMulti<T, Source> {
         RidgedMulti { persistence: persistence, ..self }
+            .update_max_value()
+            .update_amplitudes()
     }
 
-    pub fn set_gain(self, gain: T) -> RidgedMulti<T> {
-        RidgedMulti { gain: gain, ..self }
+    pub fn set_gain(self, gain: T) -> RidgedMulti<T, Source> {
+        RidgedMulti { gain: gain, ..self }.update_max_value()
     }
-}
 
-/// 2-dimensional RidgedMulti noise
-impl<T: Float> NoiseModule<Point2<T>> for RidgedMulti<T> {
-    type Output = T;
+    pub fn set_amplitude_cutoff(self, amplitude_cutoff: T) -> RidgedMulti<T, Source> {
+        RidgedMulti { amplitude_cutoff: amplitude_cutoff, ..self }
+    }
 
-    fn get(&self, mut point: Point2<T>) -> T {
-        let mut result = T::zero();
-        let mut weight = T::one();
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. A period of 0 disables tiling along that
+    /// axis.
+    ///
+    /// Each octave's source wraps at the period scaled by that octave's
+    /// frequency, rounded to the nearest lattice cell. The noise therefore
+    /// only tiles exactly when every scaled period is an integer, which holds
+    /// for integer frequencies and lacunarities.
+    pub fn set_period(self, period: Point4<usize>) -> RidgedMulti<T, Source> {
+        let periodic = period.iter().any(|&p| p > 0);
+        RidgedMulti {
+            period: if periodic { Some(period) } else { None },
+            ..self
+        }
+        .update_period()
+    }
+
+    pub fn set_normalization(self, normalization: Normalization<T>) -> RidgedMulti<T, Source> {
+        RidgedMulti { normalization: normalization, ..self }
+    }
+
+    fn update_period(mut self) -> RidgedMulti<T, Source> {
+        super::apply_period(&mut self.sources, self.period, self.frequency, self.lacunarity);
+        self
+    }
 
-        point = math::mul2(point, self.frequency);
+    /// Sets whether to flip the profile of each octave.
+    ///
+    /// Inverted ridges put the sharp creases at the low points of the noise,
+    /// forming canyons and valleys between rounded plateaus. Because the flip
+    /// happens before each octave is weighted, this differs from negating the
+    /// output value, which would keep the creases but turn them upside down.
+    pub fn set_invert_ridges(self, invert_ridges: bool) -> RidgedMulti<T, Source> {
+        RidgedMulti { invert_ridges: invert_ridges, ..self }
+    }
 
+    /// Returns the largest value the octaves can theoretically sum to, which
+    /// the output maps to 1.0.
+    ///
+    /// Each octave's signal is at most 1.0, scaled by the weight from the
+    /// previous octave. The weight is clamped to 1.0 and otherwise grows by
+    /// the gain, so octave _i_ contributes at most `min(1, gain)^i` times its
+    /// amplitude. With the default parameters this is 6.0.
+    pub fn max_value(&self) -> T {
+        self.max_value
+    }
+
+    /// Returns the smallest value the octaves can theoretically sum to, which
+    /// the output maps to -1.0.
+    ///
+    /// This is 0.0 unless the persistence is negative, in which case the odd
+    /// octaves have negative amplitudes and subtract from the sum.
+    pub fn min_value(&self) -> T {
+        self.min_value
+    }
+
+    fn update_max_value(self) -> RidgedMulti<T, Source> {
+        let weight = self.gain.max(T::zero()).min(T::one());
+        let mut min_value = T::zero();
+        let mut max_value = T::zero();
         for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
+            let bound = (self.persistence * weight).powi(math::cast(x));
+            if bound < T::zero() {
+                min_value = min_value + bound;
+            } else {
+                max_value = max_value + bound;
+            }
+        }
+        RidgedMulti {
+            min_value: min_value,
+            max_value: max_value,
+            ..self
+        }
+    }
 
-            // Make the ridges.
-            signal = signal.abs();
-            signal = T::one() - signal;
+    fn update_amplitudes(self) -> RidgedMulti<T, Source> {
+        RidgedMulti { amplitudes: super::octave_amplitudes(self.persistence, self.octaves), ..self }
+    }
+
+    /// Returns a copy of this module that only generates the first `octaves`
+    /// octaves.
+    ///
+    /// The octaves that are kept are identical to the corresponding octaves of
+    /// this module, so the copy outputs the low-frequency "shape" of the
+    /// noise. This is useful for cheaply sampling distant levels of detail.
+    /// The copy keeps the scaling of this module rather than scaling by its
+    /// own `max_value`, so adding the contribution of the remaining octaves to
+    /// its output gives the output of this module.
+    pub fn coarse(&self, octaves: usize) -> RidgedMulti<T, Source> {
+        let octaves = octaves.max(1).min(self.octaves);
+        RidgedMulti {
+            seed: self.seed,
+            seeds: self.seeds.clone(),
+            octaves: octaves,
+            frequency: self.frequency,
+            lacunarity: self.lacunarity,
+            persistence: self.persistence,
+            gain: self.gain,
+            amplitude_cutoff: self.amplitude_cutoff,
+            normalization: self.normalization,
+            invert_ridges: self.invert_ridges,
+            min_value: self.min_value,
+            max_value: self.max_value,
+            amplitudes: self.amplitudes[..octaves].to_vec(),
+            period: self.period,
+            sources: self.sources[..octaves].to_vec(),
+        }
+    }
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal = signal * signal;
+}
 
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal = signal * weight;
+/// The parameters of a `RidgedMulti` module that are serialized.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RidgedMultiParams<T> {
+    #[serde(flatten)]
+    fractal: super::FractalParams<T>,
+    gain: T,
+    invert_ridges: bool,
+}
 
-            // Weight succesive contributions by the previous signal.
-            weight = signal * self.gain;
+#[cfg(feature = "serde")]
+impl<T, Source> Serialize for RidgedMulti<T, Source>
+    where T: Float + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = RidgedMultiParams {
+            fractal: super::FractalParams {
+                seed: self.seed,
+                seeds: self.seeds.clone(),
+                octaves: self.octaves,
+                frequency: self.frequency,
+                lacunarity: self.lacunarity,
+                persistence: self.persistence,
+                amplitude_cutoff: self.amplitude_cutoff,
+                normalization: self.normalization,
+                period: self.period,
+            },
+            gain: self.gain,
+            invert_ridges: self.invert_ridges,
+        };
+        params.serialize(serializer)
+    }
+}
 
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            if math::cast::<_, f32>(weight) > 1.0 {
-                weight = T::one();
-            } else if math::cast::<_, f32>(weight) < 0.0 {
-                weight = T::zero();
-            }
+#[cfg(feature = "serde")]
+impl<'de, T, Source> Deserialize<'de> for RidgedMulti<T, Source>
+    where T: Float + Deserialize<'de>,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<RidgedMulti<T, Source>, D::Error>
+        where D: Deserializer<'de>,
+    {
+        let params = RidgedMultiParams::deserialize(deserializer)?;
+        let fractal = params.fractal;
+        // The octaves are added with `set_octaves`, so that the count is
+        // clamped exactly as when the module is built in code.
+        let ridged = RidgedMulti {
+            sources: super::build_sources(fractal.seed, &fractal.seeds, 1),
+            seed: fractal.seed,
+            seeds: fractal.seeds,
+            octaves: 1,
+            frequency: fractal.frequency,
+            lacunarity: fractal.lacunarity,
+            persistence: fractal.persistence,
+            gain: params.gain,
+            amplitude_cutoff: fractal.amplitude_cutoff,
+            normalization: fractal.normalization,
+            invert_ridges: params.invert_ridges,
+            min_value: T::zero(),
+            max_value: T::zero(),
+            amplitudes: Vec::new(),
+            period: fractal.period,
+        };
+        Ok(ridged.set_octaves(fractal.octaves)
+            .update_max_value()
+            .update_amplitudes()
+            .update_period())
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+impl_multifractal!(RidgedMulti);
 
-            // Add the signal to the result.
-            result = result + signal;
+impl<T, Source> Seedable for RidgedMulti<T, Source>
+    where T: Float,
+          Source: Default + Seedable + Periodic + Clone,
+{
+    fn set_seed(self, seed: usize) -> RidgedMulti<T, Source> {
+        RidgedMulti::set_seed(self, seed)
+    }
 
-            // Increase the frequency.
-            point = math::mul2(point, self.lacunarity);
-        }
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
 
-        // Scale and shift the result into the [-1,1] range
-        result.mul_add(math::cast(1.0 / 3.0), -T::one())
+impl<T, Source> Reseedable for RidgedMulti<T, Source>
+    where Source: Seedable + Clone,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        super::reseed_sources(&mut self.sources, seed, &mut self.seeds);
     }
 }
 
-/// 3-dimensional RidgedMulti noise
-impl<T: Float> NoiseModule<Point3<T>> for RidgedMulti<T> {
-    type Output = T;
+impl<T: Float, Source> RidgedMulti<T, Source> {
+    /// Samples only the first `octaves` octaves of this module at the given
+    /// point, without building a copy as `coarse` does.
+    ///
+    /// The result is the output of `coarse(octaves)`: it keeps the scaling of
+    /// this module, so adding the contribution of the remaining octaves gives
+    /// the output of `get`. The result is not clamped under
+    /// `Normalization::Clamp`, as that would break the sum; only the full
+    /// output is.
+    pub fn get_coarse<P>(&self, point: P, octaves: usize) -> T
+        where P: Copy + AsMut<[T]>,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let octaves = octaves.max(1).min(self.sources.len());
+        let point = super::scale_point(point, self.frequency);
+        let result = self.sum_octaves(&self.sources[..octaves], point, super::scale_point);
+        self.scale_result(result, super::unclamped(self.normalization))
+    }
+
+    /// Samples the octaves at the point, which is already scaled by the
+    /// frequency, and scales the result.
+    fn sample<P>(&self, point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
+        let result = self.sum_octaves(&self.sources, point, scale);
+        self.scale_result(result, self.normalization)
+    }
 
-    fn get(&self, mut point: Point3<T>) -> T {
+    /// Sums the octaves of the given sources at the point, which is already
+    /// scaled by the frequency.
+    fn sum_octaves<P>(&self, sources: &[Source], point: P, scale: fn(P, T) -> P) -> T
+        where P: Copy,
+              Source: NoiseModule<P, Output = T>,
+    {
         let mut result = T::zero();
         let mut weight = T::one();
+        super::accumulate_amplitudes(sources,
+                                     point,
+                                     scale,
+                                     self.lacunarity,
+                                     |x| self.amplitudes[x],
+                                     self.amplitude_cutoff,
+                                     |_, signal, amplitude| {
+                                         // Make the ridges.
+                                         let mut signal = T::one() - signal.abs();
+
+                                         // Square the signal to increase the sharpness of
+                                         // the ridges.
+                                         signal = signal * signal;
+
+                                         // Flip the profile to turn the ridges into
+                                         // valleys.
+                                         if self.invert_ridges {
+                                             signal = T::one() - signal;
+                                         }
+
+                                         // Apply the weighting from the previous octave to
+                                         // the signal. Larger values have higher weights,
+                                         // producing sharp points along the ridges.
+                                         signal = signal * weight;
+
+                                         // Weight succesive contributions by the previous
+                                         // signal.
+                                         weight = signal * self.gain;
+
+                                         // Clamp the weight to [0,1] to prevent the result
+                                         // from diverging.
+                                         if weight > T::one() {
+                                             weight = T::one();
+                                         } else if weight < T::zero() {
+                                             weight = T::zero();
+                                         }
+
+                                         // Scale the amplitude appropriately for this
+                                         // frequency, and add the signal to the result.
+                                         result = result + signal * amplitude;
+                                     });
+        result
+    }
 
-        point = math::mul3(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
+    /// Scales the accumulated result of the octaves into the [-1,1] range,
+    /// according to the normalization strategy.
+    fn scale_result(&self, result: T, normalization: Normalization<T>) -> T {
+        let range = self.max_value - self.min_value;
+        let scaled = (result - self.min_value).mul_add(math::cast::<_, T>(2.0) / range, -T::one());
+        super::normalize(normalization, scaled, scaled)
+    }
 
-            // Make the ridges.
-            signal = signal.abs();
-            signal = T::one() - signal;
+    /// Returns the range of the output values, which depends on the
+    /// normalization strategy. The octaves sum to between `min_value` and
+    /// `max_value`, which are scaled to -1 and 1.
+    fn output_bounds(&self) -> (T, T) {
+        super::scaled_bounds(self.normalization, -T::one(), T::one())
+    }
+}
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal = signal * signal;
+/// 2-dimensional RidgedMulti noise
+impl<T, Source> NoiseModule<Point2<T>> for RidgedMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point2<T>, Output = T>,
+{
+    type Output = T;
 
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal = signal * weight;
+    fn get(&self, point: Point2<T>) -> T {
+        self.sample(math::mul2(point, self.frequency), math::mul2)
+    }
 
-            // Weight succesive contributions by the previous signal.
-            weight = signal * self.gain;
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            if math::cast::<_, f32>(weight) > 1.0 {
-                weight = T::one();
-            } else if math::cast::<_, f32>(weight) < 0.0 {
-                weight = T::zero();
-            }
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+/// 3-dimensional RidgedMulti noise
+impl<T, Source> NoiseModule<Point3<T>> for RidgedMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point3<T>, Output = T>,
+{
+    type Output = T;
 
-            // Add the signal to the result.
-            result = result + signal;
+    fn get(&self, point: Point3<T>) -> T {
+        self.sample(math::mul3(point, self.frequency), math::mul3)
+    }
 
-            // Increase the frequency.
-            point = math::mul3(point, self.lacunarity);
-        }
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        // Scale and shift the result into the [-1,1] range
-        result.mul_add(math::cast(1.0 / 3.0), -T::one())
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
     }
 }
 
 /// 4-dimensional RidgedMulti noise
-impl<T: Float> NoiseModule<Point4<T>> for RidgedMulti<T> {
+impl<T, Source> NoiseModule<Point4<T>> for RidgedMulti<T, Source>
+    where T: Float,
+          Source: NoiseModule<Point4<T>, Output = T>,
+{
     type Output = T;
 
-    fn get(&self, mut point: Point4<T>) -> T {
-        let mut result = T::zero();
-        let mut weight = T::one();
+    fn get(&self, point: Point4<T>) -> T {
+        self.sample(math::mul4(point, self.frequency), math::mul4)
+    }
 
-        point = math::mul4(point, self.frequency);
+    fn complexity_estimate(&self) -> usize {
+        self.octaves
+    }
 
-        for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
+    fn bounds(&self) -> (T, T) {
+        self.output_bounds()
+    }
+}
 
-            // Make the ridges.
-            signal = signal.abs();
-            signal = T::one() - signal;
+#[cfg(test)]
+mod tests {
+    use super::{Normalization, RidgedMulti};
+    use super::super::tests::{assert_in_range, assert_pinned, points};
+    use math;
+    use modules::Perlin;
+    use NoiseModule;
+
+    // The default output at `PINNED_POINTS`.
+    const PINNED: [f64; 4] = [-0.42255127626145345,
+                              -0.3140485168066998,
+                              -0.23957659610709842,
+                              0.1708739499862506];
+
+    #[test]
+    fn test_pinned_output() {
+        assert_pinned(&RidgedMulti::<f64>::new(), &RidgedMulti::<f32>::new(), &PINNED);
+    }
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal = signal * signal;
+    /// Returns the output value at the sharpest point along a line, relative
+    /// to the range of output values along it, from 0.0 at the minimum to 1.0
+    /// at the maximum.
+    fn sharpest_point(ridged: &RidgedMulti<f64>) -> f64 {
+        let values: Vec<f64> = (0..2000)
+            .map(|i| ridged.get([i as f64 * 0.002 + 0.1, 0.35]))
+            .collect();
+        let min = values.iter().cloned().fold(1.0 / 0.0, f64::min);
+        let max = values.iter().cloned().fold(-1.0 / 0.0, f64::max);
+
+        let sharpest = (1..values.len() - 1)
+            .max_by(|&a, &b| {
+                let curvature = |i: usize| (values[i - 1] - 2.0 * values[i] + values[i + 1]).abs();
+                curvature(a).partial_cmp(&curvature(b)).unwrap()
+            })
+            .unwrap();
+        (values[sharpest] - min) / (max - min)
+    }
 
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal = signal * weight;
+    #[test]
+    fn test_max_value() {
+        assert_eq!(RidgedMulti::<f64>::new().max_value(), 6.0);
+        assert_eq!(RidgedMulti::<f64>::new().set_gain(0.5).set_octaves(3).max_value(), 1.75);
 
-            // Weight succesive contributions by the previous signal.
-            weight = signal * self.gain;
+        let ridged = RidgedMulti::new().set_persistence(1.5).set_octaves(8);
+        for i in 0..512 {
+            let value = ridged.get([i as f64 * 0.173 - 40.0, i as f64 * 0.311 + 3.0]);
+            assert!(value >= -1.0 && value <= 1.0, "{} out of range", value);
+        }
+    }
 
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            if math::cast::<_, f32>(weight) > 1.0 {
-                weight = T::one();
-            } else if math::cast::<_, f32>(weight) < 0.0 {
-                weight = T::zero();
+    #[test]
+    fn test_coarse_plus_remaining_octaves() {
+        let ridged = RidgedMulti::<f64>::new().set_seed(7);
+        let max_value = ridged.max_value();
+
+        // Both outputs are scaled by the module's own max_value into [-1,1],
+        // and then remapped from the [min,max] range of the normalization.
+        let normalizations = [(Normalization::None, -1.0, 1.0),
+                              (Normalization::Observed(-0.6, 0.9), -0.6, 0.9)];
+        for &(normalization, min, max) in &normalizations {
+            let ridged = ridged.clone().set_normalization(normalization);
+            let scale = |sum: f64| (sum * 2.0 / max_value - 1.0 - min) * 2.0 / (max - min) - 1.0;
+            for i in 0..16 {
+                let point = [i as f64 * 0.31 - 1.7, i as f64 * 0.53 + 0.2, -0.4];
+
+                let mut coarse = 0.0;
+                let mut full = 0.0;
+                let mut weight = 1.0;
+                let mut frequency = ridged.frequency;
+                for x in 0..ridged.octaves {
+                    let octave_point = math::mul3(point, frequency);
+                    let signal = 1.0 - Perlin::new(7 + x).get(octave_point).abs();
+                    let signal = signal * signal * weight;
+                    weight = (signal * ridged.gain).max(0.0).min(1.0);
+                    full += signal * ridged.persistence.powi(x as i32);
+                    if x < 2 {
+                        coarse = full;
+                    }
+                    frequency = frequency * ridged.lacunarity;
+                }
+
+                assert!((ridged.get_coarse(point, 2) - scale(coarse)).abs() < 1e-12);
+                assert!((ridged.get(point) - scale(full)).abs() < 1e-12);
             }
+        }
+    }
 
-            // Scale the amplitude appropriately for this frequency.
-            signal = signal * self.persistence.powi(math::cast(x));
+    #[test]
+    fn test_amplitudes_follow_parameters() {
+        let ridged = RidgedMulti::new().set_persistence(0.7).set_octaves(9);
+        let reordered = RidgedMulti::new().set_octaves(9).set_persistence(0.7);
+        for &point in points().iter() {
+            assert_eq!(ridged.get(point), reordered.get(point));
+        }
+
+        // The coarse copy has the amplitudes of a 4-octave module, but keeps
+        // the scaling of the full module.
+        let coarse = ridged.coarse(4);
+        let four = RidgedMulti::new().set_persistence(0.7).set_octaves(4);
+        assert_eq!(coarse.max_value(), ridged.max_value());
+        let rescale = four.max_value() / ridged.max_value();
+        for &point in points().iter() {
+            let expected = (four.get(point) + 1.0) * rescale - 1.0;
+            assert!((coarse.get(point) - expected).abs() < 1e-12);
+        }
+    }
 
-            // Add the signal to the result.
-            result = result + signal;
+    #[test]
+    fn test_invert_ridges() {
+        let ridged = RidgedMulti::new().set_octaves(1);
+        assert!(sharpest_point(&ridged) > 0.9);
+        assert!(sharpest_point(&ridged.set_invert_ridges(true)) < 0.1);
+    }
 
-            // Increase the frequency.
-            point = math::mul4(point, self.lacunarity);
+    #[test]
+    fn test_negative_persistence() {
+        for &persistence in [-0.5, -0.9, -1.0].iter() {
+            let ridged = RidgedMulti::new().set_persistence(persistence);
+            assert!(ridged.min_value() < 0.0);
+            assert_in_range(&ridged);
+            assert_in_range(&ridged.clone().set_normalization(Normalization::Theoretical));
         }
+        assert_eq!(RidgedMulti::<f64>::new().set_persistence(-1.0).min_value(), -3.0);
+    }
 
-        // Scale and shift the result into the [-1,1] range
-        result.mul_add(math::cast(1.0 / 3.0), -T::one())
+    #[test]
+    fn test_bounds() {
+        let ridged = RidgedMulti::<f64>::new();
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&ridged), (-1.0, 1.0));
+        let observed = ridged.set_normalization(Normalization::Observed(-0.5, 0.5));
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&observed), (-2.0, 2.0));
     }
 }
@@ -12,16 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use self::cell_fn::*;
 pub use self::checkerboard::*;
 pub use self::constant::*;
 pub use self::cylinders::*;
 pub use self::fractals::*;
+pub use self::open_simplex::*;
 pub use self::perlin::*;
+pub use self::spheres::*;
+pub use self::value::*;
 pub use self::worley::*;
 
-mod constant;
+mod cell_fn;
 mod checkerboard;
+mod constant;
 mod cylinders;
 mod fractals;
+mod open_simplex;
 mod perlin;
+mod spheres;
+mod value;
 mod worley;
@@ -0,0 +1,176 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use {NoiseModule, PermutationTable};
+use {open_simplex2, open_simplex3, open_simplex4};
+use modules::{Reseedable, Seedable};
+
+/// Default noise seed for the OpenSimplex noise module.
+pub const DEFAULT_OPEN_SIMPLEX_SEED: usize = 0;
+
+// Largest magnitudes of the output of `open_simplex2`, `open_simplex3` and
+// `open_simplex4`, found by hill climbing from many starting points over many
+// seeds. The output is divided by these to map it onto [-1, 1].
+const MAX_VALUE_2D: f64 = 0.544;
+const MAX_VALUE_3D: f64 = 0.538;
+const MAX_VALUE_4D: f64 = 1.12;
+
+/// Noise module that outputs 2/3/4-dimensional OpenSimplex noise.
+///
+/// OpenSimplex noise is gradient noise on a simplectic lattice, like Ken
+/// Perlin's simplex noise, but with an independent algorithm that is free of
+/// the patent that covered simplex noise. Compared to Perlin noise, it has
+/// fewer axis-aligned artifacts, at a higher cost per sample. It uses the
+/// same functions as `open_simplex2`, `open_simplex3` and `open_simplex4`,
+/// with the output rescaled onto the range -1.0 to 1.0.
+///
+/// The extremes of the noise can't be computed analytically, so the scaling
+/// is based on a numerical search. The rare values beyond it are clamped.
+///
+/// The lattice isn't aligned with the axes, so the noise can't be made
+/// periodic, and the module can't be the source of the fractal modules.
+#[derive(Clone, Copy, Debug)]
+pub struct OpenSimplex {
+    perm_table: PermutationTable,
+
+    /// Seed.
+    pub seed: usize,
+}
+
+impl OpenSimplex {
+    pub fn new(seed: usize) -> OpenSimplex {
+        OpenSimplex {
+            perm_table: PermutationTable::new(seed as u32),
+            seed: seed,
+        }
+    }
+
+    pub fn set_seed(self, seed: usize) -> OpenSimplex {
+        OpenSimplex::new(seed)
+    }
+}
+
+impl Default for OpenSimplex {
+    fn default() -> OpenSimplex {
+        OpenSimplex::new(DEFAULT_OPEN_SIMPLEX_SEED)
+    }
+}
+
+impl Seedable for OpenSimplex {
+    fn set_seed(self, seed: usize) -> OpenSimplex {
+        OpenSimplex::set_seed(self, seed)
+    }
+
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl Reseedable for OpenSimplex {
+    fn reseed(&mut self, seed: usize) {
+        self.perm_table = PermutationTable::new(seed as u32);
+        self.seed = seed;
+    }
+}
+
+/// Divides the value by the largest magnitude it can have, clamping the
+/// result to [-1, 1].
+#[inline(always)]
+fn normalize<T: Float>(value: T, max_value: f64) -> T {
+    let value = value / math::cast(max_value);
+    value.max(-T::one()).min(T::one())
+}
+
+/// 2-dimensional OpenSimplex noise
+impl<T: Float> NoiseModule<Point2<T>> for OpenSimplex {
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> T {
+        normalize(open_simplex2(&self.perm_table, &point), MAX_VALUE_2D)
+    }
+}
+
+/// 3-dimensional OpenSimplex noise
+impl<T: Float> NoiseModule<Point3<T>> for OpenSimplex {
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> T {
+        normalize(open_simplex3(&self.perm_table, &point), MAX_VALUE_3D)
+    }
+}
+
+/// 4-dimensional OpenSimplex noise
+impl<T: Float> NoiseModule<Point4<T>> for OpenSimplex {
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> T {
+        normalize(open_simplex4(&self.perm_table, &point), MAX_VALUE_4D)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_VALUE_2D, MAX_VALUE_3D, MAX_VALUE_4D, OpenSimplex};
+    use modules::Seedable;
+    use {NoiseModule, PermutationTable};
+    use {open_simplex2, open_simplex3, open_simplex4};
+
+    fn points() -> Vec<[f64; 4]> {
+        (0..4096)
+            .map(|i| {
+                let i = i as f64;
+                [i * 0.173 - 40.0, i * 0.311 + 3.0, i * -0.07, (i * 0.6180339887).fract() * 9.0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_range() {
+        let noise = OpenSimplex::new(3);
+        let mut max: [f64; 3] = [0.0; 3];
+        for p in points() {
+            let values: [f64; 3] =
+                [noise.get([p[0], p[1]]), noise.get([p[0], p[1], p[2]]), noise.get(p)];
+            for (max, &value) in max.iter_mut().zip(values.iter()) {
+                assert!(value >= -1.0 && value <= 1.0, "{} out of range", value);
+                *max = max.max(value.abs());
+            }
+        }
+
+        // The output spans most of the range.
+        for &max in max.iter() {
+            assert!(max > 0.7, "{} is too narrow", max);
+        }
+    }
+
+    #[test]
+    fn test_matches_functions() {
+        let noise = OpenSimplex::new(0).set_seed(11);
+        let perm_table = PermutationTable::new(11);
+        for p in points().into_iter().take(64) {
+            let expected = [open_simplex2(&perm_table, &[p[0], p[1]]) / MAX_VALUE_2D,
+                            open_simplex3(&perm_table, &[p[0], p[1], p[2]]) / MAX_VALUE_3D,
+                            open_simplex4(&perm_table, &p) / MAX_VALUE_4D];
+            let values: [f64; 3] =
+                [noise.get([p[0], p[1]]), noise.get([p[0], p[1], p[2]]), noise.get(p)];
+            for (&value, &expected) in values.iter().zip(expected.iter()) {
+                assert!((value - expected).abs() < 1e-12);
+            }
+        }
+        assert_eq!(Seedable::seed(&noise), 11);
+    }
+}
@@ -14,18 +14,305 @@
 
 use num_traits::Float;
 use math;
-use math::{Point2, Point3, Point4};
+use math::{Point1, Point2, Point3, Point4};
 use {NoiseModule, PermutationTable, gradient};
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use simd;
+use modules::{Periodic, Reseedable, Seedable};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-/// Noise module that outputs 2/3/4-dimensional Perlin noise.
+/// Default frequency for the Perlin noise module.
+pub const DEFAULT_PERLIN_FREQUENCY: f64 = 1.0;
+
+/// Noise module that outputs 1/2/3/4-dimensional Perlin noise.
+///
+/// With the `serde` feature, only the seed, offset, frequency, period and
+/// wrapping size are serialized. The permutation table is rebuilt from the
+/// seed on deserialization.
 #[derive(Clone, Copy, Debug)]
 pub struct Perlin {
+    seed: usize,
+
     perm_table: PermutationTable,
+
+    /// Offset applied to every permutation table lookup. See `with_offset`.
+    offset: usize,
+
+    /// Frequency the input coordinates are multiplied by. See
+    /// `set_frequency`.
+    frequency: f64,
+
+    /// Number of lattice cells after which the noise repeats along each axis,
+    /// if any. See `set_period`.
+    period: Option<Point4<usize>>,
+
+    /// Size of the range that input coordinates are wrapped into, if any. See
+    /// `set_wrap`.
+    wrap: Option<usize>,
+
+    /// Gradient vectors used by the noise in each dimension. See
+    /// `set_gradients2`.
+    gradients2: &'static [[f64; 2]],
+    gradients3: &'static [[f64; 3]],
+    gradients4: &'static [[f64; 4]],
 }
 
 impl Perlin {
     pub fn new(seed: usize) -> Perlin {
-        Perlin { perm_table: PermutationTable::new(seed as u32) }
+        Perlin {
+            seed: seed,
+            perm_table: PermutationTable::new(seed as u32),
+            offset: 0,
+            frequency: DEFAULT_PERLIN_FREQUENCY,
+            period: None,
+            wrap: None,
+            gradients2: &gradient::GRADIENTS_2D,
+            gradients3: &gradient::GRADIENTS_3D,
+            gradients4: &gradient::GRADIENTS_4D,
+        }
+    }
+
+    /// Returns a variant of this noise module that applies a constant offset
+    /// to every permutation table lookup.
+    ///
+    /// Each offset produces a different noise field from the same permutation
+    /// table, which is much cheaper than building a new table by reseeding.
+    /// This is useful for generating many variants of the same noise, such as
+    /// for scattering objects. An offset of 0 produces the original field.
+    pub fn with_offset(self, offset: usize) -> Perlin {
+        Perlin { offset: offset, ..self }
+    }
+
+    /// Sets the frequency of the noise, which the input coordinates are
+    /// multiplied by before sampling, as with the `set_frequency` of the
+    /// fractal noise modules. The lattice cells are 1 / `frequency` units
+    /// wide. The period and wrapping size are measured in lattice cells, so
+    /// they are applied after the frequency.
+    pub fn set_frequency(self, frequency: f64) -> Perlin {
+        Perlin { frequency: frequency, ..self }
+    }
+
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. Each axis wraps independently, and a period
+    /// of 0 disables tiling along that axis. The lower-dimensional noise only
+    /// uses the leading components.
+    pub fn set_period(self, period: Point4<usize>) -> Perlin {
+        let periodic = period.iter().any(|&p| p > 0);
+        Perlin { period: if periodic { Some(period) } else { None }, ..self }
+    }
+
+    /// Wraps every input coordinate into the range [0, `size`) before
+    /// sampling, and repeats the lattice with the same size along the axes
+    /// without a period of their own, so the noise stays continuous across
+    /// the wrap. A size of 0 disables wrapping.
+    ///
+    /// Far from the origin, the floor of a coordinate leaves little precision
+    /// for its fractional part, particularly with `f32`, and the noise
+    /// degrades into blocks. Wrapping keeps the coordinates used for the
+    /// lattice and surflet computations small, at the cost of making the
+    /// noise repeat every `size` units along each axis. A size large enough
+    /// that the repetition is never noticed, such as 4096, works well for
+    /// streaming worlds. Precision already lost in the input coordinates
+    /// before they reach the module can't be recovered, so a world streamed
+    /// in `f32` should also keep its own coordinates small.
+    ///
+    /// If a period is also set with `set_period`, `size` should be a multiple
+    /// of it along each axis, or the noise is discontinuous at the wrap.
+    pub fn set_wrap(self, size: usize) -> Perlin {
+        Perlin { wrap: if size > 0 { Some(size) } else { None }, ..self }
+    }
+
+    /// Returns the period of the lattice along each axis, combining the
+    /// period with the wrapping size, if either is set.
+    #[inline(always)]
+    fn lattice_period(&self) -> Option<Point4<usize>> {
+        match (self.period, self.wrap) {
+            (period, None) => period,
+            (None, Some(size)) => Some([size; 4]),
+            (Some(period), Some(size)) => {
+                Some(math::map4(period, |p| if p > 0 { p } else { size }))
+            }
+        }
+    }
+
+    /// Multiplies an input coordinate by the frequency, then wraps it into
+    /// the range [0, `size`) if wrapping is enabled.
+    #[inline(always)]
+    fn scale_and_wrap<T: Float>(&self, x: T) -> T {
+        let x = x * math::cast(self.frequency);
+        match self.wrap {
+            Some(size) => {
+                let size: T = math::cast(size);
+                x - (x / size).floor() * size
+            }
+            None => x,
+        }
+    }
+
+    /// Sets the gradient vectors used by the 2-dimensional noise.
+    ///
+    /// Each lattice point selects a gradient from the table by its hashed
+    /// index, wrapping around the length of the table, so the gradients should
+    /// be unit vectors spread evenly around the circle. Matching the table of
+    /// another implementation allows its output to be reproduced. By default,
+    /// `gradient::GRADIENTS_2D` is used. Custom tables are not serialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gradients` is empty.
+    pub fn set_gradients2(self, gradients: &'static [[f64; 2]]) -> Perlin {
+        assert!(!gradients.is_empty(), "gradient table is empty");
+        Perlin { gradients2: gradients, ..self }
+    }
+
+    /// Sets the gradient vectors used by the 3-dimensional noise. See
+    /// `set_gradients2`.
+    pub fn set_gradients3(self, gradients: &'static [[f64; 3]]) -> Perlin {
+        assert!(!gradients.is_empty(), "gradient table is empty");
+        Perlin { gradients3: gradients, ..self }
+    }
+
+    /// Sets the gradient vectors used by the 4-dimensional noise. See
+    /// `set_gradients2`.
+    pub fn set_gradients4(self, gradients: &'static [[f64; 4]]) -> Perlin {
+        assert!(!gradients.is_empty(), "gradient table is empty");
+        Perlin { gradients4: gradients, ..self }
+    }
+
+    /// Applies the offset to a hashed lattice point, with an extra lookup so
+    /// that neighbouring offsets produce unrelated gradients. Only the offset
+    /// modulo 256 matters, so the sum is allowed to wrap.
+    #[inline(always)]
+    fn hash(&self, index: usize) -> usize {
+        if self.offset == 0 {
+            index
+        } else {
+            self.perm_table.get1(index.wrapping_add(self.offset) as isize)
+        }
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Perlin {
+        Perlin::new(0)
+    }
+}
+
+impl Seedable for Perlin {
+    fn set_seed(self, seed: usize) -> Perlin {
+        Perlin {
+            seed: seed,
+            perm_table: PermutationTable::new(seed as u32),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl Periodic for Perlin {
+    fn set_period(self, period: Point4<usize>) -> Perlin {
+        Perlin::set_period(self, period)
+    }
+}
+
+impl Reseedable for Perlin {
+    fn reseed(&mut self, seed: usize) {
+        self.seed = seed;
+        self.perm_table = PermutationTable::new(seed as u32);
+    }
+}
+
+/// The parameters of a `Perlin` module that are serialized.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct PerlinParams {
+    seed: usize,
+    offset: usize,
+    #[serde(default = "default_frequency", skip_serializing_if = "is_default_frequency")]
+    frequency: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    period: Option<Point4<usize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wrap: Option<usize>,
+}
+
+#[cfg(feature = "serde")]
+fn default_frequency() -> f64 {
+    DEFAULT_PERLIN_FREQUENCY
+}
+
+#[cfg(feature = "serde")]
+fn is_default_frequency(frequency: &f64) -> bool {
+    *frequency == DEFAULT_PERLIN_FREQUENCY
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Perlin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PerlinParams {
+                seed: self.seed,
+                offset: self.offset,
+                frequency: self.frequency,
+                period: self.period,
+                wrap: self.wrap,
+            }
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Perlin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Perlin, D::Error> {
+        let params = PerlinParams::deserialize(deserializer)?;
+        let perlin = Perlin::new(params.seed).with_offset(params.offset);
+        Ok(Perlin {
+            frequency: params.frequency,
+            period: params.period,
+            wrap: params.wrap,
+            ..perlin
+        })
+    }
+}
+
+/// 1-dimensional perlin noise
+impl<T: Float> NoiseModule<Point1<T>> for Perlin {
+    type Output = T;
+
+    fn get(&self, point: Point1<T>) -> T {
+        #[inline(always)]
+        fn surflet<T: Float>(perlin: &Perlin, corner: isize, distance: T) -> T {
+            let attn = T::one() - distance * distance;
+            if attn > T::zero() {
+                let index = perlin.hash(perlin.perm_table.get1(corner));
+                let gradient = if index & 1 == 0 { T::one() } else { -T::one() };
+                math::pow4(attn) * distance * gradient
+            } else {
+                T::zero()
+            }
+        }
+
+        let point = [self.scale_and_wrap(point[0])];
+        let floored = point[0].floor();
+        let mut near_corner: isize = math::cast(floored);
+        let mut far_corner = near_corner + 1;
+        if let Some(period) = self.lattice_period() {
+            let period = [period[0] as isize, period[0] as isize];
+            let wrapped = math::mod2([near_corner, far_corner], period);
+            near_corner = wrapped[0];
+            far_corner = wrapped[1];
+        }
+        let near_distance = point[0] - floored;
+        let far_distance = near_distance - T::one();
+
+        let f0 = surflet(self, near_corner, near_distance);
+        let f1 = surflet(self, far_corner, far_distance);
+
+        // Multiply by arbitrary value to scale to -1..1
+        (f0 + f1) * math::cast(3.1604938271604937)
     }
 }
 
@@ -35,34 +322,41 @@ impl<T: Float> NoiseModule<Point2<T>> for Perlin {
 
     fn get(&self, point: Point2<T>) -> T {
         #[inline(always)]
-        fn surflet<T: Float>(perm_table: &PermutationTable,
+        fn surflet<T: Float>(perlin: &Perlin,
                              corner: math::Point2<isize>,
                              distance: math::Vector2<T>)
                              -> T {
             let attn = T::one() - math::dot2(distance, distance);
             if attn > T::zero() {
-                math::pow4(attn) * math::dot2(distance, gradient::get2(perm_table.get2(corner)))
+                let index = perlin.hash(perlin.perm_table.get2(corner));
+                math::pow4(attn) * math::dot2(distance, gradient::get2_from(perlin.gradients2, index))
             } else {
                 T::zero()
             }
         }
 
+        let point = math::map2(point, |x| self.scale_and_wrap(x));
         let floored = math::map2(point, T::floor);
-        let near_corner = math::map2(floored, math::cast);
-        let far_corner = math::add2(near_corner, math::one2());
+        let mut near_corner = math::map2(floored, math::cast);
+        let mut far_corner = math::add2(near_corner, math::one2());
+        if let Some(period) = self.lattice_period() {
+            let period = [period[0] as isize, period[1] as isize];
+            near_corner = math::mod2(near_corner, period);
+            far_corner = math::mod2(far_corner, period);
+        }
         let near_distance = math::sub2(point, floored);
         let far_distance = math::sub2(near_distance, math::one2());
 
-        let f00 = surflet(&self.perm_table,
+        let f00 = surflet(self,
                           [near_corner[0], near_corner[1]],
                           [near_distance[0], near_distance[1]]);
-        let f10 = surflet(&self.perm_table,
+        let f10 = surflet(self,
                           [far_corner[0], near_corner[1]],
                           [far_distance[0], near_distance[1]]);
-        let f01 = surflet(&self.perm_table,
+        let f01 = surflet(self,
                           [near_corner[0], far_corner[1]],
                           [near_distance[0], far_distance[1]]);
-        let f11 = surflet(&self.perm_table,
+        let f11 = surflet(self,
                           [far_corner[0], far_corner[1]],
                           [far_distance[0], far_distance[1]]);
 
@@ -77,46 +371,53 @@ impl<T: Float> NoiseModule<Point3<T>> for Perlin {
 
     fn get(&self, point: Point3<T>) -> T {
         #[inline(always)]
-        fn surflet<T: Float>(perm_table: &PermutationTable,
+        fn surflet<T: Float>(perlin: &Perlin,
                              corner: math::Point3<isize>,
                              distance: math::Vector3<T>)
                              -> T {
             let attn = T::one() - math::dot3(distance, distance);
             if attn > T::zero() {
-                math::pow4(attn) * math::dot3(distance, gradient::get3(perm_table.get3(corner)))
+                let index = perlin.hash(perlin.perm_table.get3(corner));
+                math::pow4(attn) * math::dot3(distance, gradient::get3_from(perlin.gradients3, index))
             } else {
                 T::zero()
             }
         }
 
+        let point = math::map3(point, |x| self.scale_and_wrap(x));
         let floored = math::map3(point, T::floor);
-        let near_corner = math::map3(floored, math::cast);
-        let far_corner = math::add3(near_corner, math::one3());
+        let mut near_corner = math::map3(floored, math::cast);
+        let mut far_corner = math::add3(near_corner, math::one3());
+        if let Some(period) = self.lattice_period() {
+            let period = [period[0] as isize, period[1] as isize, period[2] as isize];
+            near_corner = math::mod3(near_corner, period);
+            far_corner = math::mod3(far_corner, period);
+        }
         let near_distance = math::sub3(point, floored);
         let far_distance = math::sub3(near_distance, math::one3());
 
-        let f000 = surflet(&self.perm_table,
+        let f000 = surflet(self,
                            [near_corner[0], near_corner[1], near_corner[2]],
                            [near_distance[0], near_distance[1], near_distance[2]]);
-        let f100 = surflet(&self.perm_table,
+        let f100 = surflet(self,
                            [far_corner[0], near_corner[1], near_corner[2]],
                            [far_distance[0], near_distance[1], near_distance[2]]);
-        let f010 = surflet(&self.perm_table,
+        let f010 = surflet(self,
                            [near_corner[0], far_corner[1], near_corner[2]],
                            [near_distance[0], far_distance[1], near_distance[2]]);
-        let f110 = surflet(&self.perm_table,
+        let f110 = surflet(self,
                            [far_corner[0], far_corner[1], near_corner[2]],
                            [far_distance[0], far_distance[1], near_distance[2]]);
-        let f001 = surflet(&self.perm_table,
+        let f001 = surflet(self,
                            [near_corner[0], near_corner[1], far_corner[2]],
                            [near_distance[0], near_distance[1], far_distance[2]]);
-        let f101 = surflet(&self.perm_table,
+        let f101 = surflet(self,
                            [far_corner[0], near_corner[1], far_corner[2]],
                            [far_distance[0], near_distance[1], far_distance[2]]);
-        let f011 = surflet(&self.perm_table,
+        let f011 = surflet(self,
                            [near_corner[0], far_corner[1], far_corner[2]],
                            [near_distance[0], far_distance[1], far_distance[2]]);
-        let f111 = surflet(&self.perm_table,
+        let f111 = surflet(self,
                            [far_corner[0], far_corner[1], far_corner[2]],
                            [far_distance[0], far_distance[1], far_distance[2]]);
 
@@ -129,77 +430,110 @@ impl<T: Float> NoiseModule<Point3<T>> for Perlin {
 impl<T: Float> NoiseModule<Point4<T>> for Perlin {
     type Output = T;
 
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    fn get(&self, point: Point4<T>) -> T {
+        self.get4_scalar(point)
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
     fn get(&self, point: Point4<T>) -> T {
+        self.get4_simd(point)
+    }
+}
+
+impl Perlin {
+    /// Returns the lattice corners of the cell containing the point, after
+    /// scaling it by the frequency and wrapping it if wrapping is enabled, and
+    /// the distances from them to the point. The corners are wrapped by the
+    /// lattice period if there is one.
+    #[inline(always)]
+    fn cell4<T: Float>(&self,
+                       point: Point4<T>)
+                       -> (Point4<isize>, Point4<isize>, math::Vector4<T>, math::Vector4<T>) {
+        let point = math::map4(point, |x| self.scale_and_wrap(x));
+        let floored = math::map4(point, T::floor);
+        let mut near_corner = math::map4(floored, math::cast);
+        let mut far_corner = math::add4(near_corner, math::one4());
+        if let Some(period) = self.lattice_period() {
+            let period = math::cast4(period);
+            near_corner = math::mod4(near_corner, period);
+            far_corner = math::mod4(far_corner, period);
+        }
+        let near_distance = math::sub4(point, floored);
+        let far_distance = math::sub4(near_distance, math::one4());
+
+        (near_corner, far_corner, near_distance, far_distance)
+    }
+
+    #[cfg_attr(all(feature = "simd", target_arch = "x86_64"), allow(dead_code))]
+    fn get4_scalar<T: Float>(&self, point: Point4<T>) -> T {
         #[inline(always)]
-        fn surflet<T: Float>(perm_table: &PermutationTable,
+        fn surflet<T: Float>(perlin: &Perlin,
                              corner: math::Point4<isize>,
                              distance: math::Vector4<T>)
                              -> T {
             let attn = T::one() - math::dot4(distance, distance);
             if attn > T::zero() {
-                math::pow4(attn) * math::dot4(distance, gradient::get4(perm_table.get4(corner)))
+                let index = perlin.hash(perlin.perm_table.get4(corner));
+                math::pow4(attn) * math::dot4(distance, gradient::get4_from(perlin.gradients4, index))
             } else {
                 T::zero()
             }
         }
 
-        let floored = math::map4(point, T::floor);
-        let near_corner = math::map4(floored, math::cast);
-        let far_corner = math::add4(near_corner, math::one4());
-        let near_distance = math::sub4(point, floored);
-        let far_distance = math::sub4(near_distance, math::one4());
+        let (near_corner, far_corner, near_distance, far_distance) = self.cell4(point);
 
         let f0000 =
-            surflet(&self.perm_table,
+            surflet(self,
                     [near_corner[0], near_corner[1], near_corner[2], near_corner[3]],
                     [near_distance[0], near_distance[1], near_distance[2], near_distance[3]]);
         let f1000 =
-            surflet(&self.perm_table,
+            surflet(self,
                     [far_corner[0], near_corner[1], near_corner[2], near_corner[3]],
                     [far_distance[0], near_distance[1], near_distance[2], near_distance[3]]);
         let f0100 =
-            surflet(&self.perm_table,
+            surflet(self,
                     [near_corner[0], far_corner[1], near_corner[2], near_corner[3]],
                     [near_distance[0], far_distance[1], near_distance[2], near_distance[3]]);
-        let f1100 = surflet(&self.perm_table,
+        let f1100 = surflet(self,
                             [far_corner[0], far_corner[1], near_corner[2], near_corner[3]],
                             [far_distance[0], far_distance[1], near_distance[2], near_distance[3]]);
         let f0010 =
-            surflet(&self.perm_table,
+            surflet(self,
                     [near_corner[0], near_corner[1], far_corner[2], near_corner[3]],
                     [near_distance[0], near_distance[1], far_distance[2], near_distance[3]]);
-        let f1010 = surflet(&self.perm_table,
+        let f1010 = surflet(self,
                             [far_corner[0], near_corner[1], far_corner[2], near_corner[3]],
                             [far_distance[0], near_distance[1], far_distance[2], near_distance[3]]);
-        let f0110 = surflet(&self.perm_table,
+        let f0110 = surflet(self,
                             [near_corner[0], far_corner[1], far_corner[2], near_corner[3]],
                             [near_distance[0], far_distance[1], far_distance[2], near_distance[3]]);
-        let f1110 = surflet(&self.perm_table,
+        let f1110 = surflet(self,
                             [far_corner[0], far_corner[1], far_corner[2], near_corner[3]],
                             [far_distance[0], far_distance[1], far_distance[2], near_distance[3]]);
         let f0001 =
-            surflet(&self.perm_table,
+            surflet(self,
                     [near_corner[0], near_corner[1], near_corner[2], far_corner[3]],
                     [near_distance[0], near_distance[1], near_distance[2], far_distance[3]]);
-        let f1001 = surflet(&self.perm_table,
+        let f1001 = surflet(self,
                             [far_corner[0], near_corner[1], near_corner[2], far_corner[3]],
                             [far_distance[0], near_distance[1], near_distance[2], far_distance[3]]);
-        let f0101 = surflet(&self.perm_table,
+        let f0101 = surflet(self,
                             [near_corner[0], far_corner[1], near_corner[2], far_corner[3]],
                             [near_distance[0], far_distance[1], near_distance[2], far_distance[3]]);
-        let f1101 = surflet(&self.perm_table,
+        let f1101 = surflet(self,
                             [far_corner[0], far_corner[1], near_corner[2], far_corner[3]],
                             [far_distance[0], far_distance[1], near_distance[2], far_distance[3]]);
-        let f0011 = surflet(&self.perm_table,
+        let f0011 = surflet(self,
                             [near_corner[0], near_corner[1], far_corner[2], far_corner[3]],
                             [near_distance[0], near_distance[1], far_distance[2], far_distance[3]]);
-        let f1011 = surflet(&self.perm_table,
+        let f1011 = surflet(self,
                             [far_corner[0], near_corner[1], far_corner[2], far_corner[3]],
                             [far_distance[0], near_distance[1], far_distance[2], far_distance[3]]);
-        let f0111 = surflet(&self.perm_table,
+        let f0111 = surflet(self,
                             [near_corner[0], far_corner[1], far_corner[2], far_corner[3]],
                             [near_distance[0], far_distance[1], far_distance[2], far_distance[3]]);
-        let f1111 = surflet(&self.perm_table,
+        let f1111 = surflet(self,
                             [far_corner[0], far_corner[1], far_corner[2], far_corner[3]],
                             [far_distance[0], far_distance[1], far_distance[2], far_distance[3]]);
 
@@ -208,4 +542,238 @@ impl<T: Float> NoiseModule<Point4<T>> for Perlin {
          f1001 + f0101 + f1101 + f0011 + f1011 + f0111 + f1111) *
         math::cast(4.424369240215691)
     }
+
+    /// Like `get4_scalar`, but gathers the sixteen surflets and evaluates them
+    /// in SIMD registers. The surflets are computed in `f64` and summed in a
+    /// different order, so the result can differ from the scalar path by
+    /// rounding error.
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn get4_simd<T: Float>(&self, point: Point4<T>) -> T {
+        let (near_corner, far_corner, near_distance, far_distance) = self.cell4(point);
+
+        let mut distances = [[0.0; 4]; 16];
+        let mut gradients = [[0.0; 4]; 16];
+        let surflets = distances.iter_mut().zip(gradients.iter_mut());
+        for (i, (distance, gradient)) in surflets.enumerate() {
+            // Bit `axis` of the surflet index selects the far corner on that
+            // axis.
+            let mut corner = near_corner;
+            for axis in 0..4 {
+                if i & (1 << axis) == 0 {
+                    distance[axis] = math::cast(near_distance[axis]);
+                } else {
+                    corner[axis] = far_corner[axis];
+                    distance[axis] = math::cast(far_distance[axis]);
+                }
+            }
+            let index = self.hash(self.perm_table.get4(corner));
+            *gradient = gradient::get4_from(self.gradients4, index);
+        }
+
+        math::cast(simd::sum_surflets4(&distances, &gradients) * 4.424369240215691)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Perlin;
+    use math;
+    use test_utils::{sample_points2, sample_points3, sample_points4};
+    use NoiseModule;
+
+    /// Points at which the output is pinned, in and away from the origin's
+    /// neighbourhood.
+    const PINNED_POINTS: [[f64; 4]; 4] = [
+        [0.37, 1.25, -2.75, 3.1],
+        [13.37, -7.5, 0.3, 0.9],
+        [-101.9, 42.42, 6.125, -0.6],
+        [1000.3, 2.7, -54.1, 77.7],
+    ];
+
+    // The output of `Perlin::new(0)` at the pinned points, in 1 to 4
+    // dimensions. These values are part of the crate's output guarantee, and
+    // must only change on purpose: seeded worlds and lockstep simulations
+    // rely on every platform producing them.
+    const PINNED_VALUES: [[f64; 4]; 4] = [
+        [0.3855124431464335, -0.5723098928945003, -0.1550953255640043, -0.26241317825753385],
+        [-0.38551244314643546, -0.5849006899127513, -0.12675415004248042, 0.07061365983319035],
+        [-0.2998888675555404, -0.19604313993970837, -0.4582594250919118, -0.507522214052635],
+        [-0.799861854814729, -0.09904437670812036, 0.23151353073072733, -0.5452356342235559],
+    ];
+
+    #[test]
+    fn test_pinned_output() {
+        let perlin = Perlin::new(0);
+        for (p, expected) in PINNED_POINTS.iter().zip(PINNED_VALUES.iter()) {
+            let values: [f64; 4] = [
+                perlin.get([p[0]]),
+                perlin.get([p[0], p[1]]),
+                perlin.get([p[0], p[1], p[2]]),
+                perlin.get(*p),
+            ];
+            for (&value, &expected) in values.iter().zip(expected.iter()) {
+                assert!((value - expected).abs() < 1e-12, "{} != {}", value, expected);
+            }
+
+            // f32 loses precision in the input coordinates themselves, so it
+            // only agrees with the f64 output approximately.
+            let point = [p[0] as f32, p[1] as f32, p[2] as f32];
+            let value: f32 = perlin.get(point);
+            assert!((value as f64 - expected[2]).abs() < 1e-4, "{} != {}", value, expected[2]);
+        }
+    }
+
+    #[test]
+    fn test_offset() {
+        let base = Perlin::new(0);
+        let zero = base.with_offset(0);
+        let one = base.with_offset(1);
+        let two = base.with_offset(2);
+
+        // Offsets near the top of the range wrap rather than overflow.
+        let max = base.with_offset(usize::max_value());
+        let wrapped = base.with_offset(255);
+
+        let mut differs = (false, false);
+        for &point in sample_points3(32).iter() {
+            let value: f64 = base.get(point);
+            assert_eq!(zero.get(point), value);
+            differs.0 |= one.get(point) != value;
+            differs.1 |= two.get(point) != one.get(point);
+            assert_eq!(max.get(point), wrapped.get(point));
+        }
+        assert!(differs.0 && differs.1);
+    }
+
+    #[test]
+    fn test_get_many() {
+        let perlin = Perlin::new(0);
+        let points = sample_points2(32);
+        let mut out = vec![0.0; 32];
+        perlin.get_many(&points, &mut out);
+        for (&point, &value) in points.iter().zip(out.iter()) {
+            assert_eq!(value, perlin.get(point));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_many_length_mismatch() {
+        Perlin::new(0).get_many(&[[0.5, 0.5]; 3], &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn test_period_4d() {
+        let period = [9, 2, 8, 5];
+        let perlin = Perlin::new(0).set_period(period);
+
+        let mut differs = [false; 4];
+        for &point in sample_points4(32).iter() {
+            let value: f64 = perlin.get(point);
+            for axis in 0..4 {
+                let mut shifted = point;
+                shifted[axis] += period[axis] as f64;
+                assert!((perlin.get(shifted) - value).abs() < 1e-9);
+
+                // The other axes are not wrapped by this axis's period.
+                let mut other = point;
+                other[(axis + 1) % 4] += period[axis] as f64 + 0.5;
+                differs[axis] |= (perlin.get(other) - value).abs() > 1e-9;
+            }
+        }
+        assert!(differs.iter().all(|&d| d));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let perlin = Perlin::new(0).set_wrap(64);
+        for &point in sample_points3(64).iter() {
+            let value: f64 = perlin.get(point);
+            let far = [point[0] + 64.0 * 1e9, point[1] - 64.0 * 3e8, point[2] + 64.0];
+            assert!((perlin.get(far) - value).abs() < 1e-4);
+        }
+
+        // The noise is continuous across the wrap.
+        let below: f64 = perlin.get([64.0 - 1e-9, 10.3]);
+        let above: f64 = perlin.get([1e-9, 10.3]);
+        assert!((below - above).abs() < 1e-6);
+
+        // Away from the wrap, the noise is unchanged.
+        let point = [3.7, 20.1, 5.5, 0.25];
+        let value: f64 = Perlin::new(0).get(point);
+        assert!((perlin.get(point) - value).abs() < 1e-12);
+
+        // An explicit period takes precedence on its axis.
+        let periodic = perlin.set_period([8, 0, 0, 0]);
+        let value: f64 = periodic.get([1.3, 2.4]);
+        assert!((periodic.get([9.3, 2.4]) - value).abs() < 1e-9);
+        assert!((periodic.get([1.3, 66.4]) - value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frequency() {
+        let perlin = Perlin::new(0);
+        let scaled = perlin.set_frequency(2.5);
+        for &point in sample_points3(32).iter() {
+            let value: f64 = perlin.get(math::mul3(point, 2.5));
+            assert_eq!(scaled.get(point), value);
+        }
+
+        // The wrapping size is measured in lattice cells.
+        let wrapped = scaled.set_wrap(16);
+        let value: f64 = wrapped.get([1.3, 2.4]);
+        assert!((wrapped.get([1.3 + 16.0 / 2.5, 2.4]) - value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_custom_gradients() {
+        static DEFAULT_2D: [[f64; 2]; 8] = ::gradient::GRADIENTS_2D;
+        static AXES_2D: [[f64; 2]; 4] = [[1.0, 0.0], [-1.0, 0.0], [0.0, 1.0], [0.0, -1.0]];
+
+        let base = Perlin::new(0);
+        let default = base.set_gradients2(&DEFAULT_2D);
+        let axes = base.set_gradients2(&AXES_2D);
+
+        let mut differs = false;
+        for &point in sample_points2(32).iter() {
+            let value: f64 = base.get(point);
+            assert_eq!(default.get(point), value);
+            differs |= axes.get(point) != value;
+
+            // The 3-dimensional noise is unaffected.
+            let point = [point[0], point[1], 0.6];
+            assert_eq!(axes.get(point), base.get(point));
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_1d() {
+        let perlin = Perlin::new(0).set_period([5, 0, 0, 0]);
+
+        let mut differs = false;
+        for i in 0..256 {
+            let x = i as f64 * 0.173 - 20.0;
+            let value: f64 = perlin.get([x]);
+            assert!(value >= -1.0 && value <= 1.0);
+            assert!((perlin.get([x + 5.0]) - value).abs() < 1e-9);
+            differs |= Perlin::new(1).get([x]) != value;
+        }
+        assert!(differs);
+        assert_eq!(perlin.get([3.0]), 0.0);
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_simd_matches_scalar() {
+        let perlin = Perlin::new(0);
+        let periodic = Perlin::new(3).set_period([3, 0, 4, 2]);
+        for &point in sample_points4(256).iter() {
+            assert!((perlin.get4_simd(point) - perlin.get4_scalar(point)).abs() < 1e-12);
+            assert!((periodic.get4_simd(point) - periodic.get4_scalar(point)).abs() < 1e-12);
+
+            let point = [point[0] as f32, point[1] as f32, point[2] as f32, point[3] as f32];
+            assert!((perlin.get4_simd(point) - perlin.get4_scalar(point)).abs() < 1e-5);
+        }
+    }
 }
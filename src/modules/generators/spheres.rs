@@ -0,0 +1,121 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that outputs concentric spheres.
+///
+/// This noise module outputs concentric spheres centered on the origin, like
+/// the concentric rings of an onion. The output only depends on the distance
+/// from the origin, using all of the coordinates of the input value.
+///
+/// The output value is 1.0 on spheres of integer radius (divided by the
+/// frequency), and falls to -1.0 halfway between them.
+#[derive(Clone, Copy, Debug)]
+pub struct Spheres<T: Float> {
+    /// Frequency of the concentric spheres.
+    pub frequency: T,
+}
+
+impl<T: Float> Spheres<T> {
+    pub fn new(frequency: T) -> Spheres<T> {
+        Spheres { frequency: frequency }
+    }
+
+    /// Sets the frequency of the concentric spheres. Increasing the frequency
+    /// moves the spheres closer together.
+    pub fn set_frequency(self, frequency: T) -> Spheres<T> {
+        Spheres { frequency: frequency }
+    }
+}
+
+/// Calculates the output value given the distance from the origin.
+fn spheres<T: Float>(dist_from_center: T) -> T {
+    let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
+    let dist_from_larger_sphere = T::one() - dist_from_smaller_sphere;
+    let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
+    T::one() - (nearest_dist * math::cast(4.0))
+}
+
+impl<T: Float> Reseedable for Spheres<T> {
+    fn reseed(&mut self, _seed: usize) {}
+}
+
+impl<T: Float> NoiseModule<Point2<T>> for Spheres<T> {
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let point = math::mul2(point, self.frequency);
+        spheres(math::dot2(point, point).sqrt())
+    }
+}
+
+impl<T: Float> NoiseModule<Point3<T>> for Spheres<T> {
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let point = math::mul3(point, self.frequency);
+        spheres(math::dot3(point, point).sqrt())
+    }
+}
+
+impl<T: Float> NoiseModule<Point4<T>> for Spheres<T> {
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> Self::Output {
+        let point = math::mul4(point, self.frequency);
+        spheres(math::dot4(point, point).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Spheres;
+    use NoiseModule;
+
+    #[test]
+    fn test_peaks_on_integer_radii() {
+        let spheres = Spheres::<f64>::new(1.0);
+        for radius in 1..5 {
+            let on = radius as f64 / 2.0;
+            let between = (radius as f64 + 0.5) / 2.0;
+            assert!((spheres.get([on, on, on, on]) - 1.0).abs() < 1e-12);
+            assert!((spheres.get([between, between, between, between]) + 1.0).abs() < 1e-12);
+
+            let on = radius as f64 * 0.6;
+            let between = (radius as f64 + 0.5) * 0.6;
+            assert!((spheres.get([on, on * 4.0 / 3.0]) - 1.0).abs() < 1e-12);
+            assert!((spheres.get([between, 0.0, between * 4.0 / 3.0]) + 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_frequency_scales_spacing() {
+        let spheres = Spheres::<f64>::new(1.0).set_frequency(4.0);
+        assert!((spheres.get([0.0, 0.25, 0.0]) - 1.0).abs() < 1e-12);
+        assert!((spheres.get([0.0, 0.0, 0.375]) + 1.0).abs() < 1e-12);
+
+        let unit = Spheres::<f64>::new(1.0);
+        for i in 0..16 {
+            let point = [i as f64 * 0.21 - 1.3, i as f64 * -0.17 + 0.9, 0.4];
+            let scaled = [point[0] * 4.0, point[1] * 4.0, point[2] * 4.0];
+            assert!((spheres.get(point) - unit.get(scaled)).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use math::interp;
+use {NoiseModule, PermutationTable};
+use modules::{Periodic, Reseedable, Seedable};
+
+/// Default noise seed for the Value noise module.
+pub const DEFAULT_VALUE_SEED: usize = 0;
+
+/// Noise module that outputs 2/3/4-dimensional Value noise.
+///
+/// Each integer lattice point is assigned a pseudo-random value, and the
+/// output is interpolated between the values of the surrounding lattice
+/// points with a smooth S-curve. Value noise is cheaper than Perlin noise, and
+/// has a blockier look.
+#[derive(Clone, Copy, Debug)]
+pub struct Value {
+    perm_table: PermutationTable,
+
+    /// Seed.
+    pub seed: usize,
+
+    /// Number of lattice cells after which the noise repeats along each axis,
+    /// if any.
+    pub period: Option<Point4<usize>>,
+}
+
+impl Value {
+    pub fn new(seed: usize) -> Value {
+        Value {
+            perm_table: PermutationTable::new(seed as u32),
+            seed: seed,
+            period: None,
+        }
+    }
+
+    pub fn set_seed(self, seed: usize) -> Value {
+        Value {
+            perm_table: PermutationTable::new(seed as u32),
+            seed: seed,
+            ..self
+        }
+    }
+
+    /// Makes the noise repeat every `period[i]` units along axis `i`, so that
+    /// it can be tiled seamlessly. Each axis wraps independently, and a period
+    /// of 0 disables tiling along that axis. The 2- and 3-dimensional noise
+    /// only use the leading components.
+    pub fn set_period(self, period: Point4<usize>) -> Value {
+        let periodic = period.iter().any(|&p| p > 0);
+        Value { period: if periodic { Some(period) } else { None }, ..self }
+    }
+}
+
+impl Default for Value {
+    fn default() -> Value {
+        Value::new(0)
+    }
+}
+
+impl Seedable for Value {
+    fn set_seed(self, seed: usize) -> Value {
+        Value::set_seed(self, seed)
+    }
+
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl Periodic for Value {
+    fn set_period(self, period: Point4<usize>) -> Value {
+        Value::set_period(self, period)
+    }
+}
+
+impl Reseedable for Value {
+    fn reseed(&mut self, seed: usize) {
+        self.perm_table = PermutationTable::new(seed as u32);
+        self.seed = seed;
+    }
+}
+
+/// 2-dimensional value noise
+impl<T: Float> NoiseModule<Point2<T>> for Value {
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> T {
+        #[inline(always)]
+        fn get<T: Float>(perm_table: &PermutationTable, corner: Point2<isize>) -> T {
+            math::cast::<_, T>(perm_table.get2(corner)) * math::cast(1.0 / 255.0)
+        }
+
+        let floored = math::map2(point, T::floor);
+        let mut near_corner = math::map2(floored, math::cast);
+        let mut far_corner = math::add2(near_corner, math::one2());
+        let weight = math::map2(math::sub2(point, floored), interp::s_curve5);
+
+        if let Some(period) = self.period {
+            let period = [period[0] as isize, period[1] as isize];
+            near_corner = math::mod2(near_corner, period);
+            far_corner = math::mod2(far_corner, period);
+        }
+
+        let f00 = get(&self.perm_table, [near_corner[0], near_corner[1]]);
+        let f10 = get(&self.perm_table, [far_corner[0], near_corner[1]]);
+        let f01 = get(&self.perm_table, [near_corner[0], far_corner[1]]);
+        let f11 = get(&self.perm_table, [far_corner[0], far_corner[1]]);
+
+        let d0 = interp::linear(f00, f10, weight[0]);
+        let d1 = interp::linear(f01, f11, weight[0]);
+        let d = interp::linear(d0, d1, weight[1]);
+
+        d * math::cast(2) - math::cast(1)
+    }
+}
+
+/// 3-dimensional value noise
+impl<T: Float> NoiseModule<Point3<T>> for Value {
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> T {
+        #[inline(always)]
+        fn get<T: Float>(perm_table: &PermutationTable, corner: Point3<isize>) -> T {
+            math::cast::<_, T>(perm_table.get3(corner)) * math::cast(1.0 / 255.0)
+        }
+
+        let floored = math::map3(point, T::floor);
+        let mut near_corner = math::map3(floored, math::cast);
+        let mut far_corner = math::add3(near_corner, math::one3());
+        let weight = math::map3(math::sub3(point, floored), interp::s_curve5);
+
+        if let Some(period) = self.period {
+            let period = [period[0] as isize, period[1] as isize, period[2] as isize];
+            near_corner = math::mod3(near_corner, period);
+            far_corner = math::mod3(far_corner, period);
+        }
+
+        let (n, f) = (near_corner, far_corner);
+        let f000: T = get(&self.perm_table, [n[0], n[1], n[2]]);
+        let f100: T = get(&self.perm_table, [f[0], n[1], n[2]]);
+        let f010: T = get(&self.perm_table, [n[0], f[1], n[2]]);
+        let f110: T = get(&self.perm_table, [f[0], f[1], n[2]]);
+        let f001: T = get(&self.perm_table, [n[0], n[1], f[2]]);
+        let f101: T = get(&self.perm_table, [f[0], n[1], f[2]]);
+        let f011: T = get(&self.perm_table, [n[0], f[1], f[2]]);
+        let f111: T = get(&self.perm_table, [f[0], f[1], f[2]]);
+
+        let d00 = interp::linear(f000, f100, weight[0]);
+        let d01 = interp::linear(f001, f101, weight[0]);
+        let d10 = interp::linear(f010, f110, weight[0]);
+        let d11 = interp::linear(f011, f111, weight[0]);
+        let d0 = interp::linear(d00, d10, weight[1]);
+        let d1 = interp::linear(d01, d11, weight[1]);
+        let d = interp::linear(d0, d1, weight[2]);
+
+        d * math::cast(2) - math::cast(1)
+    }
+}
+
+/// 4-dimensional value noise
+impl<T: Float> NoiseModule<Point4<T>> for Value {
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> T {
+        #[inline(always)]
+        fn get<T: Float>(perm_table: &PermutationTable, corner: Point4<isize>) -> T {
+            math::cast::<_, T>(perm_table.get4(corner)) * math::cast(1.0 / 255.0)
+        }
+
+        let floored = math::map4(point, T::floor);
+        let mut near_corner = math::map4(floored, math::cast);
+        let mut far_corner = math::add4(near_corner, math::one4());
+        let weight = math::map4(math::sub4(point, floored), interp::s_curve5);
+
+        if let Some(period) = self.period {
+            let period = math::cast4(period);
+            near_corner = math::mod4(near_corner, period);
+            far_corner = math::mod4(far_corner, period);
+        }
+
+        let (n, f) = (near_corner, far_corner);
+        let f0000: T = get(&self.perm_table, [n[0], n[1], n[2], n[3]]);
+        let f1000: T = get(&self.perm_table, [f[0], n[1], n[2], n[3]]);
+        let f0100: T = get(&self.perm_table, [n[0], f[1], n[2], n[3]]);
+        let f1100: T = get(&self.perm_table, [f[0], f[1], n[2], n[3]]);
+        let f0010: T = get(&self.perm_table, [n[0], n[1], f[2], n[3]]);
+        let f1010: T = get(&self.perm_table, [f[0], n[1], f[2], n[3]]);
+        let f0110: T = get(&self.perm_table, [n[0], f[1], f[2], n[3]]);
+        let f1110: T = get(&self.perm_table, [f[0], f[1], f[2], n[3]]);
+        let f0001: T = get(&self.perm_table, [n[0], n[1], n[2], f[3]]);
+        let f1001: T = get(&self.perm_table, [f[0], n[1], n[2], f[3]]);
+        let f0101: T = get(&self.perm_table, [n[0], f[1], n[2], f[3]]);
+        let f1101: T = get(&self.perm_table, [f[0], f[1], n[2], f[3]]);
+        let f0011: T = get(&self.perm_table, [n[0], n[1], f[2], f[3]]);
+        let f1011: T = get(&self.perm_table, [f[0], n[1], f[2], f[3]]);
+        let f0111: T = get(&self.perm_table, [n[0], f[1], f[2], f[3]]);
+        let f1111: T = get(&self.perm_table, [f[0], f[1], f[2], f[3]]);
+
+        let d000 = interp::linear(f0000, f1000, weight[0]);
+        let d010 = interp::linear(f0010, f1010, weight[0]);
+        let d100 = interp::linear(f0100, f1100, weight[0]);
+        let d110 = interp::linear(f0110, f1110, weight[0]);
+        let d001 = interp::linear(f0001, f1001, weight[0]);
+        let d011 = interp::linear(f0011, f1011, weight[0]);
+        let d101 = interp::linear(f0101, f1101, weight[0]);
+        let d111 = interp::linear(f0111, f1111, weight[0]);
+        let d00 = interp::linear(d000, d100, weight[1]);
+        let d10 = interp::linear(d010, d110, weight[1]);
+        let d01 = interp::linear(d001, d101, weight[1]);
+        let d11 = interp::linear(d011, d111, weight[1]);
+        let d0 = interp::linear(d00, d10, weight[2]);
+        let d1 = interp::linear(d01, d11, weight[2]);
+        let d = interp::linear(d0, d1, weight[3]);
+
+        d * math::cast(2) - math::cast(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+    use test_utils::sample_points2;
+    use NoiseModule;
+
+    #[test]
+    fn test_value_range() {
+        let value = Value::new(1);
+        for i in 0..256 {
+            let point = [i as f64 * 0.173 - 20.0, i as f64 * 0.311 + 3.0, i as f64 * -0.07];
+            let v: f64 = value.get(point);
+            assert!(v >= -1.0 && v <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_value_period() {
+        let value = Value::new(1).set_period([5, 3, 0, 0]);
+        for &point in sample_points2(32).iter() {
+            let shifted = [point[0] + 5.0, point[1] - 6.0];
+            let (a, b): (f64, f64) = (value.get(point), value.get(shifted));
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+}
@@ -14,16 +14,23 @@
 
 use num_traits::Float;
 use {NoiseModule, PermutationTable, math};
+use modules::{Reseedable, Seedable};
 use math::{Point2, Point3, Point4};
 
 /// Default noise seed for the Worley noise module.
 pub const DEFAULT_WORLEY_SEED: usize = 0;
 /// Default RangeFunction for the Worley noise module.
 pub const DEFAULT_WORLEY_RANGEFUNCTION: RangeFunction = RangeFunction::Euclidean;
+/// Default ReturnType for the Worley noise module.
+pub const DEFAULT_WORLEY_RETURN_TYPE: ReturnType = ReturnType::Value;
 /// Default frequency for the Worley noise module.
 pub const DEFAULT_WORLEY_FREQUENCY: f32 = 1.0;
 /// Default displacement for the Worley noise module.
 pub const DEFAULT_WORLEY_DISPLACEMENT: f32 = 1.0;
+/// Default distance curve for the Worley noise module.
+pub const DEFAULT_WORLEY_DISTANCE_CURVE: f32 = 1.0;
+/// Default jitter for the Worley noise module.
+pub const DEFAULT_WORLEY_JITTER: f32 = 1.0;
 
 /// Noise module that outputs 2/3/4-dimensional Worley noise.
 #[derive(Clone, Copy, Debug)]
@@ -41,6 +48,9 @@ pub struct Worley<T> {
     /// the output value.
     pub enable_range: bool,
 
+    /// Specifies which value the noise module outputs.
+    pub return_type: ReturnType,
+
     /// Frequency of the seed points.
     pub frequency: T,
 
@@ -51,6 +61,23 @@ pub struct Worley<T> {
     /// random values to assign to each cell. The range of random values is +/-
     /// the displacement value.
     pub displacement: T,
+
+    /// Exponent applied to the distance, normalized to [0,1], before it is
+    /// output. Only used when the return type is not `ReturnType::Value`.
+    ///
+    /// A curve of 1.0 outputs the distance unchanged. Larger values darken the
+    /// interiors of the cells and sharpen the gradient towards their edges,
+    /// while smaller values brighten the interiors.
+    pub distance_curve: T,
+
+    /// How far each seed point is offset from its lattice point, from 0.0 to
+    /// 1.0.
+    ///
+    /// Each lattice point has a seed point at a random offset of up to half a
+    /// cell, derived from the hashed lattice point and scaled by the jitter.
+    /// A jitter of 0.0 places the seed points on a regular grid, and 1.0 uses
+    /// the full random offset.
+    pub jitter: T,
 }
 
 impl<T> Worley<T>
@@ -62,8 +89,11 @@ impl<T> Worley<T>
             seed: DEFAULT_WORLEY_SEED,
             range_function: DEFAULT_WORLEY_RANGEFUNCTION,
             enable_range: false,
+            return_type: DEFAULT_WORLEY_RETURN_TYPE,
             frequency: math::cast(DEFAULT_WORLEY_FREQUENCY),
             displacement: math::cast(DEFAULT_WORLEY_DISPLACEMENT),
+            distance_curve: math::cast(DEFAULT_WORLEY_DISTANCE_CURVE),
+            jitter: math::cast(DEFAULT_WORLEY_JITTER),
         }
     }
 
@@ -87,6 +117,11 @@ impl<T> Worley<T>
         Worley { enable_range: enable_range, ..self }
     }
 
+    /// Sets which value the noise module outputs.
+    pub fn set_return_type(self, return_type: ReturnType) -> Worley<T> {
+        Worley { return_type: return_type, ..self }
+    }
+
     /// Sets the frequency of the seed points.
     pub fn set_frequency(self, frequency: T) -> Worley<T> {
         Worley { frequency: frequency, ..self }
@@ -95,6 +130,117 @@ impl<T> Worley<T>
     pub fn set_displacement(self, displacement: T) -> Worley<T> {
         Worley { displacement: displacement, ..self }
     }
+
+    /// Sets the exponent applied to the normalized distance before it is
+    /// output.
+    pub fn set_distance_curve(self, distance_curve: T) -> Worley<T> {
+        Worley { distance_curve: distance_curve, ..self }
+    }
+
+    /// Sets how far each seed point is offset from its lattice point, clamped
+    /// to the range 0.0 to 1.0.
+    ///
+    /// Lowering the jitter makes the cells more even in size and shape, down
+    /// to a regular grid of square cells at 0.0, which is useful for stylized
+    /// patterns. This is the parameter that some other cellular noise
+    /// libraries call displacement; the `displacement` of this module scales
+    /// the values assigned to the cells instead.
+    pub fn set_jitter(self, jitter: T) -> Worley<T> {
+        Worley { jitter: jitter.max(T::zero()).min(T::one()), ..self }
+    }
+
+    /// Returns the distances from the point to the nearest and second nearest
+    /// seed points, searching the lattice points around the point.
+    fn nearest2(&self, point: Point2<T>) -> (T, T) {
+        let point = math::mul2(point, self.frequency);
+        let center = math::map2(point, |a| math::cast::<_, i64>(a.round()));
+
+        let mut ranges = (T::max_value(), T::max_value());
+        for x in -1..2 {
+            for y in -1..2 {
+                let whole = [center[0] + x, center[1] + y];
+                let offset = math::mul2(get_vec2(self.perm_table.get2(whole)), self.jitter);
+                let seed_point = math::add2(offset, math::cast2::<_, T>(whole));
+                insert_range(&mut ranges,
+                             calculate_range(self.range_function, &point, &seed_point));
+            }
+        }
+        ranges
+    }
+
+    /// Returns the distances from the point to the nearest and second nearest
+    /// seed points, searching the lattice points around the point.
+    fn nearest3(&self, point: Point3<T>) -> (T, T) {
+        let point = math::mul3(point, self.frequency);
+        let center = math::map3(point, |a| math::cast::<_, i64>(a.round()));
+
+        let mut ranges = (T::max_value(), T::max_value());
+        for x in -1..2 {
+            for y in -1..2 {
+                for z in -1..2 {
+                    let whole = [center[0] + x, center[1] + y, center[2] + z];
+                    let offset = math::mul3(get_vec3(self.perm_table.get3(whole)), self.jitter);
+                    let seed_point = math::add3(offset, math::cast3::<_, T>(whole));
+                    insert_range(&mut ranges,
+                                 calculate_range(self.range_function, &point, &seed_point));
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Returns the distances from the point to the nearest and second nearest
+    /// seed points, searching the lattice points around the point.
+    fn nearest4(&self, point: Point4<T>) -> (T, T) {
+        let point = math::mul4(point, self.frequency);
+        let center = math::map4(point, |a| math::cast::<_, i64>(a.round()));
+
+        let mut ranges = (T::max_value(), T::max_value());
+        for x in -1..2 {
+            for y in -1..2 {
+                for z in -1..2 {
+                    for u in -1..2 {
+                        let whole = [center[0] + x, center[1] + y, center[2] + z, center[3] + u];
+                        let offset = math::mul4(get_vec4(self.perm_table.get4(whole)), self.jitter);
+                        let seed_point = math::add4(offset, math::cast4::<_, T>(whole));
+                        insert_range(&mut ranges,
+                                     calculate_range(self.range_function, &point, &seed_point));
+                    }
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Scales the distance selected by the return type into the [-1,1] range,
+    /// applying the distance curve.
+    fn distance_output(&self, ranges: (T, T)) -> T {
+        let (f1, f2) = ranges;
+        let distance = match self.return_type {
+            ReturnType::F1 => f1,
+            ReturnType::F2 => f2,
+            ReturnType::F2MinusF1 => f2 - f1,
+            ReturnType::Value => unreachable!(),
+        };
+        distance.min(T::one()).powf(self.distance_curve).mul_add(math::cast(2.0), -T::one())
+    }
+}
+
+impl<T: Float> Seedable for Worley<T> {
+    fn set_seed(self, seed: usize) -> Worley<T> {
+        Worley::set_seed(self, seed)
+    }
+
+    fn seed(&self) -> usize {
+        self.seed
+    }
+}
+
+impl<T> Reseedable for Worley<T> {
+    fn reseed(&mut self, seed: usize) {
+        self.perm_table = PermutationTable::new(seed as u32);
+        self.seed = seed;
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -122,6 +268,36 @@ pub enum RangeFunction {
     Quadratic,
 }
 
+/// Specifies which value the Worley noise module outputs.
+///
+/// All of the distance based return types are scaled so that the output lies
+/// in the [-1,1] range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReturnType {
+    /// The random value assigned to the nearest seed point's cell. If
+    /// `enable_range` is set, the distance to the seed point is added.
+    Value,
+
+    /// The distance to the nearest seed point.
+    F1,
+
+    /// The distance to the second nearest seed point.
+    F2,
+
+    /// The difference between the distances to the second nearest and the
+    /// nearest seed points. This is close to zero along the cell boundaries.
+    F2MinusF1,
+}
+
+fn insert_range<T: Float>(ranges: &mut (T, T), range: T) {
+    if range < ranges.0 {
+        ranges.1 = ranges.0;
+        ranges.0 = range;
+    } else if range < ranges.1 {
+        ranges.1 = range;
+    }
+}
+
 fn calculate_range<T: Float>(range_function: RangeFunction, p1: &[T], p2: &[T]) -> T {
     match range_function {
         RangeFunction::Euclidean => range_euclidean(p1, p2),
@@ -187,9 +363,17 @@ impl<T: Float> NoiseModule<Point2<T>> for Worley<T> {
     type Output = T;
 
     fn get(&self, point: Point2<T>) -> T {
+        if self.return_type != ReturnType::Value {
+            return self.distance_output(self.nearest2(point));
+        }
+
         #[inline(always)]
-        fn get_point<T: Float>(perm_table: &PermutationTable, whole: Point2<i64>) -> Point2<T> {
-            math::add2(get_vec2(perm_table.get2(whole)), math::cast2::<_, T>(whole))
+        fn get_point<T: Float>(perm_table: &PermutationTable,
+                               whole: Point2<i64>,
+                               jitter: T)
+                               -> Point2<T> {
+            math::add2(math::mul2(get_vec2(perm_table.get2(whole)), jitter),
+                       math::cast2::<_, T>(whole))
         }
 
         let half: T = math::cast(0.5);
@@ -207,7 +391,7 @@ impl<T: Float> NoiseModule<Point2<T>> for Worley<T> {
         let far = [whole[0] + (!x_half as i64), whole[1] + (!y_half as i64)];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(&self.perm_table, near, self.jitter);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (half - frac[0]) * (half - frac[0]); // x-distance squared to center line
@@ -216,7 +400,7 @@ impl<T: Float> NoiseModule<Point2<T>> for Worley<T> {
         macro_rules! test_point(
             [$x:expr, $y:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y]);
+                    let cur_point = get_point(&self.perm_table, [$x, $y], self.jitter);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -274,11 +458,17 @@ impl<T: Float> NoiseModule<Point3<T>> for Worley<T> {
     type Output = T;
 
     fn get(&self, point: Point3<T>) -> T {
+        if self.return_type != ReturnType::Value {
+            return self.distance_output(self.nearest3(point));
+        }
+
         #[inline(always)]
         fn get_point<T: Float>(perm_table: &PermutationTable,
-                               whole: math::Point3<i64>)
+                               whole: math::Point3<i64>,
+                               jitter: T)
                                -> Point3<T> {
-            math::add3(get_vec3(perm_table.get3(whole)), math::cast3::<_, T>(whole))
+            math::add3(math::mul3(get_vec3(perm_table.get3(whole)), jitter),
+                       math::cast3::<_, T>(whole))
         }
 
         let half: T = math::cast(0.5);
@@ -299,7 +489,7 @@ impl<T: Float> NoiseModule<Point3<T>> for Worley<T> {
             [whole[0] + (!x_half as i64), whole[1] + (!y_half as i64), whole[2] + (!z_half as i64)];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(&self.perm_table, near, self.jitter);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (half - frac[0]) * (half - frac[0]); // x-distance squared to center line
@@ -309,7 +499,7 @@ impl<T: Float> NoiseModule<Point3<T>> for Worley<T> {
         macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y, $z]);
+                    let cur_point = get_point(&self.perm_table, [$x, $y, $z], self.jitter);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -389,9 +579,17 @@ impl<T: Float> NoiseModule<Point4<T>> for Worley<T> {
     type Output = T;
 
     fn get(&self, point: Point4<T>) -> T {
+        if self.return_type != ReturnType::Value {
+            return self.distance_output(self.nearest4(point));
+        }
+
         #[inline(always)]
-        fn get_point<T: Float>(perm_table: &PermutationTable, whole: Point4<i64>) -> Point4<T> {
-            math::add4(get_vec4(perm_table.get4(whole)), math::cast4::<_, T>(whole))
+        fn get_point<T: Float>(perm_table: &PermutationTable,
+                               whole: Point4<i64>,
+                               jitter: T)
+                               -> Point4<T> {
+            math::add4(math::mul4(get_vec4(perm_table.get4(whole)), jitter),
+                       math::cast4::<_, T>(whole))
         }
 
         let half: T = math::cast(0.5);
@@ -417,7 +615,7 @@ impl<T: Float> NoiseModule<Point4<T>> for Worley<T> {
                    whole[3] + (!w_half as i64)];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(&self.perm_table, near, self.jitter);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (half - frac[0]) * (half - frac[0]); // x-distance squared to center line
@@ -428,7 +626,7 @@ impl<T: Float> NoiseModule<Point4<T>> for Worley<T> {
         macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr, $w:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y, $z, $w]);
+                    let cur_point = get_point(&self.perm_table, [$x, $y, $z, $w], self.jitter);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -541,3 +739,67 @@ pub fn get_vec4<T: Float>(index: usize) -> Point4<T> {
         _ => panic!("Attempt to access 4D gradient {} of 32", index % 32),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ReturnType, Worley};
+    use NoiseModule;
+
+    #[test]
+    fn test_return_types() {
+        let f1 = Worley::new().set_return_type(ReturnType::F1);
+        let f2 = f1.set_return_type(ReturnType::F2);
+        let difference = f1.set_return_type(ReturnType::F2MinusF1);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.23 + 1.0, i as f64 * -0.11];
+            let (a, b, c): (f64, f64, f64) = (f1.get(point), f2.get(point), difference.get(point));
+            assert!(a <= b);
+            for &value in [a, b, c].iter() {
+                assert!(value >= -1.0 && value <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_curve() {
+        let linear = Worley::new().set_return_type(ReturnType::F1);
+        let identity = linear.set_distance_curve(1.0);
+        let squared = linear.set_distance_curve(2.0);
+
+        let mut darker = false;
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.23 + 1.0];
+            let value: f64 = linear.get(point);
+            assert_eq!(identity.get(point), value);
+
+            let distance = (value + 1.0) / 2.0;
+            assert!((squared.get(point) - (distance * distance * 2.0 - 1.0)).abs() < 1e-12);
+            darker |= squared.get(point) < value;
+        }
+        assert!(darker);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let grid = Worley::new().set_return_type(ReturnType::F1).set_jitter(0.0);
+        let jittered = grid.set_jitter(0.5);
+        assert_eq!(Worley::<f64>::new().set_jitter(3.0).jitter, 1.0);
+
+        // Without jitter, the seed points are the lattice points, so the
+        // distance to the nearest one only depends on the fractional part of
+        // the point.
+        let mut differs = false;
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.23 + 1.0];
+            let value: f64 = grid.get(point);
+            let shifted = [point[0] + 3.0, point[1] - 7.0];
+            assert!((grid.get(shifted) - value).abs() < 1e-12);
+            differs |= (jittered.get(shifted) - jittered.get(point)).abs() > 1e-6;
+
+            let cell: f64 = grid.set_return_type(ReturnType::Value).get(point);
+            assert!(cell >= -1.0 && cell <= 1.0);
+        }
+        assert!(differs);
+    }
+}
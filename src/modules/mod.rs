@@ -17,11 +17,19 @@ pub use self::generators::*;
 pub use self::modifiers::*;
 pub use self::selectors::*;
 pub use self::cache::*;
+pub use self::ext::*;
+pub use self::reseed::*;
+pub use self::tiled_world::*;
 pub use self::transformers::*;
+pub use self::typed::*;
 
 mod combiners;
 mod generators;
 mod modifiers;
 mod selectors;
 mod cache;
+mod ext;
+mod reseed;
+mod tiled_world;
 mod transformers;
+mod typed;
@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that outputs the absolute value of the output value from the
 /// source module.
@@ -28,6 +29,12 @@ impl<Source> Abs<Source> {
     }
 }
 
+impl<Source: Reseedable> Reseedable for Abs<Source> {
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for Abs<Source>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -38,4 +45,17 @@ impl<Source, T, U> NoiseModule<T> for Abs<Source>
     fn get(&self, point: T) -> Self::Output {
         (self.source.get(point)).abs()
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+        match () {
+            _ if lower >= U::zero() => (lower, upper),
+            _ if upper <= U::zero() => (-upper, -lower),
+            _ => (U::zero(), upper.max(-lower)),
+        }
+    }
 }
@@ -0,0 +1,154 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+
+/// Number of samples along each axis of the region used to measure the
+/// percentiles of the source module.
+pub const AUTO_CONTRAST_RESOLUTION: usize = 64;
+
+/// Percentile of the source module output that is mapped to -1.0.
+pub const AUTO_CONTRAST_LOWER_PERCENTILE: f64 = 0.02;
+
+/// Percentile of the source module output that is mapped to 1.0.
+pub const AUTO_CONTRAST_UPPER_PERCENTILE: f64 = 0.98;
+
+/// Noise module that stretches the output value from the source module so that
+/// most of it spans the range -1.0 to 1.0.
+///
+/// On construction, the source module is sampled over a grid spanning the
+/// given region to find the 2nd and 98th percentiles of its output. Output
+/// values are then linearly remapped so that those percentiles map to -1.0 and
+/// 1.0, and outliers beyond them are clipped. Samples that are NaN or
+/// infinite are ignored, and if every sample is, the output is 0.
+pub struct AutoContrast<Source, T> {
+    /// Outputs a value.
+    source: Source,
+
+    /// Output value from the source module that is mapped to -1.0.
+    lower_bound: T,
+
+    /// Output value from the source module that is mapped to 1.0.
+    upper_bound: T,
+}
+
+impl<Source, T> AutoContrast<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    pub fn new(source: Source, region: (Point2<T>, Point2<T>)) -> AutoContrast<Source, T> {
+        let (lower, upper) = region;
+        let resolution = AUTO_CONTRAST_RESOLUTION;
+        let step = [(upper[0] - lower[0]) / math::cast(resolution - 1),
+                    (upper[1] - lower[1]) / math::cast(resolution - 1)];
+
+        let mut samples = Vec::with_capacity(resolution * resolution);
+        for y in 0..resolution {
+            for x in 0..resolution {
+                let point = [lower[0] + step[0] * math::cast(x),
+                             lower[1] + step[1] * math::cast(y)];
+                samples.push(source.get(point));
+            }
+        }
+        samples.retain(|value| value.is_finite());
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // With no usable samples, the empty range makes the output 0.
+        if samples.is_empty() {
+            samples.push(T::zero());
+        }
+
+        AutoContrast {
+            lower_bound: percentile(&samples, AUTO_CONTRAST_LOWER_PERCENTILE),
+            upper_bound: percentile(&samples, AUTO_CONTRAST_UPPER_PERCENTILE),
+            source: source,
+        }
+    }
+}
+
+/// Returns the value at the given fraction of the way through the sorted
+/// samples.
+fn percentile<T: Float>(sorted: &[T], fraction: f64) -> T {
+    let index = (fraction * (sorted.len() - 1) as f64).round() as usize;
+    sorted[index]
+}
+
+impl<Source, T, U> NoiseModule<T> for AutoContrast<Source, U>
+    where Source: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        let range = self.upper_bound - self.lower_bound;
+        if range <= U::zero() {
+            return U::zero();
+        }
+
+        let two: U = math::cast(2.0);
+        let value = (self.source.get(point) - self.lower_bound) / range * two - U::one();
+        value.max(-U::one()).min(U::one())
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoContrast, percentile};
+    use modules::{CellFn, Perlin};
+    use std::f64;
+    use NoiseModule;
+
+    #[test]
+    fn test_percentiles_stretched() {
+        let region = ([-8.0, -8.0], [8.0, 8.0]);
+        let contrast = AutoContrast::new(Perlin::new(0), region);
+
+        let mut samples = Vec::new();
+        for y in 0..50 {
+            for x in 0..50 {
+                samples.push(contrast.get([x as f64 * 0.32 - 8.0, y as f64 * 0.32 - 8.0]));
+            }
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert!((percentile(&samples, 0.02) + 1.0).abs() < 0.05);
+        assert!((percentile(&samples, 0.98) - 1.0).abs() < 0.05);
+        assert!(samples.iter().all(|v| v.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_non_finite_samples_ignored() {
+        let region = ([-8.0, -8.0], [8.0, 8.0]);
+
+        // The cells with negative x output NaN, and the others their x.
+        let cells = CellFn::new(|cell: [isize; 2]| {
+            if cell[0] < 0 { f64::NAN } else { cell[0] as f64 }
+        });
+        let contrast = AutoContrast::new(cells, region);
+        assert_eq!(contrast.get([0.5, 0.5]), -1.0);
+        assert_eq!(contrast.get([8.5, 0.5]), 1.0);
+
+        let nan = AutoContrast::new(CellFn::new(|_| f64::NAN), region);
+        assert_eq!(nan.get([0.5, 0.5]), 0.0);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Default amplitude of the dither, one quantization step of an 8-bit output
+/// spanning -1.0 to 1.0.
+pub const DEFAULT_DITHER_AMPLITUDE: f64 = 2.0 / 255.0;
+
+/// Default size of each dither cell in input units.
+pub const DEFAULT_DITHER_CELL_SIZE: f64 = 1.0;
+
+/// Width and height of the blue-noise tile.
+const TILE_SIZE: usize = 16;
+
+/// A 16 × 16 blue-noise tile, generated with the void-and-cluster method.
+/// Each entry is the rank of its cell, so every value from 0 to 255 appears
+/// exactly once, and cells with similar ranks are spread apart.
+const BLUE_NOISE: [u8; TILE_SIZE * TILE_SIZE] = [
+    120,  61, 134, 223,  84,  33, 168,  12, 113, 225,  63, 246, 185, 233,  88, 169,
+     23, 206, 181,  17, 109, 214,  58, 140, 201,  24, 161,  93,  34, 133,  14, 221,
+    144,  73, 250,  49, 158, 187,  81, 251, 100,  51, 142, 210, 172,  57, 191, 106,
+     42, 167, 101, 126, 220,   3, 121,  40, 170, 231,  82,   8, 114, 255,  80, 232,
+    212,  11, 195,  31,  72, 239, 152, 196,  16, 127, 188, 222,  45, 157,  26, 128,
+    154,  87, 235, 143, 179,  94,  54, 108, 237,  65,  29, 105, 139, 207, 184,  66,
+    248,  47, 115,  62, 209,  20, 164, 217,  79, 146, 178, 243,  69,  90,   1, 118,
+     30, 190, 173,   6, 131, 254,  41, 136,  10, 204,  43, 159,  22, 229, 162, 218,
+     77, 148,  99, 226,  74, 182, 117, 192,  86, 247, 119,  97, 197, 130,  53, 103,
+    242,  19, 198,  44, 155,  96,  59, 230,  28, 165,  60,   5, 240,  39, 175, 202,
+    137,  64, 122, 238,  25, 211,   0, 149, 104, 224, 135, 183, 151,  71, 112,   9,
+     91, 213, 166,  85, 186, 111, 249, 174,  48,  75, 208,  32,  89, 205, 236, 160,
+     37, 252,  18,  55, 138,  38,  78, 123, 194,  13, 107, 253, 124,  15,  56, 189,
+     76, 145, 110, 228, 203, 163, 219,  21, 241, 141, 171,  50, 156, 227, 102, 129,
+      2, 199, 176,  68,   7,  98,  52, 150,  92,  36, 215,  83, 200,  27, 177, 216,
+    244,  95,  35, 153, 245, 125, 193, 234,  70, 180, 132,   4, 116,  67, 147,  46,
+];
+
+/// Noise module that adds a small blue-noise offset to the output value from
+/// the source module.
+///
+/// When the output is quantized to a low bit depth, such as when writing an
+/// 8-bit image, smooth gradients show visible banding. Adding an offset of up
+/// to half a quantization step before quantizing breaks up the bands. The
+/// offsets come from a repeating blue-noise tile, which has no low-frequency
+/// structure and so is much less visible than white noise.
+///
+/// The tile is indexed by the input point divided by the cell size, so the
+/// cell size should match the spacing between output pixels. The offsets
+/// average to zero over each tile.
+pub struct BlueNoiseDither<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Peak-to-peak amplitude of the offsets. Default is 2/255, one step of an
+    /// 8-bit output spanning -1.0 to 1.0.
+    pub amplitude: T,
+
+    /// Size of each dither cell in input units. Default is 1.0.
+    pub cell_size: T,
+}
+
+impl<Source, T> BlueNoiseDither<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> BlueNoiseDither<Source, T> {
+        BlueNoiseDither {
+            source: source,
+            amplitude: math::cast(DEFAULT_DITHER_AMPLITUDE),
+            cell_size: math::cast(DEFAULT_DITHER_CELL_SIZE),
+        }
+    }
+
+    pub fn set_amplitude(self, amplitude: T) -> BlueNoiseDither<Source, T> {
+        BlueNoiseDither { amplitude: amplitude, ..self }
+    }
+
+    pub fn set_cell_size(self, cell_size: T) -> BlueNoiseDither<Source, T> {
+        BlueNoiseDither { cell_size: cell_size, ..self }
+    }
+
+    /// Returns the offset for the cell containing the given point, in the range
+    /// -amplitude/2 to amplitude/2.
+    fn offset(&self, point: Point2<T>) -> T {
+        let tile_size = TILE_SIZE as isize;
+        let x: isize = math::cast((point[0] / self.cell_size).floor());
+        let y: isize = math::cast((point[1] / self.cell_size).floor());
+        let index = (y & (tile_size - 1)) * tile_size + (x & (tile_size - 1));
+
+        let rank: T = math::cast(BLUE_NOISE[index as usize]);
+        let count: T = math::cast(TILE_SIZE * TILE_SIZE);
+        let half: T = math::cast(0.5);
+        ((rank + half) / count - half) * self.amplitude
+    }
+}
+
+impl<Source, T> Reseedable for BlueNoiseDither<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for BlueNoiseDither<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        self.source.get(point) + self.offset(point)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlueNoiseDither;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_dither_is_zero_mean() {
+        let source = Perlin::new(0);
+        let dither = BlueNoiseDither::new(source).set_amplitude(0.25);
+
+        let (mut plain, mut dithered) = (0.0, 0.0);
+        for y in 0..32 {
+            for x in 0..32 {
+                let point = [x as f64 + 0.5, y as f64 + 0.5];
+                plain += source.get(point);
+                dithered += dither.get(point);
+            }
+        }
+        assert!((plain - dithered).abs() / 1024.0 < 1e-12);
+    }
+}
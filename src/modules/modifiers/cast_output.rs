@@ -0,0 +1,89 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::Float;
+use math;
+use std::marker::PhantomData;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that converts the output value from the source module into
+/// a different float type.
+///
+/// This allows modules working at different precisions to be combined in the
+/// same graph, e.g. a high-precision `f64` base feeding an `f32` detail layer.
+pub struct CastOutput<Source, V> {
+    /// Outputs a value.
+    source: Source,
+
+    output: PhantomData<V>,
+}
+
+impl<Source, V> CastOutput<Source, V> {
+    pub fn new(source: Source) -> CastOutput<Source, V> {
+        CastOutput {
+            source: source,
+            output: PhantomData,
+        }
+    }
+}
+
+impl<Source, V> Reseedable for CastOutput<Source, V>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T, U, V> NoiseModule<T> for CastOutput<Source, V>
+    where Source: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+          V: Float,
+{
+    type Output = V;
+
+    fn get(&self, point: T) -> Self::Output {
+        math::cast(self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (V, V) {
+        let (lower, upper) = self.source.bounds();
+        (math::cast(lower), math::cast(upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use modules::Perlin;
+    use NoiseModule;
+    use super::CastOutput;
+
+    #[test]
+    fn test_cast_output_f64_to_f32() {
+        let perlin = Perlin::new(0);
+        let cast = CastOutput::<_, f32>::new(perlin);
+
+        for &point in [[0.5f64, 0.25], [12.3, -4.7], [-100.1, 37.9]].iter() {
+            let expected: f64 = perlin.get(point);
+            let actual: f32 = cast.get(point);
+            assert!((actual as f64 - expected).abs() < 1e-6);
+        }
+    }
+}
@@ -14,9 +14,13 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that clamps the output value from the source module to a
 /// range of values.
+///
+/// If the bounds are given in the wrong order to `set_bounds`, they are
+/// swapped, so the lower bound never exceeds the upper bound.
 pub struct Clamp<Source, T> {
     /// Outputs a value.
     source: Source,
@@ -46,6 +50,24 @@ impl<Source, T> Clamp<Source, T>
     pub fn set_upper_bound(self, upper_bound: T) -> Clamp<Source, T> {
         Clamp { upper_bound: upper_bound, ..self }
     }
+
+    /// Sets both bounds of the clamping range. If `lower` is greater than
+    /// `upper`, the two are swapped.
+    pub fn set_bounds(self, lower: T, upper: T) -> Clamp<Source, T> {
+        Clamp {
+            lower_bound: lower.min(upper),
+            upper_bound: lower.max(upper),
+            ..self
+        }
+    }
+}
+
+impl<Source, T> Reseedable for Clamp<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
 }
 
 impl<Source, T, U> NoiseModule<T> for Clamp<Source, U>
@@ -64,4 +86,39 @@ impl<Source, T, U> NoiseModule<T> for Clamp<Source, U>
             _ => value,
         }
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+        (lower.max(self.lower_bound).min(self.upper_bound),
+         upper.min(self.upper_bound).max(self.lower_bound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Clamp;
+    use modules::{Constant, Perlin};
+    use NoiseModule;
+
+    #[test]
+    fn test_set_bounds_swaps() {
+        let clamp = Clamp::new(Constant::new(5.0)).set_bounds(2.0, -2.0);
+        assert_eq!(clamp.get([0.0, 0.0]), 2.0);
+
+        let clamp = Clamp::new(Constant::new(-5.0)).set_bounds(2.0, -2.0);
+        assert_eq!(clamp.get([0.0, 0.0]), -2.0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let clamp = Clamp::new(Perlin::new(0)).set_bounds(-0.5, 2.0);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&clamp), (-0.5, 1.0));
+
+        let clamp = Clamp::new(Constant::new(5.0)).set_bounds(-0.5, 2.0);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&clamp), (2.0, 2.0));
+    }
 }
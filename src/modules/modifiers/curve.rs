@@ -15,6 +15,7 @@
 use num_traits::Float;
 use math::interp;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that maps the output value from the source module onto an
 /// arbitrary function curve.
@@ -76,6 +77,14 @@ impl<Source, T> Curve<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for Curve<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for Curve<Source, U>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -124,6 +133,10 @@ impl<Source, T, U> NoiseModule<T> for Curve<Source, U>
                       self.control_points[index3].output,
                       alpha)
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
 }
 
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
@@ -0,0 +1,159 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that approximates a single pass of thermal erosion over the
+/// output value from a 2-dimensional source module.
+///
+/// Thermal erosion moves material from steep slopes down to their neighbors,
+/// until no slope is steeper than the _talus_ slope. Rather than simulating
+/// this over a grid, this module estimates the slope of the source module at
+/// the input value by finite differences, and lowers the output value by the
+/// amount that would be moved to the downhill neighbor. Slopes gentler than
+/// the talus slope are left unchanged.
+///
+/// This is not a full simulation, but it gives steep terrain the
+/// characteristic smoothed slopes of eroded terrain.
+pub struct ErosionPass<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Steepest slope, as rise over run, that is left unchanged. The default
+    /// talus slope is 1.0, which is an angle of 45 degrees.
+    pub talus: T,
+
+    /// Distance to the neighbors that material is moved to. This is also the
+    /// step used to estimate the slope. The default distance is 0.01.
+    pub sample_distance: T,
+
+    /// Fraction of the excess material that is moved, between 0.0 and 1.0.
+    /// The default strength is 1.0.
+    pub strength: T,
+}
+
+impl<Source, T> ErosionPass<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> ErosionPass<Source, T> {
+        ErosionPass {
+            source: source,
+            talus: T::one(),
+            sample_distance: math::cast(0.01),
+            strength: T::one(),
+        }
+    }
+
+    /// Sets the talus slope, as rise over run.
+    pub fn set_talus(self, talus: T) -> ErosionPass<Source, T> {
+        ErosionPass { talus: talus, ..self }
+    }
+
+    /// Sets the talus slope from an angle in degrees.
+    pub fn set_talus_angle(self, degrees: T) -> ErosionPass<Source, T> {
+        ErosionPass { talus: degrees.to_radians().tan(), ..self }
+    }
+
+    /// Sets the distance to the neighbors that material is moved to.
+    pub fn set_sample_distance(self, sample_distance: T) -> ErosionPass<Source, T> {
+        ErosionPass { sample_distance: sample_distance, ..self }
+    }
+
+    /// Sets the fraction of the excess material that is moved.
+    pub fn set_strength(self, strength: T) -> ErosionPass<Source, T> {
+        ErosionPass { strength: strength, ..self }
+    }
+}
+
+impl<Source, T> Reseedable for ErosionPass<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for ErosionPass<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let height = self.source.get(point);
+
+        // Estimate the gradient by central differences.
+        let d = self.sample_distance;
+        let dx = self.source.get([point[0] + d, point[1]]) -
+                 self.source.get([point[0] - d, point[1]]);
+        let dy = self.source.get([point[0], point[1] + d]) -
+                 self.source.get([point[0], point[1] - d]);
+        let slope = dx.hypot(dy) / (d + d);
+
+        if slope <= self.talus {
+            return height;
+        }
+
+        // The downhill neighbor is lower by slope * d, but should only be lower
+        // by talus * d. Moving half of the excess evens out the difference.
+        let excess = (slope - self.talus) * d;
+        height - excess * self.strength * math::cast(0.5)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        // The source module is sampled at the point and at four neighbors.
+        self.source.complexity_estimate() * 5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErosionPass;
+    use NoiseModule;
+
+    struct Ramp {
+        slope: f64,
+    }
+
+    impl NoiseModule<[f64; 2]> for Ramp {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0] * self.slope
+        }
+    }
+
+    #[test]
+    fn test_steep_slopes_are_reduced() {
+        let erosion = ErosionPass::new(Ramp { slope: 3.0 }).set_talus(1.0);
+        for i in 0..8 {
+            let point = [i as f64 * 0.3, 0.5];
+            assert!(erosion.get(point) < erosion.source.get(point));
+        }
+    }
+
+    #[test]
+    fn test_gentle_slopes_are_unchanged() {
+        let erosion = ErosionPass::new(Ramp { slope: 0.5 }).set_talus(1.0);
+        for i in 0..8 {
+            let point = [i as f64 * 0.3, 0.5];
+            assert_eq!(erosion.get(point), erosion.source.get(point));
+        }
+    }
+}
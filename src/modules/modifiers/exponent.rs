@@ -13,16 +13,17 @@
 // limitations under the License.
 
 use num_traits::Float;
-use math;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that maps the output value from the source module onto an
 /// exponential curve.
 ///
-/// Because most noise modules will output values that range from -1.0 to 1.0,
-/// this noise module first normalizes the output value (the range becomes 0.0
-/// to 1.0), maps that value onto an exponential curve, then rescales that
-/// value back to the original range.
+/// The magnitude of the output value from the source module is raised to the
+/// power of the exponent, and its sign is preserved, i.e. _v_ becomes
+/// sign(_v_) * |_v_|<sup>exponent</sup>. Values in the range -1.0 to 1.0 stay
+/// in that range. Exponents greater than 1.0 push values towards zero, and
+/// exponents less than 1.0 push them towards the extremes.
 pub struct Exponent<Source, T> {
     /// Outputs a value.
     source: Source,
@@ -47,6 +48,14 @@ impl<Source, T> Exponent<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for Exponent<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for Exponent<Source, U>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -55,10 +64,61 @@ impl<Source, T, U> NoiseModule<T> for Exponent<Source, U>
     type Output = U;
 
     fn get(&self, point: T) -> Self::Output {
-        let mut value = self.source.get(point);
-        value = (value + U::one()) / math::cast(2.0);
-        value = value.abs();
-        value = value.powf(self.exponent);
-        value.mul_add(math::cast(2.0), -U::one())
+        let value = self.source.get(point);
+
+        // Raise the magnitude rather than the value itself, as powf() is NaN
+        // for negative values and fractional exponents.
+        let magnitude = value.abs().powf(self.exponent);
+        if value < U::zero() { -magnitude } else { magnitude }
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+
+        // Negative exponents diverge at zero. Otherwise the signed power is
+        // monotonic on each side of zero, so its extremes are at the ends.
+        if self.exponent < U::zero() && lower <= U::zero() && upper >= U::zero() {
+            return (U::neg_infinity(), U::infinity());
+        }
+        let power = |value: U| {
+            let magnitude = value.abs().powf(self.exponent);
+            if value < U::zero() { -magnitude } else { magnitude }
+        };
+        let (lower, upper) = (power(lower), power(upper));
+        (lower.min(upper), lower.max(upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Exponent;
+    use modules::{Constant, Perlin, ScaleBias};
+    use NoiseModule;
+
+    #[test]
+    fn test_preserves_sign() {
+        for &(value, expected) in [(-0.25, -0.5), (0.25, 0.5), (-1.0, -1.0), (0.0, 0.0)].iter() {
+            let exponent = Exponent::new(Constant::new(value)).set_exponent(0.5);
+            let result: f64 = exponent.get([0.0, 0.0]);
+            assert!((result - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let exponent = Exponent::new(Perlin::new(0)).set_exponent(3.0);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&exponent), (-1.0, 1.0));
+
+        let positive = ScaleBias::new(Perlin::new(0)).set_scale(0.25).set_bias(0.75);
+        let exponent = Exponent::new(positive).set_exponent(2.0);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&exponent), (0.25, 1.0));
+
+        let exponent = Exponent::new(Perlin::new(0)).set_exponent(-1.0);
+        let (lower, upper) = NoiseModule::<[f64; 2]>::bounds(&exponent);
+        assert!(lower.is_infinite() && upper.is_infinite());
     }
 }
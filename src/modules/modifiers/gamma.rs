@@ -0,0 +1,115 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::Float;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that applies a gamma curve to the output value from the
+/// source module, treating it as a brightness in a grayscale heightmap.
+///
+/// The output value is remapped from the range -1.0 to 1.0 onto 0.0 to 1.0,
+/// raised to the power of the gamma, and mapped back. Unlike `Exponent`,
+/// which is symmetric around zero, this bends the curve between the two ends
+/// of the range: gammas greater than 1.0 darken and flatten the lowlands,
+/// while gammas less than 1.0 brighten them. The ends of the range are fixed
+/// points. Output values outside the range are clamped to it first.
+pub struct Gamma<Source, T> {
+    /// Outputs a value.
+    source: Source,
+
+    /// Gamma to apply to the remapped output value. Default is 1.0.
+    gamma: T,
+}
+
+impl<Source, T> Gamma<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> Gamma<Source, T> {
+        Gamma {
+            source: source,
+            gamma: T::one(),
+        }
+    }
+
+    pub fn set_gamma(self, gamma: T) -> Gamma<Source, T> {
+        Gamma { gamma: gamma, ..self }
+    }
+}
+
+impl<Source, T> Reseedable for Gamma<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T, U> NoiseModule<T> for Gamma<Source, U>
+    where Source: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        let one = U::one();
+        let two = one + one;
+
+        // Clamp before raising, as powf() is NaN for negative values and
+        // fractional gammas.
+        let value = self.source.get(point).max(-one).min(one);
+        let brightness = (value + one) / two;
+        brightness.powf(self.gamma) * two - one
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let one = U::one();
+        let two = one + one;
+
+        // The gamma curve is monotonic, so its extremes are at the ends.
+        let (lower, upper) = self.source.bounds();
+        let curve = |value: U| {
+            let brightness = (value.max(-one).min(one) + one) / two;
+            brightness.powf(self.gamma) * two - one
+        };
+        let (lower, upper) = (curve(lower), curve(upper));
+        (lower.min(upper), lower.max(upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gamma;
+    use modules::Constant;
+    use NoiseModule;
+
+    #[test]
+    fn test_gamma() {
+        let cases = [(-1.0, -1.0), (1.0, 1.0), (0.0, -0.5), (0.5, 0.125), (-3.0, -1.0)];
+        for &(value, expected) in cases.iter() {
+            let gamma = Gamma::new(Constant::new(value)).set_gamma(2.0);
+            let result: f64 = gamma.get([0.0, 0.0]);
+            assert!((result - expected).abs() < 1e-12, "{} != {}", result, expected);
+        }
+
+        // The default gamma leaves values in range unchanged.
+        let result: f64 = Gamma::new(Constant::new(0.3)).get([0.0, 0.0]);
+        assert!((result - 0.3).abs() < 1e-12);
+    }
+}
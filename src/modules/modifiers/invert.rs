@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that inverts the output value from the source module.
 pub struct Invert<Source> {
@@ -27,6 +28,12 @@ impl<Source> Invert<Source> {
     }
 }
 
+impl<Source: Reseedable> Reseedable for Invert<Source> {
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for Invert<Source>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -37,4 +44,29 @@ impl<Source, T, U> NoiseModule<T> for Invert<Source>
     fn get(&self, point: T) -> Self::Output {
         -self.source.get(point)
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+        (-upper, -lower)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Invert;
+    use modules::Constant;
+    use NoiseModule;
+
+    #[test]
+    fn test_invert_negates_source() {
+        let invert = Invert::new(Constant::new(0.25));
+        assert_eq!(invert.get([1.0, 2.0]), -0.25);
+
+        let twice = Invert::new(Invert::new(Constant::new(-0.75)));
+        assert_eq!(twice.get([0.0, 0.0, 0.0]), -0.75);
+    }
 }
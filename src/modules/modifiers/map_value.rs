@@ -0,0 +1,87 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that maps the output value from the source module through an
+/// arbitrary function.
+///
+/// This is an escape hatch for one-off remappings that no other module
+/// provides, without defining a new noise module:
+///
+/// ```rust
+/// use noise::NoiseModule;
+/// use noise::modules::{MapValue, Perlin};
+///
+/// let waves = MapValue::new(Perlin::new(0), |value: f64| (value * 8.0).sin());
+/// let value = waves.get([0.3, 0.7]);
+/// assert!(value >= -1.0 && value <= 1.0);
+/// ```
+pub struct MapValue<Source, F> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Function applied to the output value from the source module.
+    pub function: F,
+}
+
+impl<Source, F> MapValue<Source, F> {
+    pub fn new(source: Source, function: F) -> MapValue<Source, F> {
+        MapValue {
+            source: source,
+            function: function,
+        }
+    }
+}
+
+impl<Source, F> Reseedable for MapValue<Source, F>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, F, T, U> NoiseModule<T> for MapValue<Source, F>
+    where Source: NoiseModule<T, Output = U>,
+          F: Fn(U) -> U,
+          T: Copy,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        (self.function)(self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MapValue;
+    use modules::{Abs, Perlin};
+    use test_utils::assert_modules_equal;
+
+    #[test]
+    fn test_matches_abs() {
+        assert_modules_equal(&MapValue::new(Perlin::new(0), f64::abs),
+                             &Abs::new(Perlin::new(0)),
+                             ([-3.0, -3.0], [3.0, 3.0]),
+                             0.0);
+    }
+}
@@ -13,17 +13,33 @@
 // limitations under the License.
 
 pub use self::abs::*;
+pub use self::auto_contrast::*;
+pub use self::blue_noise_dither::*;
+pub use self::cast_output::*;
 pub use self::clamp::*;
 pub use self::curve::*;
+pub use self::erosion_pass::*;
 pub use self::exponent::*;
+pub use self::gamma::*;
 pub use self::invert::*;
+pub use self::map_value::*;
+pub use self::normals::*;
+pub use self::ridge::*;
 pub use self::scale_bias::*;
 pub use self::terrace::*;
 
 mod abs;
+mod auto_contrast;
+mod blue_noise_dither;
+mod cast_output;
 mod clamp;
 mod curve;
+mod erosion_pass;
 mod exponent;
+mod gamma;
 mod invert;
+mod map_value;
+mod normals;
+mod ridge;
 mod scale_bias;
 mod terrace;
@@ -0,0 +1,134 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Vector3};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Default finite difference step for the Normals noise module.
+pub const DEFAULT_NORMALS_EPSILON: f32 = 0.001;
+
+/// Noise module that outputs the surface normal of the heightfield formed by
+/// the output value of the source module.
+///
+/// The heightfield is the surface z = source(x, y), and the normal is
+/// estimated with central differences, sampling the source a small step to
+/// either side of the point along each axis. Unlike analytic derivatives, this
+/// works with any source, including the fractal generators. The output is a
+/// unit vector whose z component is always positive.
+pub struct Normals<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Distance from the point at which the source is sampled. The default
+    /// epsilon is 0.001.
+    ///
+    /// This should be small relative to the smallest feature in the source,
+    /// which for the generators is about one unit divided by the frequency of
+    /// the highest octave. Too large a step smooths out fine detail, while too
+    /// small a step amplifies floating point error.
+    pub epsilon: T,
+}
+
+impl<Source, T> Normals<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> Normals<Source, T> {
+        Normals {
+            source: source,
+            epsilon: math::cast(DEFAULT_NORMALS_EPSILON),
+        }
+    }
+
+    pub fn set_epsilon(self, epsilon: T) -> Normals<Source, T> {
+        Normals { epsilon: epsilon, ..self }
+    }
+}
+
+impl<Source, T> Reseedable for Normals<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for Normals<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = Vector3<T>;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let e = self.epsilon;
+        let dx = self.source.get([point[0] + e, point[1]]) -
+                 self.source.get([point[0] - e, point[1]]);
+        let dy = self.source.get([point[0], point[1] + e]) -
+                 self.source.get([point[0], point[1] - e]);
+
+        // The cross product of the tangents [2e, 0, dx] and [0, 2e, dy],
+        // divided by 2e.
+        let normal = [-dx, -dy, e + e];
+        math::mul3(normal, math::dot3(normal, normal).sqrt().recip())
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Normals;
+    use modules::{CellFn, Perlin};
+    use test_utils::sample_points2;
+    use NoiseModule;
+
+    #[test]
+    fn test_normals_of_plane() {
+        struct Slope;
+
+        impl NoiseModule<[f64; 2]> for Slope {
+            type Output = f64;
+
+            fn get(&self, point: [f64; 2]) -> f64 {
+                point[0] * 0.5 - point[1]
+            }
+        }
+
+        let normals = Normals::new(Slope).set_epsilon(0.1);
+        let normal = normals.get([1.3, -2.4]);
+        let length = (0.25f64 + 1.0 + 1.0).sqrt();
+        let expected = [-0.5 / length, 1.0 / length, 1.0 / length];
+        for i in 0..3 {
+            assert!((normal[i] - expected[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_normals_are_unit_vectors() {
+        let normals = Normals::new(Perlin::new(0));
+        let flat = Normals::new(CellFn::new(|_| 0.5));
+        for &point in sample_points2(32).iter() {
+            let normal = normals.get(point);
+            let length_squared = normal.iter().fold(0.0, |sum, x| sum + x * x);
+            assert!((length_squared - 1.0).abs() < 1e-9);
+            assert!(normal[2] > 0.0);
+        }
+        assert_eq!(flat.get([0.3, 0.3]), [0.0, 0.0, 1.0]);
+    }
+}
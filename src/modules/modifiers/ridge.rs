@@ -0,0 +1,128 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::Float;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that folds the output value from the source module into
+/// sharp ridges, using the same transform as each octave of `RidgedMulti`.
+///
+/// The output value _v_ becomes offset - |_v_|, optionally squared. The
+/// zero crossings of the source module become ridges at the top of the
+/// range, and with the default offset of 1.0, values in the range -1.0 to
+/// 1.0 map onto 0.0 to 1.0. Squaring sharpens the ridges and flattens the
+/// valleys between them. Follow this module with a `ScaleBias` to map the
+/// output back onto -1.0 to 1.0.
+pub struct Ridge<Source, T> {
+    /// Outputs a value.
+    source: Source,
+
+    /// Value the absolute output value is subtracted from. Default is 1.0.
+    offset: T,
+
+    /// Whether the ridged value is squared. Default is false.
+    squared: bool,
+}
+
+impl<Source, T> Ridge<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> Ridge<Source, T> {
+        Ridge {
+            source: source,
+            offset: T::one(),
+            squared: false,
+        }
+    }
+
+    pub fn set_offset(self, offset: T) -> Ridge<Source, T> {
+        Ridge { offset: offset, ..self }
+    }
+
+    pub fn set_squared(self, squared: bool) -> Ridge<Source, T> {
+        Ridge { squared: squared, ..self }
+    }
+}
+
+impl<Source, T> Reseedable for Ridge<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T, U> NoiseModule<T> for Ridge<Source, U>
+    where Source: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        let value = self.offset - self.source.get(point).abs();
+        if self.squared { value * value } else { value }
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+        let (lower_abs, upper_abs) = match () {
+            _ if lower >= U::zero() => (lower, upper),
+            _ if upper <= U::zero() => (-upper, -lower),
+            _ => (U::zero(), upper.max(-lower)),
+        };
+
+        let (lower, upper) = (self.offset - upper_abs, self.offset - lower_abs);
+        match () {
+            _ if !self.squared => (lower, upper),
+            _ if lower >= U::zero() => (lower * lower, upper * upper),
+            _ if upper <= U::zero() => (upper * upper, lower * lower),
+            _ => (U::zero(), (lower * lower).max(upper * upper)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ridge;
+    use modules::{Constant, Perlin};
+    use NoiseModule;
+
+    #[test]
+    fn test_ridge() {
+        for &(value, expected) in [(-0.25, 0.75), (0.25, 0.75), (1.0, 0.0), (0.0, 1.0)].iter() {
+            let ridge = Ridge::new(Constant::new(value));
+            let result: f64 = ridge.get([0.0, 0.0]);
+            assert!((result - expected).abs() < 1e-12);
+
+            let squared = Ridge::new(Constant::new(value)).set_offset(0.5).set_squared(true);
+            let result: f64 = squared.get([0.0, 0.0]);
+            assert!((result - (0.5 - value.abs()).powi(2)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let ridge = Ridge::new(Perlin::new(0));
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&ridge), (0.0, 1.0));
+
+        let squared = Ridge::new(Perlin::new(0)).set_offset(0.5).set_squared(true);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&squared), (0.0, 0.25));
+    }
+}
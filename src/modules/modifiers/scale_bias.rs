@@ -14,6 +14,7 @@
 
 use num_traits::Float;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that applies a scaling factor and a bias to the output value
 /// from the source module.
@@ -29,7 +30,7 @@ pub struct ScaleBias<Source, T> {
     scale: T,
 
     /// Bias to apply to the scaled output value from the source module.
-    /// The default value is 1.0.
+    /// The default value is 0.0.
     bias: T,
 }
 
@@ -40,7 +41,7 @@ impl<Source, T> ScaleBias<Source, T>
         ScaleBias {
             source: source,
             scale: T::one(),
-            bias: T::one(),
+            bias: T::zero(),
         }
     }
 
@@ -53,6 +54,14 @@ impl<Source, T> ScaleBias<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for ScaleBias<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for ScaleBias<Source, U>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -63,4 +72,39 @@ impl<Source, T, U> NoiseModule<T> for ScaleBias<Source, U>
     fn get(&self, point: T) -> Self::Output {
         (self.source.get(point)).mul_add(self.scale, self.bias)
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower, upper) = self.source.bounds();
+        let lower = lower.mul_add(self.scale, self.bias);
+        let upper = upper.mul_add(self.scale, self.bias);
+        (lower.min(upper), lower.max(upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScaleBias;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_default_is_identity() {
+        let perlin = Perlin::new(0);
+        let scale_bias = ScaleBias::new(perlin);
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * -0.19];
+            let expected: f64 = perlin.get(point);
+            assert_eq!(scale_bias.get(point), expected);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let scale_bias = ScaleBias::new(Perlin::new(0)).set_scale(-0.5).set_bias(0.5);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&scale_bias), (0.0, 1.0));
+    }
 }
@@ -15,19 +15,20 @@
 use num_traits::Float;
 use math::interp;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise module that maps the output value from the source module onto a
 /// terrace-forming curve.
 ///
 /// This noise module maps the output value from the source module onto a
-/// terrace-forming curve. The start of the curve has a slode of zero; it's
+/// terrace-forming curve. The start of the curve has a slope of zero; its
 /// slope then smoothly increases. This curve also contains _control points_
 /// which resets the slope to zero at that point, producing a "terracing"
 /// effect.
 ///
 /// To add control points to the curve, use the add_control_point method.
 ///
-/// An application must add a minumum of two control points to the curve. If
+/// An application must add a minimum of two control points to the curve. If
 /// there are less than two control points, the get() method panics. The
 /// control points can have any value, although no two control points can
 /// have the same value. There is no limit to the number of control points
@@ -94,6 +95,14 @@ impl<Source, T> Terrace<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for Terrace<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T, U> NoiseModule<T> for Terrace<Source, U>
     where Source: NoiseModule<T, Output = U>,
           T: Copy,
@@ -103,7 +112,8 @@ impl<Source, T, U> NoiseModule<T> for Terrace<Source, U>
 
     fn get(&self, point: T) -> Self::Output {
         // confirm that there's at least 2 control points in the vector.
-        assert!(self.control_points.len() >= 2);
+        assert!(self.control_points.len() >= 2,
+                "Terrace requires at least two control points");
 
         // get output value from the source module
         let source_value = self.source.get(point);
@@ -128,7 +138,7 @@ impl<Source, T, U> NoiseModule<T> for Terrace<Source, U>
             return self.control_points[index1];
         }
 
-        // Compute the alpha value used for cubic interpolation
+        // Compute the alpha value used for interpolation
         let mut input0 = self.control_points[index0];
         let mut input1 = self.control_points[index1];
         let mut alpha = (source_value - input0) / (input1 - input0);
@@ -142,9 +152,13 @@ impl<Source, T, U> NoiseModule<T> for Terrace<Source, U>
         // Squaring the alpha produces the terrace effect.
         alpha = alpha * alpha;
 
-        // Now perform the cubic interpolation and return.
+        // Now perform the interpolation and return.
         interp::linear(input0, input1, alpha)
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
 }
 
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
@@ -154,3 +168,51 @@ fn clamp_index(index: isize, min: usize, max: usize) -> usize {
         _ => index as usize,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Terrace;
+    use NoiseModule;
+
+    struct Identity;
+
+    impl NoiseModule<f64> for Identity {
+        type Output = f64;
+
+        fn get(&self, point: f64) -> f64 {
+            point
+        }
+    }
+
+    #[test]
+    fn test_terraces() {
+        let terrace = Terrace::new(Identity)
+            .add_control_point(1.0)
+            .add_control_point(-1.0)
+            .add_control_point(0.0);
+
+        // Control points map onto themselves, and values outside are clamped.
+        for &x in [-1.0, 0.0, 1.0].iter() {
+            assert_eq!(terrace.get(x), x);
+        }
+        assert_eq!(terrace.get(-2.0), -1.0);
+        assert_eq!(terrace.get(2.0), 1.0);
+
+        // The curve is flat just after each control point, and steep just
+        // before the next one.
+        assert!(terrace.get(0.1) - terrace.get(0.0) < 0.1);
+        assert!(terrace.get(1.0) - terrace.get(0.9) > 0.1);
+
+        // Inverting the terraces flips which end of each step is flat.
+        let inverted = terrace.invert_terraces(true);
+        assert!(inverted.get(0.1) - inverted.get(0.0) > 0.1);
+        assert!(inverted.get(1.0) - inverted.get(0.9) < 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_requires_two_control_points() {
+        let terrace = Terrace::new(Identity).add_control_point(0.0);
+        terrace.get(0.5);
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math;
+
+/// Trait for noise modules whose seed can be changed in place.
+///
+/// This is implemented by the generators, and by the modules that wrap or
+/// combine other modules, which reseed their sources. A whole graph of modules
+/// can then be reseeded through its root, see `seed_graph`. Generators without
+/// a seed ignore it.
+pub trait Reseedable {
+    /// Replaces the seed of the module.
+    fn reseed(&mut self, seed: usize);
+}
+
+impl<'a, M: Reseedable + ?Sized> Reseedable for &'a mut M {
+    fn reseed(&mut self, seed: usize) {
+        (**self).reseed(seed)
+    }
+}
+
+impl<M: Reseedable + ?Sized> Reseedable for Box<M> {
+    fn reseed(&mut self, seed: usize) {
+        (**self).reseed(seed)
+    }
+}
+
+/// Returns the seed that a module reseeded with `seed` passes on to its source
+/// at `index`, for modules with more than one source. Modules with a single
+/// source pass their seed on unchanged.
+///
+/// The seed is derived by hashing, so the sources of a module are seeded
+/// independently of each other.
+pub fn source_seed(seed: usize, index: usize) -> usize {
+    math::splitmix64(seed as u64 ^ math::splitmix64(index as u64)) as usize
+}
+
+/// Trait for noise modules that are built with a seed.
+///
+/// This is implemented by the generators and the fractal noise modules, so
+/// generic code can seed any of them. The fractal noise modules also use it to
+/// seed the source of each octave, so any generator implementing it, along
+/// with `Periodic`, `Default` and `Clone`, can be used as the source of a
+/// fractal.
+pub trait Seedable {
+    /// Returns a copy of the module with the given seed. All other parameters
+    /// are kept.
+    fn set_seed(self, seed: usize) -> Self;
+
+    /// Returns the seed of the module.
+    fn seed(&self) -> usize;
+}
+
+/// Reseeds every generator in the graph of modules rooted at `graph` from a
+/// single master seed.
+///
+/// Each generator is given a distinct seed, derived by hashing the master
+/// seed together with the path from the root to the generator, that is, which
+/// source of each module leads to it. The same master seed always produces the
+/// same seeds for the same graph, while neighbouring master seeds produce
+/// unrelated ones.
+pub fn seed_graph<M: Reseedable + ?Sized>(graph: &mut M, master_seed: usize) {
+    graph.reseed(math::splitmix64(master_seed as u64) as usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reseedable, Seedable, seed_graph};
+    use modules::{Abs, Add, Cache, Fbm, Multiply, Perlin, Turbulence, Value, Worley};
+    use NoiseModule;
+
+    type Graph = Add<Perlin, Multiply<Abs<Fbm<f64>>, Cache<Worley<f64>, f64>>>;
+
+    fn build(master_seed: usize) -> Graph {
+        let mut graph = Add::new(Perlin::new(0),
+                                 Multiply::new(Abs::new(Fbm::new()), Cache::new(Worley::new())));
+        seed_graph(&mut graph, master_seed);
+        graph
+    }
+
+    fn sample(graph: &Graph) -> Vec<f64> {
+        (0..32)
+            .map(|i| graph.get([i as f64 * 0.37 + 0.1, i as f64 * 0.19 + 0.3, 0.7]))
+            .collect()
+    }
+
+    #[test]
+    fn test_seed_graph_reproducible() {
+        assert_eq!(sample(&build(42)), sample(&build(42)));
+    }
+
+    #[test]
+    fn test_seed_graph_decorrelated() {
+        let a = sample(&build(42));
+        let b = sample(&build(43));
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!(x != y);
+        }
+    }
+
+    #[test]
+    fn test_seed_graph_sources() {
+        // Identical sources are given distinct seeds.
+        let mut graph = Add::new(Perlin::new(0), Perlin::new(0));
+        seed_graph(&mut graph, 42);
+        assert!(graph.source1.seed() != graph.source2.seed());
+
+        // Modules are reseeded the same through a box or a reference.
+        let mut boxed: Box<dyn Reseedable> = Box::new(Add::new(Perlin::new(0), Perlin::new(0)));
+        seed_graph(&mut boxed, 42);
+        let mut reseeded = Add::new(Perlin::new(0), Perlin::new(0));
+        seed_graph(&mut &mut reseeded, 42);
+        assert_eq!(reseeded.source1.seed(), graph.source1.seed());
+        assert_eq!(reseeded.source2.seed(), graph.source2.seed());
+    }
+
+    #[test]
+    fn test_seed_graph_explicit_seeds() {
+        let explicit = Fbm::<f64>::new().set_seeds(&[7, 8, 9, 10, 11, 12]);
+        let mut a = explicit.clone();
+        let mut b = explicit.clone();
+        seed_graph(&mut a, 98);
+        seed_graph(&mut b, 99);
+
+        // Reseeding replaces the explicit seeds too, so every octave changes,
+        // while fractals with different explicit seeds stay distinct.
+        let mut other = Fbm::<f64>::new().set_seeds(&[1, 2, 3, 4, 5, 6]);
+        seed_graph(&mut other, 99);
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 + 0.1, i as f64 * 0.19 + 0.3, 0.7];
+            assert!(a.get(point) != explicit.get(point));
+            assert!(a.get(point) != b.get(point));
+            assert!(b.get(point) != other.get(point));
+        }
+    }
+
+    #[test]
+    fn test_reseed_large_seed() {
+        // Octaves and displacement modules seeded after the given seed wrap
+        // rather than overflow.
+        let mut fbm = Fbm::<f64>::new();
+        fbm.reseed(usize::max_value());
+        let mut turbulence = Turbulence::<_, f64>::new(Perlin::new(0)).set_seed(usize::max_value());
+        turbulence.reseed(usize::max_value());
+        assert_eq!(turbulence.seed(), usize::max_value());
+        fbm.get([0.3, 0.7, -1.1]);
+    }
+
+    #[test]
+    fn test_seedable() {
+        fn next_seed<S: Seedable>(module: S) -> S {
+            let seed = module.seed();
+            module.set_seed(seed + 1)
+        }
+
+        assert_eq!(next_seed(Perlin::new(4)).seed(), 5);
+        assert_eq!(next_seed(Value::new(4)).seed(), 5);
+        assert_eq!(next_seed(Worley::<f64>::new().set_seed(4)).seed(), 5);
+
+        let fbm = next_seed(Fbm::<f64>::new().set_seed(4));
+        assert_eq!(fbm.seed(), 5);
+        let point = [0.3, 0.7, -1.1];
+        assert_eq!(fbm.get(point), Fbm::new().set_seed(5).get(point));
+    }
+}
@@ -13,14 +13,18 @@
 // limitations under the License.
 
 use num_traits::Float;
+use math;
 use math::interp;
 use NoiseModule;
+use modules::{Reseedable, source_seed};
 
 /// Noise module that outputs a weighted blend of the output values from two
 /// source modules given the output value supplied by a control module.
 ///
 /// This noise module uses linear interpolation to perform the blending
-/// operation.
+/// operation. The output value from the control module is mapped from
+/// [-1.0, 1.0] to a blend factor in [0.0, 1.0], so a control value of -1.0
+/// outputs `source1`, 0.0 outputs the midpoint and 1.0 outputs `source2`.
 #[derive(Clone, Debug)]
 pub struct Blend<Source1, Source2, Control> {
     /// Outputs one of the values to blend.
@@ -49,6 +53,18 @@ impl<Source1, Source2, Control> Blend<Source1, Source2, Control> {
     }
 }
 
+impl<Source1, Source2, Control> Reseedable for Blend<Source1, Source2, Control>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+          Control: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+        self.control.reseed(source_seed(seed, 2));
+    }
+}
+
 impl<Source1, Source2, Control, T, U> NoiseModule<T> for Blend<Source1, Source2, Control>
     where Source1: NoiseModule<T, Output = U>,
           Source2: NoiseModule<T, Output = U>,
@@ -61,8 +77,61 @@ impl<Source1, Source2, Control, T, U> NoiseModule<T> for Blend<Source1, Source2,
     fn get(&self, point: T) -> Self::Output {
         let lower = self.source1.get(point);
         let upper = self.source2.get(point);
-        let control = self.control.get(point);
+        let alpha = (self.control.get(point) + U::one()) * math::cast(0.5);
+
+        interp::linear(lower, upper, alpha)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate() +
+        self.control.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+        let (lower_control, upper_control) = self.control.bounds();
+
+        // The output is linear in each of the sources and the control value,
+        // so its extremes are at the corners of their bounds.
+        let mut bounds = (U::infinity(), U::neg_infinity());
+        for &value1 in &[lower1, upper1] {
+            for &value2 in &[lower2, upper2] {
+                for &control in &[lower_control, upper_control] {
+                    let alpha = (control + U::one()) * math::cast(0.5);
+                    let value = interp::linear(value1, value2, alpha);
+                    bounds = (bounds.0.min(value), bounds.1.max(value));
+                }
+            }
+        }
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Blend;
+    use modules::{Constant, Perlin, ScaleBias};
+    use NoiseModule;
+
+    #[test]
+    fn test_control_range() {
+        let blend = |control| {
+            Blend::new(Constant::new(-0.5), Constant::new(1.5), Constant::new(control))
+        };
+        assert_eq!(blend(-1.0).get([0.0, 0.0]), -0.5);
+        assert_eq!(blend(0.0).get([0.0, 0.0]), 0.5);
+        assert_eq!(blend(1.0).get([0.0, 0.0]), 1.5);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let blend = Blend::new(Constant::new(-0.5), Constant::new(1.5), Perlin::new(0));
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&blend), (-0.5, 1.5));
 
-        interp::linear(lower, upper, control)
+        // Control values beyond [-1,1] extrapolate past the sources.
+        let control = ScaleBias::new(Perlin::new(0)).set_scale(2.0);
+        let blend = Blend::new(Constant::new(-0.5), Constant::new(1.5), control);
+        assert_eq!(NoiseModule::<[f64; 2]>::bounds(&blend), (-1.5, 2.5));
     }
 }
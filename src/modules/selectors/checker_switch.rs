@@ -0,0 +1,107 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use NoiseModule;
+use modules::{Checkerboard, Reseedable, source_seed};
+
+/// Noise module that outputs the value from one of two source modules,
+/// alternating in a checkerboard pattern.
+///
+/// The cells of the pattern are the same as those of the `Checkerboard`
+/// noise module. In cells where the checkerboard outputs 1.0, this noise
+/// module outputs the value from `source1`. In the other cells, it outputs
+/// the value from `source2`.
+///
+/// This is useful for debugging, and for deliberate patchwork textures.
+#[derive(Clone, Copy, Debug)]
+pub struct CheckerSwitch<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Determines which source module is sampled in each cell.
+    pub checkerboard: Checkerboard,
+}
+
+impl<Source1, Source2> CheckerSwitch<Source1, Source2> {
+    /// Creates a checker switch with cells of 2<sup>size</sup> units.
+    pub fn new(source1: Source1, source2: Source2, size: usize) -> CheckerSwitch<Source1, Source2> {
+        CheckerSwitch {
+            source1: source1,
+            source2: source2,
+            checkerboard: Checkerboard::new(size),
+        }
+    }
+
+    /// Sets the size of the cells to 2<sup>size</sup> units.
+    pub fn set_size(self, size: usize) -> CheckerSwitch<Source1, Source2> {
+        CheckerSwitch { checkerboard: Checkerboard::new(size), ..self }
+    }
+}
+
+impl<Source1, Source2> Reseedable for CheckerSwitch<Source1, Source2>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<Source1, Source2, T, U> NoiseModule<T> for CheckerSwitch<Source1, Source2>
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          Checkerboard: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        if self.checkerboard.get(point) > U::zero() {
+            self.source1.get(point)
+        } else {
+            self.source2.get(point)
+        }
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        // Only one of the source modules is sampled at each point.
+        ::std::cmp::max(self.source1.complexity_estimate(), self.source2.complexity_estimate()) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CheckerSwitch;
+    use modules::Constant;
+    use NoiseModule;
+
+    #[test]
+    fn test_adjacent_cells_differ() {
+        let switch = CheckerSwitch::new(Constant::new(1.0), Constant::new(2.0), 0);
+        for i in -4..4 {
+            let x = i as f64 + 0.5;
+            let here: f64 = switch.get([x, 0.5]);
+            assert!(here != switch.get([x + 1.0, 0.5]));
+            assert!(here != switch.get([x, 1.5]));
+            assert_eq!(here, switch.get([x + 1.0, 1.5]));
+        }
+    }
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 pub use self::blend::*;
+pub use self::checker_switch::*;
 pub use self::select::*;
 
 mod blend;
+mod checker_switch;
 mod select;
@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use num_traits::Float;
+use math;
 use math::interp;
 use NoiseModule;
+use modules::{Reseedable, source_seed};
+use std::cell::RefCell;
+use std::collections::HashMap;
 
 /// Noise module that outputs the value selected from one of two source
 /// modules chosen by the output value from a control module.
@@ -61,6 +65,55 @@ impl<Source1, Source2, Control, T> Select<Source1, Source2, Control, T> {
     }
 }
 
+impl<Source1, Source2, Control, T> Select<Source1, Source2, Control, T>
+    where T: Float,
+{
+    /// Sets the bounds of the selection range. If `lower` is greater than
+    /// `upper`, the two are swapped. The edge falloff is reduced if it no
+    /// longer fits within half the selection range.
+    pub fn set_bounds(self, lower: T, upper: T) -> Select<Source1, Source2, Control, T> {
+        let select = Select {
+            lower_bound: lower.min(upper),
+            upper_bound: lower.max(upper),
+            ..self
+        };
+        let falloff = select.edge_falloff;
+        select.set_falloff(falloff)
+    }
+
+    /// Sets the falloff value at the edges of the selection range.
+    ///
+    /// Within `falloff` of either bound, the output value smoothly blends
+    /// between the two source modules using an S-curve. The falloff is
+    /// limited to half the width of the selection range, so the two blending
+    /// bands never overlap.
+    pub fn set_falloff(self, falloff: T) -> Select<Source1, Source2, Control, T> {
+        let half_range = (self.upper_bound - self.lower_bound) / (T::one() + T::one());
+        Select { edge_falloff: falloff.max(T::zero()).min(half_range), ..self }
+    }
+
+    /// Adds hysteresis to the selection, so that the source module selected
+    /// at a point only changes once the control value crosses a bound by
+    /// `margin`. See `HysteresisSelect`.
+    pub fn set_hysteresis(self,
+                          margin: T)
+                          -> HysteresisSelect<Source1, Source2, Control, T> {
+        HysteresisSelect::new(self).set_hysteresis(margin)
+    }
+}
+
+impl<Source1, Source2, Control, T> Reseedable for Select<Source1, Source2, Control, T>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+          Control: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source1.reseed(source_seed(seed, 0));
+        self.source2.reseed(source_seed(seed, 1));
+        self.control.reseed(source_seed(seed, 2));
+    }
+}
+
 impl<Source1, Source2, Control, T, U> NoiseModule<T> for Select<Source1, Source2, Control, U>
     where Source1: NoiseModule<T, Output = U>,
           Source2: NoiseModule<T, Output = U>,
@@ -72,39 +125,318 @@ impl<Source1, Source2, Control, T, U> NoiseModule<T> for Select<Source1, Source2
 
     fn get(&self, point: T) -> Self::Output {
         let control_value = self.control.get(point);
+        select_value(&self.source1,
+                     &self.source2,
+                     point,
+                     control_value,
+                     self.edge_falloff,
+                     self.lower_bound,
+                     self.upper_bound)
+    }
 
-        if self.edge_falloff > U::zero() {
-            match () {
-                _ if control_value < (self.lower_bound - self.edge_falloff) => {
-                    self.source1.get(point)
-                },
-                _ if control_value < (self.lower_bound + self.edge_falloff) => {
-                    let lower_curve: U = self.lower_bound - self.edge_falloff;
-                    let upper_curve: U = self.lower_bound + self.edge_falloff;
-                    let alpha = interp::s_curve3((control_value - lower_curve) /
-                                                 (upper_curve - lower_curve));
-
-                    interp::linear(self.source1.get(point), self.source2.get(point), alpha)
-                },
-                _ if control_value < (self.upper_bound - self.edge_falloff) => {
-                    self.source2.get(point)
-                },
-                _ if control_value < (self.upper_bound + self.edge_falloff) => {
-                    let lower_curve: U = self.upper_bound - self.edge_falloff;
-                    let upper_curve: U = self.upper_bound + self.edge_falloff;
-                    let alpha = interp::s_curve3((control_value - lower_curve) /
-                                                 (upper_curve - lower_curve));
-
-                    interp::linear(self.source2.get(point), self.source1.get(point), alpha)
-                },
-                _ => self.source1.get(point),
-            }
+    fn complexity_estimate(&self) -> usize {
+        self.source1.complexity_estimate() + self.source2.complexity_estimate() +
+        self.control.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        let (lower1, upper1) = self.source1.bounds();
+        let (lower2, upper2) = self.source2.bounds();
+        (lower1.min(lower2), upper1.max(upper2))
+    }
+}
+
+/// Outputs the value selected from one of the two source modules by the
+/// control value, blending between them within `falloff` of the bounds.
+fn select_value<Source1, Source2, T, U>(source1: &Source1,
+                                        source2: &Source2,
+                                        point: T,
+                                        control_value: U,
+                                        falloff: U,
+                                        lower: U,
+                                        upper: U)
+                                        -> U
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          T: Copy,
+          U: Float,
+{
+    if falloff > U::zero() {
+        match () {
+            _ if control_value < (lower - falloff) => {
+                source1.get(point)
+            },
+            _ if control_value < (lower + falloff) => {
+                let lower_curve: U = lower - falloff;
+                let upper_curve: U = lower + falloff;
+                let alpha = interp::s_curve3((control_value - lower_curve) /
+                                             (upper_curve - lower_curve));
+
+                interp::linear(source1.get(point), source2.get(point), alpha)
+            },
+            _ if control_value < (upper - falloff) => {
+                source2.get(point)
+            },
+            _ if control_value < (upper + falloff) => {
+                let lower_curve: U = upper - falloff;
+                let upper_curve: U = upper + falloff;
+                let alpha = interp::s_curve3((control_value - lower_curve) /
+                                             (upper_curve - lower_curve));
+
+                interp::linear(source2.get(point), source1.get(point), alpha)
+            },
+            _ => source1.get(point),
+        }
+    } else {
+        if control_value < lower || control_value > upper {
+            source1.get(point)
         } else {
-            if control_value < self.lower_bound || control_value > self.upper_bound {
-                self.source1.get(point)
-            } else {
-                self.source2.get(point)
+            source2.get(point)
+        }
+    }
+}
+
+/// Default number of points whose selection a `HysteresisSelect` remembers.
+pub const DEFAULT_HYSTERESIS_MAX_POINTS: usize = 65536;
+
+/// Noise module that selects between two source modules like `Select`, but
+/// remembers the selection made at each point and only changes it once the
+/// control value crosses a bound by a margin.
+///
+/// When the control module of a `Select` is animated, points whose control
+/// value hovers around a bound flicker between the two source modules from
+/// frame to frame. With hysteresis, a point outside the selection range only
+/// enters it once the control value is `margin` inside the bounds, and a
+/// point inside it only leaves once the control value is `margin` outside
+/// them. The plain bounds are used the first time a point is sampled. The
+/// edge falloff blends around the shifted bounds.
+///
+/// The selection is remembered per input point, of up to four dimensions, so
+/// the points should stay the same from frame to frame while the control
+/// module changes, such as by updating `select.control` between frames. At
+/// most `max_points` points are remembered. When a new point is sampled and
+/// the memory is full, it is cleared, as with `reset`. It uses interior
+/// mutability, so a `HysteresisSelect` cannot be shared between threads.
+#[derive(Clone, Debug)]
+pub struct HysteresisSelect<Source1, Source2, Control, T> {
+    /// Source modules, control module, bounds and edge falloff.
+    pub select: Select<Source1, Source2, Control, T>,
+
+    /// Distance the control value has to cross a bound by to change the
+    /// selection at a point. Default is 0.0.
+    margin: T,
+
+    /// Maximum number of points to remember the selection at.
+    max_points: usize,
+
+    /// Whether `source2` is selected at each point sampled so far, keyed by
+    /// `point_key`.
+    selected: RefCell<HashMap<[u64; 5], bool>>,
+}
+
+impl<Source1, Source2, Control, T> HysteresisSelect<Source1, Source2, Control, T>
+    where T: Float,
+{
+    pub fn new(select: Select<Source1, Source2, Control, T>)
+               -> HysteresisSelect<Source1, Source2, Control, T> {
+        HysteresisSelect {
+            select: select,
+            margin: T::zero(),
+            max_points: DEFAULT_HYSTERESIS_MAX_POINTS,
+            selected: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the bounds of the selection range, as with `Select::set_bounds`.
+    /// The margin is reduced if it no longer fits within half the selection
+    /// range.
+    pub fn set_bounds(self,
+                      lower: T,
+                      upper: T)
+                      -> HysteresisSelect<Source1, Source2, Control, T> {
+        let margin = self.margin;
+        HysteresisSelect { select: self.select.set_bounds(lower, upper), ..self }
+            .set_hysteresis(margin)
+    }
+
+    /// Sets the margin the control value has to cross a bound by to change
+    /// the selection at a point. The margin is limited to half the width of
+    /// the selection range, so that points outside it can still enter it.
+    pub fn set_hysteresis(self, margin: T) -> HysteresisSelect<Source1, Source2, Control, T> {
+        let half_range = (self.select.upper_bound - self.select.lower_bound) /
+                         (T::one() + T::one());
+        HysteresisSelect { margin: margin.max(T::zero()).min(half_range), ..self }
+    }
+
+    /// Sets the maximum number of points to remember the selection at, which
+    /// must be at least one.
+    pub fn set_max_points(self, max_points: usize)
+                          -> HysteresisSelect<Source1, Source2, Control, T> {
+        assert!(max_points > 0, "hysteresis select must be able to remember at least one point");
+
+        HysteresisSelect { max_points: max_points, ..self }
+    }
+
+    /// Forgets the selection made at every point sampled so far.
+    pub fn reset(&self) {
+        self.selected.borrow_mut().clear();
+    }
+}
+
+impl<Source1, Source2, Control, T> Reseedable for HysteresisSelect<Source1, Source2, Control, T>
+    where Source1: Reseedable,
+          Source2: Reseedable,
+          Control: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.select.reseed(seed);
+        self.selected.get_mut().clear();
+    }
+}
+
+impl<Source1, Source2, Control, T, U> NoiseModule<T>
+    for HysteresisSelect<Source1, Source2, Control, U>
+    where Source1: NoiseModule<T, Output = U>,
+          Source2: NoiseModule<T, Output = U>,
+          Control: NoiseModule<T, Output = U>,
+          T: Copy + AsRef<[U]>,
+          U: Float,
+{
+    type Output = U;
+
+    fn get(&self, point: T) -> Self::Output {
+        let select = &self.select;
+        let control_value = select.control.get(point);
+        let key = point_key(point.as_ref());
+
+        // Widen the selection range at points where source2 is selected, and
+        // narrow it where source1 is.
+        let remembered = self.selected.borrow().get(&key).cloned();
+        let (lower, upper) = match remembered {
+            Some(true) => (select.lower_bound - self.margin, select.upper_bound + self.margin),
+            Some(false) => (select.lower_bound + self.margin, select.upper_bound - self.margin),
+            None => (select.lower_bound, select.upper_bound),
+        };
+        let inside = control_value >= lower && control_value <= upper;
+
+        {
+            let mut selected = self.selected.borrow_mut();
+            if remembered.is_none() && selected.len() >= self.max_points {
+                selected.clear();
             }
+            selected.insert(key, inside);
         }
+
+        let half_range = (upper - lower) / (U::one() + U::one());
+        select_value(&select.source1,
+                     &select.source2,
+                     point,
+                     control_value,
+                     select.edge_falloff.min(half_range),
+                     lower,
+                     upper)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.select.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (U, U) {
+        self.select.bounds()
+    }
+}
+
+/// Returns the key a point is remembered by: the number of coordinates,
+/// followed by the bits of each coordinate.
+fn point_key<T: Float>(point: &[T]) -> [u64; 5] {
+    assert!(point.len() <= 4, "hysteresis select only supports points of up to four dimensions");
+
+    let mut key = [0; 5];
+    key[0] = point.len() as u64;
+    for (bits, &x) in key[1..].iter_mut().zip(point) {
+        *bits = math::cast::<_, f64>(x).to_bits();
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HysteresisSelect, Select};
+    use modules::Constant;
+    use NoiseModule;
+
+    fn select(control: f64) -> Select<Constant<f64>, Constant<f64>, Constant<f64>, f64> {
+        Select::new(Constant::new(-1.0), Constant::new(1.0), Constant::new(control), 0.0, 0.0, 0.0)
+            .set_bounds(0.5, -0.5)
+            .set_falloff(0.25)
+    }
+
+    #[test]
+    fn test_bounds_and_falloff() {
+        let point = [0.0, 0.0];
+        assert_eq!(select(-0.9).get(point), -1.0);
+        assert_eq!(select(0.0).get(point), 1.0);
+        assert_eq!(select(0.9).get(point), -1.0);
+
+        // At a bound, the blend is halfway between the two sources.
+        assert!(select(-0.5).get(point).abs() < 1e-12);
+        assert!(select(0.5).get(point).abs() < 1e-12);
+
+        let wide = select(0.0).set_falloff(4.0);
+        assert_eq!(wide.edge_falloff, 0.5);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let mut hysteresis = select(0.0).set_falloff(0.0).set_hysteresis(0.1);
+        let mut sample = |point, control| {
+            hysteresis.select.control = Constant::new(control);
+            hysteresis.get(point)
+        };
+        let point = [0.0, 0.0];
+
+        assert_eq!(sample(point, 0.0), 1.0);
+        // Crossing the upper bound switches to source1 only past the margin.
+        assert_eq!(sample(point, 0.55), 1.0);
+        assert_eq!(sample(point, 0.65), -1.0);
+        // Coming back in switches to source2 only past the margin inside.
+        assert_eq!(sample(point, 0.45), -1.0);
+        assert_eq!(sample(point, 0.35), 1.0);
+        assert_eq!(sample(point, -0.55), 1.0);
+        assert_eq!(sample(point, -0.65), -1.0);
+        assert_eq!(sample(point, -0.45), -1.0);
+
+        // A point sampled for the first time uses the plain bounds.
+        assert_eq!(sample([1.0, 0.0], -0.45), 1.0);
+
+        let wide = HysteresisSelect::new(select(0.0)).set_hysteresis(4.0);
+        assert_eq!(wide.margin, 0.5);
+    }
+
+    #[test]
+    fn test_hysteresis_max_points() {
+        let hysteresis = select(0.0).set_hysteresis(0.1).set_max_points(2);
+        for i in 0..5 {
+            hysteresis.get([i as f64, 0.0]);
+            assert!(hysteresis.selected.borrow().len() <= 2);
+        }
+
+        // Points of different dimensions are remembered separately.
+        let hysteresis = select(0.0).set_hysteresis(0.1);
+        hysteresis.get([0.0, 0.0]);
+        hysteresis.get([0.0, 0.0, 0.0]);
+        assert_eq!(hysteresis.selected.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_hysteresis_reset() {
+        let point = [0.0, 0.0];
+        let mut hysteresis = select(0.0).set_falloff(0.0).set_hysteresis(0.1);
+        assert_eq!(hysteresis.get(point), 1.0);
+
+        hysteresis.select.control = Constant::new(0.55);
+        assert_eq!(hysteresis.get(point), 1.0);
+        hysteresis.reset();
+        assert_eq!(hysteresis.get(point), -1.0);
     }
 }
@@ -0,0 +1,230 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+use modules::Reseedable;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Default number of tiles kept in the tiled world cache.
+pub const DEFAULT_TILED_WORLD_MAX_TILES: usize = 64;
+
+/// Noise module that lazily samples the source module into square tiles and
+/// caches them.
+///
+/// The source module is sampled on a lattice of points `spacing` units apart.
+/// The lattice is divided into tiles of `tile_size` × `tile_size` points. The
+/// first time a point within a tile is requested, the whole tile is generated
+/// from the source module; later requests within that tile are served from
+/// the cache. Each input point outputs the sample at the lattice point at or
+/// below it.
+///
+/// At most `max_tiles` tiles are cached. When a new tile is generated and the
+/// cache is full, the least recently used tile is evicted. This suits infinite
+/// streaming worlds, where only the tiles near the viewer are needed.
+pub struct TiledWorld<Source, T>
+    where T: Float,
+{
+    /// Outputs the values to be cached.
+    pub source: Source,
+
+    /// Number of lattice points along each side of a tile.
+    tile_size: usize,
+
+    /// Distance between neighbouring lattice points. Default is 1.0.
+    spacing: T,
+
+    /// Maximum number of tiles to cache.
+    max_tiles: usize,
+
+    tiles: RefCell<HashMap<[isize; 2], Tile<T>>>,
+
+    clock: Cell<u64>,
+}
+
+struct Tile<T> {
+    values: Vec<T>,
+    last_used: u64,
+}
+
+impl<Source, T> TiledWorld<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source, tile_size: usize) -> TiledWorld<Source, T> {
+        assert!(tile_size > 0, "tile size must be at least 1");
+
+        TiledWorld {
+            source: source,
+            tile_size: tile_size,
+            spacing: T::one(),
+            max_tiles: DEFAULT_TILED_WORLD_MAX_TILES,
+            tiles: RefCell::new(HashMap::new()),
+            clock: Cell::new(0),
+        }
+    }
+
+    /// Sets the distance between neighbouring lattice points. This clears the
+    /// cache.
+    pub fn set_spacing(self, spacing: T) -> TiledWorld<Source, T> {
+        self.tiles.borrow_mut().clear();
+        TiledWorld { spacing: spacing, ..self }
+    }
+
+    /// Sets the maximum number of tiles to cache, which must be at least one.
+    pub fn set_max_tiles(self, max_tiles: usize) -> TiledWorld<Source, T> {
+        assert!(max_tiles > 0, "tiled world must be able to cache at least one tile");
+
+        TiledWorld { max_tiles: max_tiles, ..self }
+    }
+
+    /// Returns the coordinates of the tile containing the input point.
+    pub fn tile_at(&self, point: Point2<T>) -> [isize; 2] {
+        let lattice = self.lattice_point(point);
+        let tile_size = self.tile_size as isize;
+        [div_floor(lattice[0], tile_size), div_floor(lattice[1], tile_size)]
+    }
+
+    /// Returns whether the tile with the given coordinates is cached.
+    pub fn is_cached(&self, tile: [isize; 2]) -> bool {
+        self.tiles.borrow().contains_key(&tile)
+    }
+
+    /// Returns the number of cached tiles.
+    pub fn cached_tiles(&self) -> usize {
+        self.tiles.borrow().len()
+    }
+
+    fn lattice_point(&self, point: Point2<T>) -> [isize; 2] {
+        [math::cast((point[0] / self.spacing).floor()),
+         math::cast((point[1] / self.spacing).floor())]
+    }
+
+    fn tick(&self) -> u64 {
+        let time = self.clock.get() + 1;
+        self.clock.set(time);
+        time
+    }
+}
+
+/// Integer division rounding towards negative infinity.
+fn div_floor(a: isize, b: isize) -> isize {
+    let quotient = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+impl<Source, T> Reseedable for TiledWorld<Source, T>
+    where Source: Reseedable,
+          T: Float,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+        self.tiles.borrow_mut().clear();
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for TiledWorld<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let tile_size = self.tile_size as isize;
+        let lattice = self.lattice_point(point);
+        let tile = self.tile_at(point);
+        let offset = [lattice[0] - tile[0] * tile_size, lattice[1] - tile[1] * tile_size];
+        let index = offset[1] as usize * self.tile_size + offset[0] as usize;
+        let time = self.tick();
+
+        let mut tiles = self.tiles.borrow_mut();
+        if let Some(cached) = tiles.get_mut(&tile) {
+            cached.last_used = time;
+            return cached.values[index];
+        }
+
+        if tiles.len() >= self.max_tiles {
+            let oldest = *tiles.iter()
+                .min_by_key(|&(_, cached)| cached.last_used)
+                .map(|(key, _)| key)
+                .unwrap();
+            tiles.remove(&oldest);
+        }
+
+        let mut values = Vec::with_capacity(self.tile_size * self.tile_size);
+        for y in 0..tile_size {
+            for x in 0..tile_size {
+                let sample = [math::cast::<_, T>(tile[0] * tile_size + x) * self.spacing,
+                              math::cast::<_, T>(tile[1] * tile_size + y) * self.spacing];
+                values.push(self.source.get(sample));
+            }
+        }
+        let value = values[index];
+        tiles.insert(tile, Tile { values: values, last_used: time });
+
+        value
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TiledWorld;
+    use test_utils::{Counting, Sum};
+    use NoiseModule;
+
+    #[test]
+    fn test_cached_tile_reused() {
+        let source = Counting::new(Sum);
+        let world = TiledWorld::new(&source, 4);
+
+        assert_eq!(world.get([1.5, 2.5]), 3.0);
+        assert_eq!(source.samples(), 16);
+
+        assert_eq!(world.get([1.25, 2.75]), 3.0);
+        assert_eq!(world.get([3.0, 0.0]), 3.0);
+        assert_eq!(world.get([-0.5, -3.5]), -5.0);
+        assert_eq!(source.samples(), 32);
+    }
+
+    #[test]
+    fn test_least_recently_used_tile_evicted() {
+        let source = Counting::new(Sum);
+        let world = TiledWorld::new(&source, 2).set_max_tiles(2);
+
+        world.get([0.0, 0.0]);
+        world.get([2.0, 0.0]);
+        world.get([0.0, 0.0]);
+        world.get([0.0, 2.0]);
+
+        assert_eq!(world.cached_tiles(), 2);
+        assert!(world.is_cached([0, 0]));
+        assert!(!world.is_cached([1, 0]));
+        assert!(world.is_cached([0, 1]));
+    }
+}
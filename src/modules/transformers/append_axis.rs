@@ -0,0 +1,136 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math::{Point2, Point3, Point4};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that appends a constant coordinate to the input value before
+/// returning the output value from the source module.
+///
+/// This turns an n-dimensional input value into an (n+1)-dimensional one, so
+/// that a lower dimensional graph can sample a higher dimensional source
+/// module. For example, a 2-dimensional graph can sample a slice of a
+/// 3-dimensional source module at a fixed _z_ coordinate.
+pub struct AppendAxis<Source, T> {
+    /// Source Module that outputs a value
+    pub source: Source,
+
+    /// Coordinate appended to the input value. The default value is 0.0.
+    pub value: T,
+}
+
+/// Noise module that animates the source module over time, by appending the
+/// time as an extra coordinate.
+///
+/// This is an `AppendAxis` whose appended coordinate is set with `set_time`
+/// before sampling each frame, so the module can be shared between threads
+/// while a frame is generated. The speed of the animation is controlled by how
+/// quickly the time advances relative to the frequency of the source.
+pub type Animate<Source, T> = AppendAxis<Source, T>;
+
+impl<Source, T> AppendAxis<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> AppendAxis<Source, T> {
+        AppendAxis {
+            source: source,
+            value: T::zero(),
+        }
+    }
+
+    /// Sets the coordinate appended to the input value.
+    pub fn set_value(self, value: T) -> AppendAxis<Source, T> {
+        AppendAxis { value: value, ..self }
+    }
+
+    /// Sets the time of an `Animate` module. This is the same as `set_value`.
+    pub fn set_time(self, time: T) -> AppendAxis<Source, T> {
+        self.set_value(time)
+    }
+}
+
+impl<Source, T> Reseedable for AppendAxis<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for AppendAxis<Source, T>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        self.source.get([point[0], point[1], self.value])
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for AppendAxis<Source, T>
+    where Source: NoiseModule<Point4<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        self.source.get([point[0], point[1], point[2], self.value])
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animate, AppendAxis};
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_append_zero() {
+        let perlin = Perlin::new(0);
+        let append = AppendAxis::new(perlin);
+        for i in 0..16 {
+            let (x, y) = (i as f64 * 0.37 - 2.0, i as f64 * 0.23);
+            let expected: f64 = perlin.get([x, y, 0.0]);
+            assert_eq!(append.get([x, y]), expected);
+        }
+    }
+
+    #[test]
+    fn test_animate() {
+        let perlin = Perlin::new(0);
+        let frame = Animate::new(perlin).set_time(2.5);
+        assert_eq!(frame.get([0.3, -1.7]), perlin.get([0.3, -1.7, 2.5]));
+        assert_eq!(frame.get([0.3, -1.7, 0.4]), perlin.get([0.3, -1.7, 0.4, 2.5]));
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that restricts the source module to a bounding box.
+///
+/// Inside the box, the output value is the output value of the source module.
+/// Outside the box, the output value is a constant, which defaults to -1.0.
+/// Across a band of width _falloff_ just inside the edges of the box, the
+/// output value smoothly blends between the two. This is useful for creating
+/// finite features, such as a single mountain.
+pub struct BoundedDomain<Source, P, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Lower corner of the bounding box.
+    pub lower_bound: P,
+
+    /// Upper corner of the bounding box.
+    pub upper_bound: P,
+
+    /// Width of the band inside the edges of the bounding box over which the
+    /// output blends from the constant value to the source module. The default
+    /// falloff is 0.0.
+    pub falloff: T,
+
+    /// Value to output outside the bounding box. The default value is -1.0.
+    pub value: T,
+}
+
+impl<Source, P, T> BoundedDomain<Source, P, T>
+    where T: Float,
+{
+    pub fn new(source: Source, lower_bound: P, upper_bound: P) -> BoundedDomain<Source, P, T> {
+        BoundedDomain {
+            source: source,
+            lower_bound: lower_bound,
+            upper_bound: upper_bound,
+            falloff: T::zero(),
+            value: -T::one(),
+        }
+    }
+
+    /// Sets the lower and upper corners of the bounding box.
+    pub fn set_bounds(self, lower_bound: P, upper_bound: P) -> BoundedDomain<Source, P, T> {
+        BoundedDomain {
+            lower_bound: lower_bound,
+            upper_bound: upper_bound,
+            ..self
+        }
+    }
+
+    /// Sets the width of the blending band inside the edges of the bounding
+    /// box.
+    pub fn set_falloff(self, falloff: T) -> BoundedDomain<Source, P, T> {
+        BoundedDomain { falloff: falloff.max(T::zero()), ..self }
+    }
+
+    /// Sets the value to output outside the bounding box.
+    pub fn set_value(self, value: T) -> BoundedDomain<Source, P, T> {
+        BoundedDomain { value: value, ..self }
+    }
+
+    /// Blends between the constant value and the source module, given the
+    /// distance from the point to the nearest edge of the bounding box.
+    /// Negative distances are outside the box.
+    fn blend<F>(&self, distance: T, source_value: F) -> T
+        where F: FnOnce() -> T,
+    {
+        if distance < T::zero() {
+            self.value
+        } else if distance >= self.falloff {
+            source_value()
+        } else {
+            let alpha = math::interp::s_curve3(distance / self.falloff);
+            math::interp::linear(self.value, source_value(), alpha)
+        }
+    }
+}
+
+impl<Source, P, T> Reseedable for BoundedDomain<Source, P, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for BoundedDomain<Source, Point2<T>, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let lower = math::sub2(point, self.lower_bound);
+        let upper = math::sub2(self.upper_bound, point);
+        let distance = math::fold2(math::zip_with2(lower, upper, T::min), T::min);
+
+        self.blend(distance, || self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        let (lower, upper) = self.source.bounds();
+        (lower.min(self.value), upper.max(self.value))
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for BoundedDomain<Source, Point3<T>, T>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let lower = math::sub3(point, self.lower_bound);
+        let upper = math::sub3(self.upper_bound, point);
+        let distance = math::fold3(math::zip_with3(lower, upper, T::min), T::min);
+
+        self.blend(distance, || self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        let (lower, upper) = self.source.bounds();
+        (lower.min(self.value), upper.max(self.value))
+    }
+}
+
+impl<Source, T> NoiseModule<Point4<T>> for BoundedDomain<Source, Point4<T>, T>
+    where Source: NoiseModule<Point4<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> Self::Output {
+        let lower = math::sub4(point, self.lower_bound);
+        let upper = math::sub4(self.upper_bound, point);
+        let distance = math::fold4(math::zip_with4(lower, upper, T::min), T::min);
+
+        self.blend(distance, || self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        let (lower, upper) = self.source.bounds();
+        (lower.min(self.value), upper.max(self.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedDomain;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_bounded_domain() {
+        let source = Perlin::new(0);
+        let bounded = BoundedDomain::new(source, [-2.0, -2.0], [2.0, 2.0])
+            .set_falloff(0.5)
+            .set_value(-1.0);
+
+        for i in 0..16 {
+            let t = i as f64 * 0.37;
+
+            // Well outside the box.
+            assert_eq!(bounded.get([10.0 + t, t]), -1.0);
+            assert_eq!(bounded.get([t, -10.0 - t]), -1.0);
+
+            // Inside the box, past the falloff band.
+            let inside = [t * 0.2 - 1.4, 1.4 - t * 0.2];
+            assert_eq!(bounded.get(inside), source.get(inside));
+        }
+
+        // The range includes the constant value.
+        assert_eq!(bounded.bounds(), (-1.0, 1.0));
+        assert_eq!(bounded.set_value(3.0).bounds(), (-1.0, 3.0));
+    }
+}
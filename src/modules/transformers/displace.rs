@@ -15,6 +15,7 @@
 use num_traits::Float;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::{Reseedable, source_seed};
 
 /// Noise Module that uses multiple source modules to displace each coordinate
 /// of the input value before returning the output value from the `source` module.
@@ -60,6 +61,23 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> Displace<Source,
     }
 }
 
+impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> Reseedable
+    for Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
+    where Source: Reseedable,
+          XDisplace: Reseedable,
+          YDisplace: Reseedable,
+          ZDisplace: Reseedable,
+          UDisplace: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(source_seed(seed, 0));
+        self.x_displace.reseed(source_seed(seed, 1));
+        self.y_displace.reseed(source_seed(seed, 2));
+        self.z_displace.reseed(source_seed(seed, 3));
+        self.u_displace.reseed(source_seed(seed, 4));
+    }
+}
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point2<T>>
     for Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
@@ -81,6 +99,15 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point2<T
         // original input value.
         self.source.get([x, y])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_displace.complexity_estimate() +
+        self.y_displace.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 #[cfg_attr(rustfmt, rustfmt_skip)]
@@ -98,7 +125,7 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point3<T
         // Get the output values from the displacement modules and add them to
         // the corresponding coordinate in the input value. Since this is a 3d
         // module, we only need the x_displace, y_displace, and z_displace
-        // modules. Also, panic if there is no z_displace module defined.
+        // modules.
         let x = point[0] + self.x_displace.get(point);
         let y = point[1] + self.y_displace.get(point);
         let z = point[2] + self.z_displace.get(point);
@@ -107,6 +134,15 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point3<T
         // original input value.
         self.source.get([x, y, z])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_displace.complexity_estimate() +
+        self.y_displace.complexity_estimate() + self.z_displace.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 #[cfg_attr(rustfmt, rustfmt_skip)]
@@ -124,8 +160,7 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point4<T
     fn get(&self, point: Point4<T>) -> Self::Output {
         // Get the output values from the displacement modules and add them to
         // the corresponding coordinate in the input value. Since this is a 4d
-        // module, we need all of the displace modules. Panic if there is no z-
-        // or u-displace module defined.
+        // module, we need all of the displace modules.
         let x = point[0] + self.x_displace.get(point);
         let y = point[1] + self.y_displace.get(point);
         let z = point[2] + self.z_displace.get(point);
@@ -135,4 +170,44 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace, T> NoiseModule<Point4<T
         // original input value.
         self.source.get([x, y, z, u])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_displace.complexity_estimate() +
+        self.y_displace.complexity_estimate() + self.z_displace.complexity_estimate() +
+        self.u_displace.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Displace;
+    use modules::Constant;
+    use NoiseModule;
+
+    /// Outputs the x coordinate scaled by the y coordinate.
+    struct Product;
+
+    impl NoiseModule<[f64; 2]> for Product {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0] * point[1]
+        }
+    }
+
+    #[test]
+    fn test_displacement_sampled_at_original_point() {
+        // The x displacement depends on the point, so sampling it at the
+        // already displaced point would give a different result.
+        let displace = Displace::new(Product,
+                                     Product,
+                                     Constant::new(1.0),
+                                     Constant::new(0.0),
+                                     Constant::new(0.0));
+        assert_eq!(displace.get([2.0, 3.0]), (2.0 + 6.0) * (3.0 + 1.0));
+    }
 }
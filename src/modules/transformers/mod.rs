@@ -12,14 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use self::append_axis::*;
+pub use self::bounded_domain::*;
 pub use self::displace::*;
+pub use self::modulate_frequency::*;
+pub use self::periodic_region::*;
 pub use self::rotate_point::*;
 pub use self::scale_point::*;
+pub use self::seamless::*;
+pub use self::self_warp::*;
 pub use self::translate_point::*;
 pub use self::turbulence::*;
+pub use self::vignette::*;
 
+mod append_axis;
+mod bounded_domain;
 mod displace;
+mod modulate_frequency;
+mod periodic_region;
 mod rotate_point;
 mod scale_point;
+mod seamless;
+mod self_warp;
 mod translate_point;
 mod turbulence;
+mod vignette;
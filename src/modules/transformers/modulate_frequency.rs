@@ -0,0 +1,125 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use NoiseModule;
+use modules::{Reseedable, source_seed};
+
+/// Noise module that scales the input point by the output value from a
+/// control module before returning the output value from the source module.
+///
+/// The control module is sampled at the input point, and its output value
+/// multiplies every coordinate of the point. This varies the frequency of the
+/// source module across space, giving large features where the control output
+/// is small and small features where it is large.
+///
+/// The scaling is about the origin, so features far from the origin move
+/// quickly as the control value changes. Smoothly varying control modules
+/// work best.
+pub struct ModulateFrequency<Source, Control> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Outputs the frequency multiplier for each input point.
+    pub control: Control,
+}
+
+impl<Source, Control> ModulateFrequency<Source, Control> {
+    pub fn new(source: Source, control: Control) -> ModulateFrequency<Source, Control> {
+        ModulateFrequency {
+            source: source,
+            control: control,
+        }
+    }
+}
+
+impl<Source, Control> Reseedable for ModulateFrequency<Source, Control>
+    where Source: Reseedable,
+          Control: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(source_seed(seed, 0));
+        self.control.reseed(source_seed(seed, 1));
+    }
+}
+
+impl<Source, Control, T> NoiseModule<Point2<T>> for ModulateFrequency<Source, Control>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          Control: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let frequency = self.control.get(point);
+        self.source.get(math::mul2(point, frequency))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.control.complexity_estimate()
+    }
+}
+
+impl<Source, Control, T> NoiseModule<Point3<T>> for ModulateFrequency<Source, Control>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          Control: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let frequency = self.control.get(point);
+        self.source.get(math::mul3(point, frequency))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.control.complexity_estimate()
+    }
+}
+
+impl<Source, Control, T> NoiseModule<Point4<T>> for ModulateFrequency<Source, Control>
+    where Source: NoiseModule<Point4<T>, Output = T>,
+          Control: NoiseModule<Point4<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> Self::Output {
+        let frequency = self.control.get(point);
+        self.source.get(math::mul4(point, frequency))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.control.complexity_estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModulateFrequency;
+    use modules::{Constant, Perlin, ScalePoint};
+    use test_utils::assert_modules_equal;
+
+    #[test]
+    fn test_constant_control_scales_frequency() {
+        let source = Perlin::new(0);
+        let modulated = ModulateFrequency::new(source, Constant::new(2.0));
+        let scaled = ScalePoint::new(source).set_scale(2.0);
+
+        assert_modules_equal(&modulated, &scaled, ([-3.0, -2.0], [4.0, 5.0]), 0.0);
+    }
+}
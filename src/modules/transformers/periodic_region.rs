@@ -0,0 +1,195 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that samples the source module periodically inside a region
+/// and non-periodically outside it.
+///
+/// Inside the region, the input point is wrapped so that the output value
+/// repeats every _period_ units along each axis, starting from the lower
+/// corner of the region. Outside the region, the source module is sampled
+/// directly. Across a band of width _falloff_ just inside the edges of the
+/// region, the output value smoothly blends between the two. This is useful
+/// for a tileable area, such as a skybox, embedded in an otherwise infinite
+/// world.
+pub struct PeriodicRegion<Source, P, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Lower corner of the region.
+    pub lower_bound: P,
+
+    /// Upper corner of the region.
+    pub upper_bound: P,
+
+    /// Period along each axis of the sampling inside the region.
+    pub period: P,
+
+    /// Width of the band inside the edges of the region over which the output
+    /// blends from non-periodic to periodic sampling. The default falloff is
+    /// 0.0.
+    pub falloff: T,
+}
+
+impl<Source, P, T> PeriodicRegion<Source, P, T>
+    where T: Float,
+{
+    pub fn new(source: Source,
+               lower_bound: P,
+               upper_bound: P,
+               period: P)
+               -> PeriodicRegion<Source, P, T> {
+        PeriodicRegion {
+            source: source,
+            lower_bound: lower_bound,
+            upper_bound: upper_bound,
+            period: period,
+            falloff: T::zero(),
+        }
+    }
+
+    /// Sets the lower and upper corners of the region.
+    pub fn set_bounds(self, lower_bound: P, upper_bound: P) -> PeriodicRegion<Source, P, T> {
+        PeriodicRegion {
+            lower_bound: lower_bound,
+            upper_bound: upper_bound,
+            ..self
+        }
+    }
+
+    /// Sets the period along each axis of the sampling inside the region.
+    pub fn set_period(self, period: P) -> PeriodicRegion<Source, P, T> {
+        PeriodicRegion { period: period, ..self }
+    }
+
+    /// Sets the width of the blending band inside the edges of the region.
+    pub fn set_falloff(self, falloff: T) -> PeriodicRegion<Source, P, T> {
+        PeriodicRegion { falloff: falloff.max(T::zero()), ..self }
+    }
+
+    /// Blends between non-periodic and periodic sampling, given the distance
+    /// from the point to the nearest edge of the region. Negative distances
+    /// are outside the region.
+    fn blend<F, G>(&self, distance: T, direct: F, periodic: G) -> T
+        where F: FnOnce() -> T,
+              G: FnOnce() -> T,
+    {
+        if distance < T::zero() {
+            direct()
+        } else if distance >= self.falloff {
+            periodic()
+        } else {
+            let alpha = math::interp::s_curve3(distance / self.falloff);
+            math::interp::linear(direct(), periodic(), alpha)
+        }
+    }
+}
+
+/// Wraps `x` into `[lower, lower + period)`.
+fn wrap<T: Float>(x: T, lower: T, period: T) -> T {
+    let offset = x - lower;
+    lower + offset - (offset / period).floor() * period
+}
+
+impl<Source, P, T> Reseedable for PeriodicRegion<Source, P, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for PeriodicRegion<Source, Point2<T>, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let lower = math::sub2(point, self.lower_bound);
+        let upper = math::sub2(self.upper_bound, point);
+        let distance = math::fold2(math::zip_with2(lower, upper, T::min), T::min);
+
+        self.blend(distance, || self.source.get(point), || {
+            let wrapped = [wrap(point[0], self.lower_bound[0], self.period[0]),
+                           wrap(point[1], self.lower_bound[1], self.period[1])];
+            self.source.get(wrapped)
+        })
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 2
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for PeriodicRegion<Source, Point3<T>, T>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let lower = math::sub3(point, self.lower_bound);
+        let upper = math::sub3(self.upper_bound, point);
+        let distance = math::fold3(math::zip_with3(lower, upper, T::min), T::min);
+
+        self.blend(distance, || self.source.get(point), || {
+            let wrapped = [wrap(point[0], self.lower_bound[0], self.period[0]),
+                           wrap(point[1], self.lower_bound[1], self.period[1]),
+                           wrap(point[2], self.lower_bound[2], self.period[2])];
+            self.source.get(wrapped)
+        })
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PeriodicRegion;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_periodic_region() {
+        let source = Perlin::new(0);
+        let region = PeriodicRegion::new(source, [-50.0, -50.0], [50.0, 50.0], [4.0, 4.0])
+            .set_falloff(8.0);
+
+        let mut differs = false;
+        for i in 0..16 {
+            let t = i as f64 * 0.37;
+
+            // Deep inside the region, the output tiles.
+            let inside = [t - 20.0, 10.0 - t];
+            let value = region.get(inside);
+            assert!((region.get([inside[0] + 4.0, inside[1]]) - value).abs() < 1e-12);
+            assert!((region.get([inside[0], inside[1] - 8.0]) - value).abs() < 1e-12);
+
+            // Far outside the region, the source is sampled directly.
+            let outside = [200.0 + t, t * 0.5];
+            assert_eq!(region.get(outside), source.get(outside));
+            differs |= region.get(outside) != region.get([outside[0] + 4.0, outside[1]]);
+        }
+        assert!(differs);
+    }
+}
@@ -14,9 +14,10 @@
 
 use num_traits::Float;
 use math;
-use math::{Point2, Point3, Point4};
+use math::{Point2, Point3, Point4, Vector3};
 use std::f64::consts::PI;
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise Module that rotates the input value around the origin before
 /// returning the output value from the source module.
@@ -26,6 +27,10 @@ use NoiseModule;
 ///
 /// The coordinate system of the input value is assumed to be "right-handed"
 /// (_x_ increases to the right, _y_ increases upward, and _z_ increases inward).
+///
+/// In three dimensions, the point can instead be rotated around an arbitrary
+/// axis, which avoids the gimbal artifacts of combining rotations around
+/// each axis. See `with_axis_angle`.
 pub struct RotatePoint<Source, T> {
     /// Source Module that outputs a value
     pub source: Source,
@@ -45,6 +50,10 @@ pub struct RotatePoint<Source, T> {
     /// _u_ rotation angle applied to the input value, in degrees. The
     /// default angle is set to 0.0 degrees.
     pub u_angle: T,
+
+    /// Rotation matrix applied to 3-dimensional input values instead of the
+    /// per-axis angles, if any. See `with_axis_angle`.
+    matrix: Option<[Vector3<T>; 3]>,
 }
 
 impl<Source, T> RotatePoint<Source, T>
@@ -57,9 +66,37 @@ impl<Source, T> RotatePoint<Source, T>
             y_angle: T::zero(),
             z_angle: T::zero(),
             u_angle: T::zero(),
+            matrix: None,
         }
     }
 
+    /// Creates a module that rotates 3-dimensional input values by `angle`
+    /// degrees around `axis`, following the right-hand rule.
+    ///
+    /// The rotation matrix is computed once, here, so `get` costs no more
+    /// than with the per-axis angles. In three dimensions the rotation
+    /// replaces the per-axis angles, which only apply to the other
+    /// dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is the zero vector.
+    pub fn with_axis_angle(source: Source, axis: Vector3<T>, angle: T) -> RotatePoint<Source, T> {
+        let length = math::dot3(axis, axis).sqrt();
+        assert!(length > T::zero(), "rotation axis must not be zero");
+        let axis = math::mul3(axis, length.recip());
+        let (x, y, z) = (axis[0], axis[1], axis[2]);
+
+        // Rodrigues' rotation formula.
+        let (s, c) = deg_to_rad(angle).sin_cos();
+        let t = T::one() - c;
+        let matrix = [[t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+                      [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+                      [t * x * z - s * y, t * y * z + s * x, t * z * z + c]];
+
+        RotatePoint { matrix: Some(matrix), ..RotatePoint::new(source) }
+    }
+
     /// Sets the rotation angle around the _x_ axis to apply to the input
     /// value.
     pub fn set_x_angle(self, x_angle: T) -> RotatePoint<Source, T> {
@@ -102,6 +139,14 @@ impl<Source, T> RotatePoint<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for RotatePoint<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T> NoiseModule<Point2<T>> for RotatePoint<Source, T>
     where Source: NoiseModule<Point2<T>, Output = T>,
           T: Float,
@@ -122,6 +167,14 @@ impl<Source, T> NoiseModule<Point2<T>> for RotatePoint<Source, T>
         // original input value.
         self.source.get([x2, y2])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point3<T>> for RotatePoint<Source, T>
@@ -131,8 +184,15 @@ impl<Source, T> NoiseModule<Point3<T>> for RotatePoint<Source, T>
     type Output = T;
 
     fn get(&self, point: Point3<T>) -> Self::Output {
+        if let Some(matrix) = self.matrix {
+            let rotated = [math::dot3(matrix[0], point),
+                           math::dot3(matrix[1], point),
+                           math::dot3(matrix[2], point)];
+            return self.source.get(rotated);
+        }
+
         // In three dimensions, we could rotate around any of the x, y, or z
-        // axes. Need a more complicated function to handle this case.this
+        // axes. Need a more complicated function to handle this case.
         let x_cos = deg_to_rad(self.x_angle).cos();
         let y_cos = deg_to_rad(self.y_angle).cos();
         let z_cos = deg_to_rad(self.z_angle).cos();
@@ -158,6 +218,14 @@ impl<Source, T> NoiseModule<Point3<T>> for RotatePoint<Source, T>
         // original input value.
         self.source.get([x, y, z])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point4<T>> for RotatePoint<Source, T>
@@ -170,8 +238,83 @@ impl<Source, T> NoiseModule<Point4<T>> for RotatePoint<Source, T>
         // 4d rotations are hard.
         panic!("4d not implemented!");
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 fn deg_to_rad<T: Float>(x: T) -> T {
     (x / math::cast(180.0)) * math::cast(PI)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RotatePoint;
+    use math;
+    use NoiseModule;
+
+    /// Outputs the distance from the origin, which rotations preserve.
+    struct Distance;
+
+    impl NoiseModule<[f64; 3]> for Distance {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 3]) -> f64 {
+            math::dot3(point, point).sqrt()
+        }
+    }
+
+    #[test]
+    fn test_rotation_preserves_distance() {
+        let rotate = RotatePoint::new(Distance).set_angles(30.0, 45.0, 60.0, 0.0);
+        for i in 0..16 {
+            let t = i as f64 * 0.37;
+            let point = [t - 2.0, 1.0 - t * 0.5, t * 0.25];
+            assert!((rotate.get(point) - Distance.get(point)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_axis_angle() {
+        /// Outputs one coordinate of the point.
+        struct Axis(usize);
+
+        impl NoiseModule<[f64; 3]> for Axis {
+            type Output = f64;
+
+            fn get(&self, point: [f64; 3]) -> f64 {
+                point[self.0]
+            }
+        }
+
+        fn assert_rotates(axis: [f64; 3], angle: f64, point: [f64; 3], expected: [f64; 3]) {
+            for i in 0..3 {
+                let rotate = RotatePoint::with_axis_angle(Axis(i), axis, angle);
+                assert!((rotate.get(point) - expected[i]).abs() < 1e-12);
+            }
+        }
+
+        assert_rotates([0.0, 0.0, 2.0], 90.0, [1.0, 0.0, 0.5], [0.0, 1.0, 0.5]);
+
+        // A third of a turn around the diagonal cycles the axes.
+        assert_rotates([1.0, 1.0, 1.0], 120.0, [1.0, 2.0, 3.0], [3.0, 1.0, 2.0]);
+
+        let rotate = RotatePoint::with_axis_angle(Distance, [0.3, -1.0, 0.7], 73.0);
+        for i in 0..16 {
+            let t = i as f64 * 0.37;
+            let point = [t - 2.0, 1.0 - t * 0.5, t * 0.25];
+            assert!((rotate.get(point) - Distance.get(point)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_axis_angle_zero_axis() {
+        RotatePoint::<Distance, f64>::with_axis_angle(Distance, [0.0; 3], 45.0);
+    }
+}
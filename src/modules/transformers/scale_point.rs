@@ -15,6 +15,7 @@
 use num_traits::Float;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise Module that scales the coordinates of the input value before
 /// returning the output value from the source module.
@@ -61,19 +62,19 @@ impl<Source, T> ScalePoint<Source, T>
         ScalePoint { x_scale: x_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _y_ coordinate of the input
     /// value.
     pub fn set_y_scale(self, y_scale: T) -> ScalePoint<Source, T> {
         ScalePoint { y_scale: y_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _z_ coordinate of the input
     /// value.
     pub fn set_z_scale(self, z_scale: T) -> ScalePoint<Source, T> {
         ScalePoint { z_scale: z_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _u_ coordinate of the input
     /// value.
     pub fn set_u_scale(self, u_scale: T) -> ScalePoint<Source, T> {
         ScalePoint { u_scale: u_scale, ..self }
@@ -108,6 +109,14 @@ impl<Source, T> ScalePoint<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for ScalePoint<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T> NoiseModule<Point2<T>> for ScalePoint<Source, T>
     where Source: NoiseModule<Point2<T>, Output = T>,
           T: Float,
@@ -117,6 +126,14 @@ impl<Source, T> NoiseModule<Point2<T>> for ScalePoint<Source, T>
     fn get(&self, point: Point2<T>) -> Self::Output {
         self.source.get([point[0] * self.x_scale, point[1] * self.y_scale])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point3<T>> for ScalePoint<Source, T>
@@ -129,6 +146,14 @@ impl<Source, T> NoiseModule<Point3<T>> for ScalePoint<Source, T>
 
         self.source.get([point[0] * self.x_scale, point[1] * self.y_scale, point[2] * self.z_scale])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point4<T>> for ScalePoint<Source, T>
@@ -143,4 +168,12 @@ impl<Source, T> NoiseModule<Point4<T>> for ScalePoint<Source, T>
                          point[2] * self.z_scale,
                          point[3] * self.u_scale])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
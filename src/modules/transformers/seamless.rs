@@ -0,0 +1,121 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point4};
+use std::f64::consts::PI;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that makes any 4-dimensional source tile seamlessly in two
+/// dimensions.
+///
+/// The input point is wrapped onto a torus embedded in 4-dimensional space:
+/// the x coordinate becomes an angle around one circle, and the y coordinate
+/// an angle around a second, perpendicular circle. The output repeats every
+/// `width` units along the x axis and every `height` units along the y axis,
+/// without requiring a periodic source.
+///
+/// Each circle has a circumference equal to its period, so one unit in the
+/// input covers about one unit in the source, and features keep the size they
+/// would have in the source. To change the feature size, scale the source's
+/// frequency rather than the input point; scaling the input point would also
+/// scale the period.
+pub struct Seamless<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Number of units after which the output repeats along the x axis. The
+    /// default width is 1.0.
+    pub width: T,
+
+    /// Number of units after which the output repeats along the y axis. The
+    /// default height is 1.0.
+    pub height: T,
+}
+
+impl<Source, T> Seamless<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> Seamless<Source, T> {
+        Seamless {
+            source: source,
+            width: T::one(),
+            height: T::one(),
+        }
+    }
+
+    pub fn set_size(self, width: T, height: T) -> Seamless<Source, T> {
+        Seamless {
+            width: width,
+            height: height,
+            ..self
+        }
+    }
+}
+
+impl<Source, T> Reseedable for Seamless<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for Seamless<Source, T>
+    where Source: NoiseModule<Point4<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let two_pi: T = math::cast(2.0 * PI);
+        let u = point[0] / self.width * two_pi;
+        let v = point[1] / self.height * two_pi;
+        let (u_radius, v_radius) = (self.width / two_pi, self.height / two_pi);
+
+        self.source.get([u.cos() * u_radius,
+                         u.sin() * u_radius,
+                         v.cos() * v_radius,
+                         v.sin() * v_radius])
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Seamless;
+    use modules::Fbm;
+    use test_utils::sample_points2;
+    use NoiseModule;
+
+    #[test]
+    fn test_seamless_tiles() {
+        let seamless = Seamless::new(Fbm::new()).set_size(5.0, 3.0);
+        for &point in sample_points2(32).iter() {
+            let value = seamless.get(point);
+            assert!((seamless.get([point[0] + 5.0, point[1]]) - value).abs() < 1e-9);
+            assert!((seamless.get([point[0], point[1] - 3.0]) - value).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3, Point4};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Default strength of the warp for the self-warp noise module.
+pub const DEFAULT_SELF_WARP_STRENGTH: f64 = 1.0;
+
+/// Default blend factor for the self-warp noise module.
+pub const DEFAULT_SELF_WARP_MIX: f64 = 0.5;
+
+/// Noise module that blends the output value from the source module with a
+/// copy of itself sampled at a warped point.
+///
+/// The warped point is the input point offset along every axis by the output
+/// value from the source module at the input point, multiplied by the
+/// _strength_. The two output values are then linearly blended by the _mix_
+/// factor, where 0.0 outputs the plain source and 1.0 outputs the warped copy.
+/// This is a compact way of adding detail without a separate displacement
+/// module.
+pub struct SelfWarp<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Multiplier applied to the output value from the source module to get
+    /// the offset of the warped point. The default strength is 1.0.
+    pub strength: T,
+
+    /// Blend factor between the plain and warped output values. The default
+    /// mix is 0.5.
+    pub mix: T,
+}
+
+impl<Source, T> SelfWarp<Source, T>
+    where T: Float,
+{
+    pub fn new(source: Source) -> SelfWarp<Source, T> {
+        SelfWarp {
+            source: source,
+            strength: math::cast(DEFAULT_SELF_WARP_STRENGTH),
+            mix: math::cast(DEFAULT_SELF_WARP_MIX),
+        }
+    }
+
+    pub fn set_strength(self, strength: T) -> SelfWarp<Source, T> {
+        SelfWarp { strength: strength, ..self }
+    }
+
+    pub fn set_mix(self, mix: T) -> SelfWarp<Source, T> {
+        SelfWarp { mix: mix, ..self }
+    }
+}
+
+impl<Source, T> Reseedable for SelfWarp<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for SelfWarp<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let value = self.source.get(point);
+        let warped = self.source.get(math::add2(point, math::const2(value * self.strength)));
+
+        math::interp::linear(value, warped, self.mix)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 2
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for SelfWarp<Source, T>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let value = self.source.get(point);
+        let warped = self.source.get(math::add3(point, math::const3(value * self.strength)));
+
+        math::interp::linear(value, warped, self.mix)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 2
+    }
+}
+
+impl<Source, T> NoiseModule<Point4<T>> for SelfWarp<Source, T>
+    where Source: NoiseModule<Point4<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point4<T>) -> Self::Output {
+        let value = self.source.get(point);
+        let warped = self.source.get(math::add4(point, math::const4(value * self.strength)));
+
+        math::interp::linear(value, warped, self.mix)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SelfWarp;
+    use modules::Perlin;
+    use test_utils::sample_points3;
+    use NoiseModule;
+
+    #[test]
+    fn test_zero_strength_is_plain_source() {
+        let source = Perlin::new(0);
+        let plain = SelfWarp::new(source).set_strength(0.0).set_mix(0.75);
+        let warped = SelfWarp::new(source).set_strength(2.0);
+
+        let mut differs = false;
+        for &point in sample_points3(16).iter() {
+            let value: f64 = source.get(point);
+            assert!((plain.get(point) - value).abs() < 1e-12);
+            differs |= warped.get(point) != value;
+        }
+        assert!(differs);
+    }
+}
@@ -15,6 +15,7 @@
 use num_traits::Float;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
+use modules::Reseedable;
 
 /// Noise Module that moves the coordinates of the input value before
 /// returning the output value from the source module.
@@ -55,37 +56,37 @@ impl<Source, T> TranslatePoint<Source, T>
         }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the translation amount to apply to the _x_ coordinate of the input
     /// value.
     pub fn set_x_translation(self, x_translation: T) -> TranslatePoint<Source, T> {
         TranslatePoint { x_translation: x_translation, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the translation amount to apply to the _y_ coordinate of the input
     /// value.
     pub fn set_y_translation(self, y_translation: T) -> TranslatePoint<Source, T> {
         TranslatePoint { y_translation: y_translation, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the translation amount to apply to the _z_ coordinate of the input
     /// value.
     pub fn set_z_translation(self, z_translation: T) -> TranslatePoint<Source, T> {
         TranslatePoint { z_translation: z_translation, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the translation amount to apply to the _u_ coordinate of the input
     /// value.
     pub fn set_u_translation(self, u_translation: T) -> TranslatePoint<Source, T> {
         TranslatePoint { u_translation: u_translation, ..self }
     }
 
     /// Sets the translation amount to apply to all coordinates of the input value.
-    pub fn set_translation(self, scale: T) -> TranslatePoint<Source, T> {
+    pub fn set_translation(self, translation: T) -> TranslatePoint<Source, T> {
         TranslatePoint {
-            x_translation: scale,
-            y_translation: scale,
-            z_translation: scale,
-            u_translation: scale,
+            x_translation: translation,
+            y_translation: translation,
+            z_translation: translation,
+            u_translation: translation,
             ..self
         }
     }
@@ -108,6 +109,14 @@ impl<Source, T> TranslatePoint<Source, T>
     }
 }
 
+impl<Source, T> Reseedable for TranslatePoint<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
 impl<Source, T> NoiseModule<Point2<T>> for TranslatePoint<Source, T>
     where Source: NoiseModule<Point2<T>, Output = T>,
           T: Float,
@@ -117,6 +126,14 @@ impl<Source, T> NoiseModule<Point2<T>> for TranslatePoint<Source, T>
     fn get(&self, point: Point2<T>) -> Self::Output {
         self.source.get([point[0] + self.x_translation, point[1] + self.y_translation])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point3<T>> for TranslatePoint<Source, T>
@@ -126,11 +143,18 @@ impl<Source, T> NoiseModule<Point3<T>> for TranslatePoint<Source, T>
     type Output = T;
 
     fn get(&self, point: Point3<T>) -> Self::Output {
-
         self.source.get([point[0] + self.x_translation,
                          point[1] + self.y_translation,
                          point[2] + self.z_translation])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point4<T>> for TranslatePoint<Source, T>
@@ -145,4 +169,57 @@ impl<Source, T> NoiseModule<Point4<T>> for TranslatePoint<Source, T>
                          point[2] + self.z_translation,
                          point[3] + self.u_translation])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TranslatePoint;
+    use modules::{Fbm, RotatePoint, ScalePoint, Turbulence};
+    use NoiseModule;
+
+    /// Outputs the sum of the coordinates, weighted so each axis is distinct.
+    struct Weighted;
+
+    impl NoiseModule<[f64; 3]> for Weighted {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 3]) -> f64 {
+            point[0] + point[1] * 10.0 + point[2] * 100.0
+        }
+    }
+
+    #[test]
+    fn test_per_axis_translation_and_scale() {
+        let translate = TranslatePoint::new(Weighted).set_all_translations(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(translate.get([0.5, 0.5, 0.5]), 1.5 + 25.0 + 350.0);
+
+        let scale = ScalePoint::new(Weighted).set_all_scales(2.0, 3.0, 4.0, 5.0);
+        assert_eq!(scale.get([1.0, 1.0, 1.0]), 2.0 + 30.0 + 400.0);
+    }
+
+    #[test]
+    fn test_bounds_forwarded() {
+        // The unnormalized Fbm exceeds [-1,1], and the domain transformers
+        // report its range unchanged.
+        let fbm = Fbm::<f64>::new();
+        let expected = NoiseModule::<[f64; 3]>::bounds(&fbm);
+        assert!(expected.1 > 1.0);
+
+        let scale = ScalePoint::new(fbm.clone()).set_scale(2.0);
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&scale), expected);
+        let translate = TranslatePoint::new(fbm.clone()).set_translation(2.0);
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&translate), expected);
+        let rotate = RotatePoint::new(fbm.clone()).set_angles(30.0, 0.0, 45.0, 0.0);
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&rotate), expected);
+        let turbulence = Turbulence::new(fbm);
+        assert_eq!(NoiseModule::<[f64; 3]>::bounds(&turbulence), expected);
+    }
 }
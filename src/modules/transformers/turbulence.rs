@@ -16,11 +16,15 @@ use num_traits::Float;
 use math;
 use math::{Point2, Point3, Point4};
 use NoiseModule;
-use modules::Fbm;
+use modules::{Fbm, Reseedable, Seedable, source_seed};
 
+/// Default seed for the turbulence noise module.
 pub const DEFAULT_TURBULENCE_SEED: usize = 0;
+/// Default frequency for the turbulence noise module.
 pub const DEFAULT_TURBULENCE_FREQUENCY: f32 = 1.0;
+/// Default power for the turbulence noise module.
 pub const DEFAULT_TURBULENCE_POWER: f32 = 1.0;
+/// Default roughness for the turbulence noise module.
 pub const DEFAULT_TURBULENCE_ROUGHNESS: usize = 3;
 
 /// Noise Module that randomly displaces the input value before returning the
@@ -31,10 +35,17 @@ pub const DEFAULT_TURBULENCE_ROUGHNESS: usize = 3;
 /// retrieving the output value from the source module. To control the
 /// turbulence, an application can modify its frequency, its power, and its
 /// roughness.
+///
+/// Each coordinate is displaced by its own `Fbm` noise module, so the
+/// strength and character of the warp can be tuned independently of the
+/// source module.
 pub struct Turbulence<Source, T> {
     /// Source Module that outputs a value
     pub source: Source,
 
+    /// Scaling factor applied to the output of the displacement modules,
+    /// which is the maximum distance a coordinate is displaced by. The
+    /// default power is 1.0.
     pub power: T,
 
     x_distort_module: Fbm<T>,
@@ -69,16 +80,21 @@ impl<Source, T> Turbulence<Source, T>
         }
     }
 
+    /// Sets the seed of the displacement modules. Each coordinate uses a
+    /// consecutive seed starting from this one.
     pub fn set_seed(self, seed: usize) -> Turbulence<Source, T> {
         Turbulence {
             x_distort_module: self.x_distort_module.set_seed(seed),
-            y_distort_module: self.y_distort_module.set_seed(seed + 1),
-            z_distort_module: self.z_distort_module.set_seed(seed + 2),
-            u_distort_module: self.u_distort_module.set_seed(seed + 3),
+            y_distort_module: self.y_distort_module.set_seed(seed.wrapping_add(1)),
+            z_distort_module: self.z_distort_module.set_seed(seed.wrapping_add(2)),
+            u_distort_module: self.u_distort_module.set_seed(seed.wrapping_add(3)),
             ..self
         }
     }
 
+    /// Sets the frequency of the displacement modules, which controls how
+    /// rapidly the displacement changes across the input space. The default
+    /// frequency is 1.0.
     pub fn set_frequency(self, frequency: T) -> Turbulence<Source, T> {
         Turbulence {
             x_distort_module: self.x_distort_module.set_frequency(frequency),
@@ -89,10 +105,15 @@ impl<Source, T> Turbulence<Source, T>
         }
     }
 
+    /// Sets the scaling factor applied to the displacement. The default power
+    /// is 1.0.
     pub fn set_power(self, power: T) -> Turbulence<Source, T> {
         Turbulence { power: power, ..self }
     }
 
+    /// Sets the roughness of the displacement, which is the number of octaves
+    /// of the displacement modules. Higher roughness gives a more jagged
+    /// warp. The default roughness is 3.
     pub fn set_roughness(self, roughness: usize) -> Turbulence<Source, T> {
         Turbulence {
             x_distort_module: self.x_distort_module.set_octaves(roughness),
@@ -102,15 +123,10 @@ impl<Source, T> Turbulence<Source, T>
             ..self
         }
     }
-}
-
-impl<Source, T> NoiseModule<Point2<T>> for Turbulence<Source, T>
-    where Source: NoiseModule<Point2<T>, Output = T>,
-          T: Float,
-{
-    type Output = T;
 
-    fn get(&self, point: Point2<T>) -> Self::Output {
+    /// Returns the displaced point that the source module is sampled at for
+    /// the given 2-dimensional input point.
+    pub fn warp2(&self, point: Point2<T>) -> Point2<T> {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -123,7 +139,47 @@ impl<Source, T> NoiseModule<Point2<T>> for Turbulence<Source, T>
         let x_distort = point[0] + (self.x_distort_module.get([x0, y0]) * self.power);
         let y_distort = point[1] + (self.y_distort_module.get([x1, y1]) * self.power);
 
-        self.source.get([x_distort, y_distort])
+        [x_distort, y_distort]
+    }
+}
+
+impl<Source, T: Float> Seedable for Turbulence<Source, T> {
+    fn set_seed(self, seed: usize) -> Turbulence<Source, T> {
+        Turbulence::set_seed(self, seed)
+    }
+
+    fn seed(&self) -> usize {
+        self.x_distort_module.seed
+    }
+}
+
+impl<Source: Reseedable, T> Reseedable for Turbulence<Source, T> {
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(source_seed(seed, 0));
+        self.x_distort_module.reseed(seed);
+        self.y_distort_module.reseed(seed.wrapping_add(1));
+        self.z_distort_module.reseed(seed.wrapping_add(2));
+        self.u_distort_module.reseed(seed.wrapping_add(3));
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for Turbulence<Source, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        self.source.get(self.warp2(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_distort_module.octaves +
+        self.y_distort_module.octaves
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
     }
 }
 
@@ -155,6 +211,15 @@ impl<Source, T> NoiseModule<Point3<T>> for Turbulence<Source, T>
 
         self.source.get([x_distort, y_distort, z_distort])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_distort_module.octaves +
+        self.y_distort_module.octaves + self.z_distort_module.octaves
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
 
 impl<Source, T> NoiseModule<Point4<T>> for Turbulence<Source, T>
@@ -194,4 +259,14 @@ impl<Source, T> NoiseModule<Point4<T>> for Turbulence<Source, T>
 
         self.source.get([x_distort, y_distort, z_distort, u_distort])
     }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate() + self.x_distort_module.octaves +
+        self.y_distort_module.octaves + self.z_distort_module.octaves +
+        self.u_distort_module.octaves
+    }
+
+    fn bounds(&self) -> (T, T) {
+        self.source.bounds()
+    }
 }
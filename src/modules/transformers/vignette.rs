@@ -0,0 +1,173 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::{Point2, Point3};
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that fades the output value from the source module towards a
+/// constant with distance from a center point.
+///
+/// Within the _inner radius_ of the center, the output value is the output
+/// value of the source module. Beyond the _outer radius_, the output value is
+/// the constant, which defaults to -1.0. Between the two radii, the output
+/// value smoothly blends from one to the other. This is useful for focusing
+/// detail around a point of interest.
+pub struct Vignette<Source, P, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Center of the vignette.
+    pub center: P,
+
+    /// Distance from the center within which the source module is unchanged.
+    /// The default inner radius is 0.5.
+    pub inner_radius: T,
+
+    /// Distance from the center beyond which the constant is output. The
+    /// default outer radius is 1.0.
+    pub outer_radius: T,
+
+    /// Value to fade towards. The default value is -1.0.
+    pub value: T,
+}
+
+impl<Source, P, T> Vignette<Source, P, T>
+    where T: Float,
+{
+    pub fn new(source: Source, center: P) -> Vignette<Source, P, T> {
+        Vignette {
+            source: source,
+            center: center,
+            inner_radius: math::cast(0.5),
+            outer_radius: T::one(),
+            value: -T::one(),
+        }
+    }
+
+    pub fn set_center(self, center: P) -> Vignette<Source, P, T> {
+        Vignette { center: center, ..self }
+    }
+
+    /// Sets the inner and outer radii. If `inner` is greater than `outer`,
+    /// the two are swapped.
+    pub fn set_radii(self, inner: T, outer: T) -> Vignette<Source, P, T> {
+        Vignette {
+            inner_radius: inner.min(outer),
+            outer_radius: inner.max(outer),
+            ..self
+        }
+    }
+
+    pub fn set_value(self, value: T) -> Vignette<Source, P, T> {
+        Vignette { value: value, ..self }
+    }
+
+    /// Blends between the source module and the constant, given the distance
+    /// from the point to the center.
+    fn blend<F>(&self, distance: T, source_value: F) -> T
+        where F: FnOnce() -> T,
+    {
+        if distance <= self.inner_radius {
+            source_value()
+        } else if distance >= self.outer_radius {
+            self.value
+        } else {
+            let alpha = (distance - self.inner_radius) / (self.outer_radius - self.inner_radius);
+            math::interp::linear(source_value(), self.value, math::interp::s_curve3(alpha))
+        }
+    }
+}
+
+impl<Source, P, T> Reseedable for Vignette<Source, P, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for Vignette<Source, Point2<T>, T>
+    where Source: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        let offset = math::sub2(point, self.center);
+        let distance = math::dot2(offset, offset).sqrt();
+
+        self.blend(distance, || self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        let (lower, upper) = self.source.bounds();
+        (lower.min(self.value), upper.max(self.value))
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for Vignette<Source, Point3<T>, T>
+    where Source: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    type Output = T;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        let offset = math::sub3(point, self.center);
+        let distance = math::dot3(offset, offset).sqrt();
+
+        self.blend(distance, || self.source.get(point))
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (T, T) {
+        let (lower, upper) = self.source.bounds();
+        (lower.min(self.value), upper.max(self.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vignette;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_vignette() {
+        let source = Perlin::new(0);
+        let vignette = Vignette::new(source, [2.0, -1.0])
+            .set_radii(3.0, 1.0)
+            .set_value(0.25);
+
+        for i in 0..16 {
+            let angle = i as f64 * 0.41;
+            let inside = [2.0 + angle.cos() * 0.9, -1.0 + angle.sin() * 0.9];
+            assert_eq!(vignette.get(inside), source.get(inside));
+
+            let outside = [2.0 + angle.cos() * 3.1, -1.0 + angle.sin() * 3.1];
+            assert_eq!(vignette.get(outside), 0.25);
+        }
+        assert_eq!(vignette.get([2.0, -1.0]), source.get([2.0, -1.0]));
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math::{Point2, Point3, Point4};
+use std::marker::PhantomData;
+use NoiseModule;
+use modules::Reseedable;
+
+/// Noise module that fixes the float type of the points passed to the source
+/// module.
+///
+/// Generators such as `Perlin` implement `NoiseModule` for every float type,
+/// so the point type usually has to be annotated at each call. Wrapping the
+/// generator binds the float type once, after which plain literals can be
+/// used:
+///
+/// ```rust
+/// use noise::NoiseModule;
+/// use noise::modules::{NoiseModuleExt, Perlin};
+///
+/// let perlin = Perlin::new(0).typed::<f32>();
+/// let value = perlin.get([0.5, 0.25]);
+/// # let _: f32 = value;
+/// ```
+pub struct Typed<Source, T> {
+    /// Outputs a value.
+    pub source: Source,
+
+    input: PhantomData<T>,
+}
+
+impl<Source, T> Typed<Source, T> {
+    pub fn new(source: Source) -> Typed<Source, T> {
+        Typed {
+            source: source,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<Source, T> Reseedable for Typed<Source, T>
+    where Source: Reseedable,
+{
+    fn reseed(&mut self, seed: usize) {
+        self.source.reseed(seed);
+    }
+}
+
+impl<Source, T> NoiseModule<Point2<T>> for Typed<Source, T>
+    where Source: NoiseModule<Point2<T>>,
+          T: Copy,
+{
+    type Output = Source::Output;
+
+    fn get(&self, point: Point2<T>) -> Self::Output {
+        self.source.get(point)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (Self::Output, Self::Output)
+        where Self::Output: Float,
+    {
+        self.source.bounds()
+    }
+}
+
+impl<Source, T> NoiseModule<Point3<T>> for Typed<Source, T>
+    where Source: NoiseModule<Point3<T>>,
+          T: Copy,
+{
+    type Output = Source::Output;
+
+    fn get(&self, point: Point3<T>) -> Self::Output {
+        self.source.get(point)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (Self::Output, Self::Output)
+        where Self::Output: Float,
+    {
+        self.source.bounds()
+    }
+}
+
+impl<Source, T> NoiseModule<Point4<T>> for Typed<Source, T>
+    where Source: NoiseModule<Point4<T>>,
+          T: Copy,
+{
+    type Output = Source::Output;
+
+    fn get(&self, point: Point4<T>) -> Self::Output {
+        self.source.get(point)
+    }
+
+    fn complexity_estimate(&self) -> usize {
+        self.source.complexity_estimate()
+    }
+
+    fn bounds(&self) -> (Self::Output, Self::Output)
+        where Self::Output: Float,
+    {
+        self.source.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Typed;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_typed_matches_source() {
+        let perlin = Perlin::new(3);
+        let typed_f32 = Typed::<_, f32>::new(perlin);
+        let typed_f64 = Typed::<_, f64>::new(perlin);
+
+        assert_eq!(typed_f32.get([1.3, -0.7]), perlin.get([1.3f32, -0.7]));
+        assert_eq!(typed_f64.get([1.3, -0.7, 2.1]), perlin.get([1.3f64, -0.7, 2.1]));
+    }
+}
@@ -81,6 +81,11 @@ impl PermutationTable {
     /// Internally this uses a `XorShiftRng`, but we don't really need to worry
     /// about cryptographic security when working with procedural noise.
     ///
+    /// The seed is passed to the generator as-is, so nearby seeds produce
+    /// visibly correlated tables. This matters when layering noise seeded with
+    /// consecutive values, such as the octaves of a fractal. Use
+    /// `with_hashed_seed` to decorrelate nearby seeds.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -93,24 +98,49 @@ impl PermutationTable {
         rng.gen()
     }
 
+    /// Generates a new permutation table by shuffling with the given random
+    /// number generator.
+    pub fn from_rng<R: Rng>(rng: &mut R) -> PermutationTable {
+        rng.gen()
+    }
+
+    /// Deterministically generates a new permutation table based on a `u64`
+    /// seed value, which is first scrambled with the SplitMix64 finalizer.
+    ///
+    /// Unlike `new`, nearby seeds such as 0 and 1 produce unrelated tables.
+    /// The tables differ from those produced by `new` for the same seed.
+    pub fn with_hashed_seed(seed: u64) -> PermutationTable {
+        let a = math::splitmix64(seed);
+        let b = math::splitmix64(a);
+        // The generator requires a seed that is not all zeroes.
+        let mut rng: XorShiftRng =
+            SeedableRng::from_seed([a as u32 | 1, (a >> 32) as u32, b as u32, (b >> 32) as u32]);
+        PermutationTable::from_rng(&mut rng)
+    }
+
+    /// Returns the table entry for the given coordinate, wrapped to the size of
+    /// the table, i.e. `perm[x & 255]`.
     #[inline(always)]
     pub fn get1<T: Signed + PrimInt + NumCast>(&self, x: T) -> usize {
         let x: usize = math::cast(x & math::cast(0xff));
         self.values[x] as usize
     }
 
+    /// Hashes a 2-dimensional lattice point, as `perm[perm[x & 255] ^ (y & 255)]`.
     #[inline(always)]
     pub fn get2<T: Signed + PrimInt + NumCast>(&self, pos: math::Point2<T>) -> usize {
         let y: usize = math::cast(pos[1] & math::cast(0xff));
         self.values[self.get1(pos[0]) ^ y] as usize
     }
 
+    /// Hashes a 3-dimensional lattice point, as `perm[get2([x, y]) ^ (z & 255)]`.
     #[inline(always)]
     pub fn get3<T: Signed + PrimInt + NumCast>(&self, pos: math::Point3<T>) -> usize {
         let z: usize = math::cast(pos[2] & math::cast(0xff));
         self.values[self.get2([pos[0], pos[1]]) ^ z] as usize
     }
 
+    /// Hashes a 4-dimensional lattice point, as `perm[get3([x, y, z]) ^ (w & 255)]`.
     #[inline(always)]
     pub fn get4<T: Signed + PrimInt + NumCast>(&self, pos: math::Point4<T>) -> usize {
         let w: usize = math::cast(pos[3] & math::cast(0xff));
@@ -132,7 +162,7 @@ impl fmt::Debug for PermutationTable {
 
 #[cfg(test)]
 mod tests {
-    use rand::random;
+    use rand::{SeedableRng, XorShiftRng, random};
     use perlin::perlin3;
     use super::PermutationTable;
 
@@ -145,4 +175,21 @@ mod tests {
     fn test_negative_params() {
         let _ = perlin3::<f32>(&PermutationTable::new(0), &[-1.0, 2.0, 3.0]);
     }
+
+    #[test]
+    fn test_from_rng() {
+        let mut rng: XorShiftRng = SeedableRng::from_seed([1, 5, 5, 5]);
+        assert_eq!(PermutationTable::from_rng(&mut rng).values[..],
+                   PermutationTable::new(5).values[..]);
+    }
+
+    #[test]
+    fn test_hashed_seeds_are_decorrelated() {
+        for seed in 0..8 {
+            let a = PermutationTable::with_hashed_seed(seed);
+            let b = PermutationTable::with_hashed_seed(seed + 1);
+            let matches = a.values.iter().zip(b.values.iter()).filter(|&(x, y)| x == y).count();
+            assert!(matches < 8, "{} matching entries", matches);
+        }
+    }
 }
@@ -0,0 +1,57 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! SIMD kernels for the noise functions, enabled by the `simd` feature.
+//!
+//! SSE2 is part of the x86_64 baseline, so these need no runtime detection.
+
+use std::arch::x86_64::*;
+
+/// Sums sixteen 4-dimensional Perlin surflets, given the distance from each
+/// lattice corner to the sample point and the gradient at that corner.
+///
+/// Each surflet is `max(0, 1 - |d|²)⁴ * (d · g)`. The surflets are evaluated
+/// in pairs, one per lane of an SSE2 register.
+pub fn sum_surflets4(distances: &[[f64; 4]; 16], gradients: &[[f64; 4]; 16]) -> f64 {
+    // Safe because SSE2 is always available on x86_64, and the only memory
+    // access is the store into `lanes`, which has room for both lanes.
+    unsafe {
+        let zero = _mm_setzero_pd();
+        let one = _mm_set1_pd(1.0);
+        let mut sum = zero;
+
+        for pair in 0..8 {
+            let (d0, d1) = (distances[pair * 2], distances[pair * 2 + 1]);
+            let (g0, g1) = (gradients[pair * 2], gradients[pair * 2 + 1]);
+
+            let mut length = zero;
+            let mut dot = zero;
+            for axis in 0..4 {
+                let d = _mm_set_pd(d1[axis], d0[axis]);
+                let g = _mm_set_pd(g1[axis], g0[axis]);
+                length = _mm_add_pd(length, _mm_mul_pd(d, d));
+                dot = _mm_add_pd(dot, _mm_mul_pd(d, g));
+            }
+
+            let attn = _mm_max_pd(_mm_sub_pd(one, length), zero);
+            let attn2 = _mm_mul_pd(attn, attn);
+            sum = _mm_add_pd(sum, _mm_mul_pd(_mm_mul_pd(attn2, attn2), dot));
+        }
+
+        let mut lanes = [0.0; 2];
+        _mm_storeu_pd(lanes.as_mut_ptr(), sum);
+        lanes[0] + lanes[1]
+    }
+}
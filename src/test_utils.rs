@@ -0,0 +1,161 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Helpers shared by the crate's tests.
+
+use math::{Point2, Point3, Point4};
+use modules::{Periodic, Seedable};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::cell::Cell;
+use NoiseModule;
+
+/// Number of samples along each axis of the grid compared by
+/// `assert_modules_equal`.
+const GRID_SIZE: usize = 32;
+
+/// Module that outputs the value of its source, counting how often it is
+/// sampled.
+///
+/// Copies share nothing, so each counts its own samples. With the `serde`
+/// feature, only the source is serialized.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Counting<Source> {
+    pub source: Source,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    samples: Cell<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    batches: Cell<usize>,
+}
+
+impl<Source> Counting<Source> {
+    pub fn new(source: Source) -> Counting<Source> {
+        Counting {
+            source: source,
+            samples: Cell::new(0),
+            batches: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of points sampled, by `get` or `get_many`.
+    pub fn samples(&self) -> usize {
+        self.samples.get()
+    }
+
+    /// Returns the number of calls to `get_many`.
+    pub fn batches(&self) -> usize {
+        self.batches.get()
+    }
+}
+
+impl<Source: Seedable> Seedable for Counting<Source> {
+    fn set_seed(self, seed: usize) -> Counting<Source> {
+        Counting { source: self.source.set_seed(seed), ..self }
+    }
+
+    fn seed(&self) -> usize {
+        self.source.seed()
+    }
+}
+
+impl<Source: Periodic> Periodic for Counting<Source> {
+    fn set_period(self, period: Point4<usize>) -> Counting<Source> {
+        Counting { source: self.source.set_period(period), ..self }
+    }
+}
+
+impl<P, Source> NoiseModule<P> for Counting<Source>
+    where Source: NoiseModule<P>,
+{
+    type Output = Source::Output;
+
+    fn get(&self, point: P) -> Source::Output {
+        self.samples.set(self.samples.get() + 1);
+        self.source.get(point)
+    }
+
+    fn get_many(&self, points: &[P], out: &mut [Source::Output])
+        where P: Copy,
+    {
+        self.batches.set(self.batches.get() + 1);
+        self.samples.set(self.samples.get() + points.len());
+        self.source.get_many(points, out)
+    }
+}
+
+/// Module that outputs the sum of the coordinates of the point.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sum;
+
+impl NoiseModule<Point2<f64>> for Sum {
+    type Output = f64;
+
+    fn get(&self, point: Point2<f64>) -> f64 {
+        point[0] + point[1]
+    }
+}
+
+/// Samples both modules over a grid spanning the region between the two
+/// corners of `region`, and panics if their output values differ by more than
+/// `tolerance` anywhere.
+///
+/// This is used to check that refactorings preserve the output of a module.
+pub fn assert_modules_equal<A, B>(a: &A,
+                                  b: &B,
+                                  region: (Point2<f64>, Point2<f64>),
+                                  tolerance: f64)
+    where A: NoiseModule<Point2<f64>, Output = f64>,
+          B: NoiseModule<Point2<f64>, Output = f64>,
+{
+    let (lower, upper) = region;
+    let step = [(upper[0] - lower[0]) / (GRID_SIZE - 1) as f64,
+                (upper[1] - lower[1]) / (GRID_SIZE - 1) as f64];
+
+    for y in 0..GRID_SIZE {
+        for x in 0..GRID_SIZE {
+            let point = [lower[0] + step[0] * x as f64, lower[1] + step[1] * y as f64];
+            let (value_a, value_b) = (a.get(point), b.get(point));
+            assert!((value_a - value_b).abs() <= tolerance,
+                    "modules differ at {:?}: {} != {}",
+                    point,
+                    value_a,
+                    value_b);
+        }
+    }
+}
+
+/// Returns the `index`th point of a line that crosses lattice cells at uneven
+/// offsets, so that consecutive points land at different positions within
+/// their cells.
+fn sample_point(index: usize) -> Point4<f64> {
+    let i = index as f64;
+    [i * 0.37 - 3.0, i * 0.29 + 0.4, i * -0.13 + 0.6, i * 0.23 - 0.9]
+}
+
+/// Returns `count` 2-dimensional points for spot checks of module output.
+pub fn sample_points2(count: usize) -> Vec<Point2<f64>> {
+    (0..count).map(|i| { let p = sample_point(i); [p[0], p[1]] }).collect()
+}
+
+/// Returns `count` 3-dimensional points for spot checks of module output.
+pub fn sample_points3(count: usize) -> Vec<Point3<f64>> {
+    (0..count).map(|i| { let p = sample_point(i); [p[0], p[1], p[2]] }).collect()
+}
+
+/// Returns `count` 4-dimensional points for spot checks of module output.
+pub fn sample_points4(count: usize) -> Vec<Point4<f64>> {
+    (0..count).map(sample_point).collect()
+}
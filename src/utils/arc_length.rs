@@ -0,0 +1,98 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+
+/// Number of straight segments the curve is approximated with when measuring
+/// its length.
+const ARC_LENGTH_SEGMENTS: usize = 1024;
+
+/// Samples a module at roughly equal arc-length intervals along a parametric
+/// curve.
+///
+/// `curve` maps a parameter in [0, 1] to a point on the curve. The curve is
+/// walked from its start, and the module is sampled every `spacing` units of
+/// distance along it. The returned `Vec` holds each sampled point together
+/// with the module's output value there.
+///
+/// This is useful for placing detail along splines, such as roads or rivers.
+/// The arc length is measured by approximating the curve with many short
+/// straight segments, so the spacing is only approximate for sharply curved
+/// paths.
+pub fn sample_arc_length<M, F, T>(module: &M, curve: F, spacing: T) -> Vec<(Point2<T>, T)>
+    where M: NoiseModule<Point2<T>, Output = T>,
+          F: Fn(T) -> Point2<T>,
+          T: Float,
+{
+    let mut samples = Vec::new();
+    if spacing <= T::zero() {
+        return samples;
+    }
+
+    let segments: T = math::cast(ARC_LENGTH_SEGMENTS);
+    let mut previous = curve(T::zero());
+    let mut travelled = T::zero();
+    let mut next_sample = T::zero();
+
+    for i in 1..ARC_LENGTH_SEGMENTS + 1 {
+        let current = curve(math::cast::<_, T>(i) / segments);
+        let delta = math::sub2(current, previous);
+        let length = math::dot2(delta, delta).sqrt();
+
+        // Emit every sample that falls within this segment.
+        while next_sample <= travelled + length {
+            let alpha = if length > T::zero() {
+                (next_sample - travelled) / length
+            } else {
+                T::zero()
+            };
+            let point = math::add2(previous, math::mul2(delta, alpha));
+            samples.push((point, module.get(point)));
+            next_sample = next_sample + spacing;
+        }
+
+        travelled = travelled + length;
+        previous = current;
+    }
+
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample_arc_length;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_straight_line_spacing() {
+        let perlin = Perlin::new(0);
+        let samples = sample_arc_length(&perlin, |t: f64| [t * 30.0, t * 40.0], 2.5);
+
+        // The line is 50 units long, so there are samples at 0, 2.5, ..., 50.
+        assert_eq!(samples.len(), 21);
+        for pair in samples.windows(2) {
+            let (a, b) = (pair[0].0, pair[1].0);
+            let distance = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+            assert!((distance - 2.5).abs() < 1e-9);
+        }
+        for &(point, value) in samples.iter() {
+            assert_eq!(value, perlin.get(point));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use math::Point2;
+use modules::ScaleBias;
+use NoiseModule;
+
+/// Samples the module over a grid of `samples` by `samples` points spanning
+/// the region, including its corners, and returns the smallest and largest
+/// output values found, as `(min, max)`.
+///
+/// Unlike `NoiseModule::bounds`, this works for any module, including chains
+/// of modifiers and closures whose range can't be worked out analytically.
+/// The result is only as good as the sampling, though. The extremes of
+/// coherent noise are rare, so the measured range is almost always a little
+/// narrower than the true range, and it narrows further if the grid is too
+/// coarse to resolve the module's features. More samples and a larger region
+/// bring the measured range closer to the true range, at a cost proportional
+/// to the number of samples. Sampling each feature a few times over a region
+/// a few dozen features across is a reasonable starting point.
+///
+/// # Panics
+///
+/// Panics if `samples` is less than 2.
+pub fn measure_range<M>(module: &M,
+                        region: (Point2<f64>, Point2<f64>),
+                        samples: usize)
+                        -> (f64, f64)
+    where M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    assert!(samples >= 2, "at least 2 samples are needed along each axis");
+
+    let (lower, upper) = region;
+    let step = [(upper[0] - lower[0]) / (samples - 1) as f64,
+                (upper[1] - lower[1]) / (samples - 1) as f64];
+
+    let mut range = (::std::f64::INFINITY, ::std::f64::NEG_INFINITY);
+    for y in 0..samples {
+        for x in 0..samples {
+            let point = [lower[0] + step[0] * x as f64, lower[1] + step[1] * y as f64];
+            let value = module.get(point);
+            range = (range.0.min(value), range.1.max(value));
+        }
+    }
+    range
+}
+
+/// Measures the range of the module over the region with `measure_range`,
+/// and wraps the module in a `ScaleBias` that maps that range onto -1.0 to
+/// 1.0.
+///
+/// This is a quick fix for output that looks washed out or clipped when
+/// rendered. Since the measured range is usually slightly narrower than the
+/// true range, the output can slightly overshoot -1.0 to 1.0 away from the
+/// sampled points, so follow it with a `Clamp` if the range must be strict.
+/// A module with a constant output over the region is mapped to 0.0.
+///
+/// # Panics
+///
+/// Panics if `samples` is less than 2.
+pub fn calibrate<M>(module: M,
+                    region: (Point2<f64>, Point2<f64>),
+                    samples: usize)
+                    -> ScaleBias<M, f64>
+    where M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    let (min, max) = measure_range(&module, region, samples);
+    let (scale, bias) = if max > min {
+        (2.0 / (max - min), -(max + min) / (max - min))
+    } else {
+        (0.0, 0.0)
+    };
+    ScaleBias::new(module).set_scale(scale).set_bias(bias)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calibrate, measure_range};
+    use modules::{Constant, Fbm, NoiseModuleExt};
+    use NoiseModule;
+
+    #[test]
+    fn test_calibrate() {
+        let region = ([-4.0, -4.0], [4.0, 4.0]);
+        let module = Fbm::new().scale_bias(0.25, 3.0);
+        let (min, max) = measure_range(&module, region, 64);
+        assert!(min > 2.0 && max < 4.0 && min < max);
+
+        let calibrated = calibrate(module, region, 64);
+        let (min, max) = measure_range(&calibrated, region, 64);
+        assert!((min + 1.0).abs() < 1e-9 && (max - 1.0).abs() < 1e-9);
+
+        let constant = calibrate(Constant::new(0.7), region, 2);
+        assert_eq!(constant.get([0.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use NoiseModule;
+
+/// Returns whether the output value of the module at the given point lies
+/// within `threshold` of zero.
+///
+/// This is the primitive for tracing isolines, such as coastlines, without a
+/// full marching squares implementation.
+pub fn is_near_zero<M, P, T>(module: &M, point: P, threshold: T) -> bool
+    where M: NoiseModule<P, Output = T>,
+          T: Float,
+{
+    module.get(point).abs() < threshold
+}
+
+/// Samples the module over a `width` × `height` grid spanning the region
+/// between the two corners of `region`, and returns a row-major mask that is
+/// `true` wherever the output value lies within `threshold` of zero.
+pub fn near_zero_mask<M, T>(module: &M,
+                            region: (Point2<T>, Point2<T>),
+                            width: usize,
+                            height: usize,
+                            threshold: T)
+                            -> Vec<bool>
+    where M: NoiseModule<Point2<T>, Output = T>,
+          T: Float,
+{
+    let (lower, upper) = region;
+    let step = [(upper[0] - lower[0]) / math::cast(width.max(2) - 1),
+                (upper[1] - lower[1]) / math::cast(height.max(2) - 1)];
+
+    let mut mask = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let point = [lower[0] + step[0] * math::cast(x), lower[1] + step[1] * math::cast(y)];
+            mask.push(is_near_zero(module, point, threshold));
+        }
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::near_zero_mask;
+    use NoiseModule;
+
+    /// Outputs the x coordinate, so the zero line is the y axis.
+    struct LinearGradient;
+
+    impl NoiseModule<[f64; 2]> for LinearGradient {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn test_mask_only_near_zero_line() {
+        let mask = near_zero_mask(&LinearGradient, ([-1.0, -1.0], [1.0, 1.0]), 21, 21, 0.15);
+        for y in 0..21 {
+            for x in 0..21 {
+                let near = (x as f64 * 0.1 - 1.0).abs() < 0.15;
+                assert_eq!(mask[y * 21 + x], near);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::Point3;
+use std::f64::consts::PI;
+use super::NoiseMap;
+use NoiseModule;
+
+/// Generates noise maps that tile horizontally, by sampling around the
+/// surface of a cylinder.
+///
+/// The map wraps seamlessly from its right edge back to its left, while its
+/// vertical axis runs linearly along the cylinder. This suits textures that
+/// only need to tile in one direction, such as terrain strips and skyboxes.
+#[derive(Clone, Copy, Debug)]
+pub struct CylinderMap;
+
+impl CylinderMap {
+    /// Samples the module around a cylinder of radius 1 into a `width` ×
+    /// `height` map.
+    ///
+    /// Column _x_ lies at the angle `a = 2π * x / width` around the _y_ axis,
+    /// and row _y_ at the height `h = 2π * y / width`, so the point sampled
+    /// is `[cos(a), h, sin(a)]`. Neighbouring pixels are therefore about
+    /// `2π / width` apart in both directions, and a module with a frequency
+    /// of _f_ repeats its features about `2π * f` times around the cylinder.
+    pub fn generate<M>(module: &M, width: usize, height: usize) -> NoiseMap
+        where M: NoiseModule<Point3<f64>, Output = f64>,
+    {
+        let step = 2.0 * PI / width as f64;
+
+        let mut map = NoiseMap::new(width, height);
+        for (y, row) in map.rows_mut().enumerate() {
+            let h = y as f64 * step;
+            for (x, value) in row.iter_mut().enumerate() {
+                let angle = x as f64 * step;
+                *value = module.get([angle.cos(), h, angle.sin()]);
+            }
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CylinderMap;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_mapping() {
+        struct Point;
+
+        impl NoiseModule<[f64; 3]> for Point {
+            type Output = f64;
+
+            fn get(&self, point: [f64; 3]) -> f64 {
+                point[0] + point[1] * 10.0 + point[2] * 100.0
+            }
+        }
+
+        let map = CylinderMap::generate(&Point, 4, 3);
+        let step = ::std::f64::consts::PI / 2.0;
+        assert!((map.get_value(0, 0) - 1.0).abs() < 1e-12);
+        assert!((map.get_value(1, 0) - 100.0).abs() < 1e-12);
+        assert!((map.get_value(2, 2) - (-1.0 + 20.0 * step)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_wraps_horizontally() {
+        let module = Perlin::new(0);
+        let map = CylinderMap::generate(&module, 256, 32);
+        for y in 0..32 {
+            // The first column sits at angle zero, where the last one wraps to.
+            let h = y as f64 * 2.0 * ::std::f64::consts::PI / 256.0;
+            assert!((map.get_value(0, y) - module.get([1.0, h, 0.0])).abs() < 1e-12);
+            assert!((map.get_value(0, y) - map.get_value(255, y)).abs() < 0.1);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::Point2;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::PathBuf;
+use super::NoiseMap;
+use NoiseModule;
+
+/// Stores baked noise maps in a directory, so that expensive module graphs
+/// only need to be sampled once across runs.
+///
+/// Each map is stored in a file named after a hash of the serialized
+/// parameters of its module, together with the region and size it was
+/// sampled at. Changing any of them bakes a new map.
+///
+/// Requires the `disk_cache` feature.
+#[derive(Clone, Debug)]
+pub struct DiskCache {
+    /// Directory that the maps are stored in.
+    pub dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Creates a cache that stores its maps in `dir`. The directory is
+    /// created when the first map is stored.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> DiskCache {
+        DiskCache { dir: dir.into() }
+    }
+
+    /// Returns the map of the module sampled as by `NoiseMap::generate`,
+    /// loading it from the cache if it has been stored, and otherwise
+    /// generating and storing it.
+    ///
+    /// `name` is included in the key, to tell apart different module types
+    /// whose parameters serialize identically. A stored map that can't be
+    /// read is generated again and overwritten.
+    pub fn get_or_build<M>(&self,
+                           name: &str,
+                           module: &M,
+                           bounds: (Point2<f64>, Point2<f64>),
+                           width: usize,
+                           height: usize)
+                           -> io::Result<NoiseMap>
+        where M: NoiseModule<Point2<f64>, Output = f64> + Serialize,
+    {
+        let params = serde_json::to_string(&(module, bounds, width, height))?;
+        let path = self.dir.join(format!("{}-{:016x}.json", name, fnv1a(params.as_bytes())));
+
+        if let Ok(file) = File::open(&path) {
+            if let Ok(stored) = serde_json::from_reader(BufReader::new(file)) {
+                if let Some(map) = StoredMap::into_map(stored) {
+                    return Ok(map);
+                }
+            }
+        }
+
+        let map = NoiseMap::generate(module, bounds, width, height);
+        fs::create_dir_all(&self.dir)?;
+        let stored = StoredMap::from_map(&map);
+        serde_json::to_writer(BufWriter::new(File::create(&path)?), &stored)?;
+        Ok(map)
+    }
+}
+
+/// A noise map as it is stored on disk.
+///
+/// The values are stored as the bits of each `f64`, since JSON numbers can't
+/// represent NaN or the infinities, and are not guaranteed to parse back to
+/// the exact value that was written.
+#[derive(Serialize, Deserialize)]
+struct StoredMap {
+    width: usize,
+    height: usize,
+    values: Vec<u64>,
+}
+
+impl StoredMap {
+    fn from_map(map: &NoiseMap) -> StoredMap {
+        StoredMap {
+            width: map.width(),
+            height: map.height(),
+            values: map.values().iter().map(|value| value.to_bits()).collect(),
+        }
+    }
+
+    /// Returns the stored map, or `None` if the number of values doesn't
+    /// match its size.
+    fn into_map(self) -> Option<NoiseMap> {
+        if self.values.len() != self.width * self.height {
+            return None;
+        }
+        let mut map = NoiseMap::new(self.width, self.height);
+        for (value, &bits) in map.rows_mut().flat_map(|row| row.iter_mut()).zip(&self.values) {
+            *value = f64::from_bits(bits);
+        }
+        Some(map)
+    }
+}
+
+/// 64-bit FNV-1a hash of the bytes. Unlike the standard library's hashers,
+/// this is guaranteed to be stable across Rust releases, so the keys of
+/// stored maps stay valid.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiskCache;
+    use math::Point2;
+    use serde::Serialize;
+    use std::env;
+    use std::f64;
+    use std::fs;
+    use test_utils::Counting;
+    use utils::NoiseMap;
+    use NoiseModule;
+
+    /// Outputs values that have no short decimal representation, and NaN for
+    /// `x` above 0.9.
+    #[derive(Serialize)]
+    struct Wave {
+        slope: f64,
+    }
+
+    impl NoiseModule<Point2<f64>> for Wave {
+        type Output = f64;
+
+        fn get(&self, point: Point2<f64>) -> f64 {
+            if point[0] > 0.9 {
+                return f64::NAN;
+            }
+            (point[0] * self.slope + point[1]).sin() / 3.0
+        }
+    }
+
+    fn bits(map: &NoiseMap) -> Vec<u64> {
+        map.values().iter().map(|value| value.to_bits()).collect()
+    }
+
+    #[test]
+    fn test_get_or_build() {
+        let dir = env::temp_dir().join("noise_test_disk_cache");
+        let _ = fs::remove_dir_all(&dir);
+        let cache = DiskCache::new(dir.clone());
+        let bounds = ([0.0, 0.0], [1.0, 1.0]);
+
+        let module = Counting::new(Wave { slope: 0.5 });
+        let built = cache.get_or_build("wave", &module, bounds, 8, 4).unwrap();
+        assert_eq!(module.samples(), 32);
+
+        // The stored map is loaded back exactly, including NaN values.
+        let loaded = cache.get_or_build("wave", &module, bounds, 8, 4).unwrap();
+        assert_eq!(module.samples(), 32);
+        assert!(built.values().iter().any(|value| value.is_nan()));
+        assert_eq!(bits(&loaded), bits(&built));
+
+        // Changing the parameters or the region builds a new map.
+        let steeper = Counting::new(Wave { slope: 0.75 });
+        cache.get_or_build("wave", &steeper, bounds, 8, 4).unwrap();
+        assert_eq!(steeper.samples(), 32);
+        cache.get_or_build("wave", &module, bounds, 4, 4).unwrap();
+        assert_eq!(module.samples(), 48);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
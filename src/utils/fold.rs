@@ -0,0 +1,91 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point2;
+use modules::Turbulence;
+
+/// Estimates how much of a region is folded onto itself by a `Turbulence`
+/// module.
+///
+/// Strong turbulence can displace neighbouring points past each other, which
+/// shows up as visible pinching in the output. This samples a
+/// `samples` × `samples` grid over the region between the two corners of
+/// `region`, estimates the Jacobian of the warp at each sample by finite
+/// differences, and returns the fraction of samples where its determinant is
+/// negative.
+///
+/// A result of zero means no folding was detected, which makes this useful
+/// for picking a safe turbulence power.
+pub fn domain_fold_score<Source, T>(turbulence: &Turbulence<Source, T>,
+                                    region: (Point2<T>, Point2<T>),
+                                    samples: usize)
+                                    -> T
+    where T: Float,
+{
+    if samples == 0 {
+        return T::zero();
+    }
+
+    let (lower, upper) = region;
+    let count: T = math::cast(samples);
+    let step = [(upper[0] - lower[0]) / count, (upper[1] - lower[1]) / count];
+
+    // Use a difference step well below the sample spacing, so that the
+    // estimates don't straddle neighbouring samples.
+    let delta: T = math::cast(1.0e-4);
+
+    let mut folded = 0;
+    for y in 0..samples {
+        for x in 0..samples {
+            let point = [lower[0] + step[0] * (math::cast::<_, T>(x) + math::cast(0.5)),
+                         lower[1] + step[1] * (math::cast::<_, T>(y) + math::cast(0.5))];
+
+            let center = turbulence.warp2(point);
+            let dx = math::sub2(turbulence.warp2([point[0] + delta, point[1]]), center);
+            let dy = math::sub2(turbulence.warp2([point[0], point[1] + delta]), center);
+
+            let determinant = dx[0] * dy[1] - dx[1] * dy[0];
+            if determinant < T::zero() {
+                folded += 1;
+            }
+        }
+    }
+
+    math::cast::<_, T>(folded) / (count * count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::domain_fold_score;
+    use modules::{Constant, Turbulence};
+
+    #[test]
+    fn test_zero_power_does_not_fold() {
+        let turbulence = Turbulence::new(Constant::new(0.0)).set_power(0.0);
+        let score = domain_fold_score(&turbulence, ([-4.0, -4.0], [4.0, 4.0]), 32);
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn test_strong_power_folds() {
+        let turbulence = Turbulence::new(Constant::new(0.0))
+            .set_frequency(4.0)
+            .set_power(2.0);
+        let score = domain_fold_score(&turbulence, ([-4.0, -4.0], [4.0, 4.0]), 32);
+        assert!(score > 0.0);
+    }
+}
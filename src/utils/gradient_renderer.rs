@@ -0,0 +1,387 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::{self, Point2, Vector3};
+#[cfg(feature = "image")]
+use std::io;
+#[cfg(feature = "image")]
+use std::path::Path;
+use super::NoiseMap;
+use NoiseModule;
+
+#[cfg(feature = "image")]
+use image;
+
+/// Renders noise through a color gradient, producing an RGBA pixel buffer.
+///
+/// The gradient is defined by control points, each mapping a noise value to
+/// an `[r, g, b, a]` color. Values between two control points are blended
+/// linearly in each channel, and values beyond the outermost control points
+/// take the color of the nearest one.
+///
+/// Optionally, the colors can be hillshaded by a directional light, which
+/// makes the relief of the noise readable when it is treated as a heightfield,
+/// and cut out below an alpha threshold, for sprites and decals that are ready
+/// to composite.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::modules::Perlin;
+/// use noise::utils::GradientRenderer;
+///
+/// let terrain = GradientRenderer::new()
+///     .add_gradient_point(-1.0, [0, 0, 128, 255])
+///     .add_gradient_point(-0.05, [0, 96, 192, 255])
+///     .add_gradient_point(0.0, [224, 208, 128, 255])
+///     .add_gradient_point(0.1, [32, 160, 0, 255])
+///     .add_gradient_point(0.5, [128, 128, 128, 255])
+///     .add_gradient_point(1.0, [255, 255, 255, 255])
+///     .enable_light([-1.0, 1.0, 1.0], 0.75);
+///
+/// let pixels = terrain.render(&Perlin::new(0), 64, 32, ([0.0, 0.0], [4.0, 2.0]));
+/// assert_eq!(pixels.len(), 64 * 32 * 4);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GradientRenderer {
+    /// Control points, sorted by value.
+    points: Vec<(f64, [u8; 4])>,
+
+    /// Normalized direction towards the light, and its intensity.
+    light: Option<(Vector3<f64>, f64)>,
+
+    /// Value below which pixels are fully transparent. See
+    /// `enable_alpha_threshold`.
+    alpha_threshold: Option<f64>,
+}
+
+impl GradientRenderer {
+    /// Creates a renderer with no control points.
+    pub fn new() -> GradientRenderer {
+        GradientRenderer {
+            points: Vec::new(),
+            light: None,
+            alpha_threshold: None,
+        }
+    }
+
+    /// Adds a control point mapping `value` to `color`. If a control point
+    /// already exists at `value`, its color is replaced.
+    pub fn add_gradient_point(mut self, value: f64, color: [u8; 4]) -> GradientRenderer {
+        match self.points.iter().position(|&(v, _)| v >= value) {
+            Some(index) if self.points[index].0 == value => self.points[index].1 = color,
+            Some(index) => self.points.insert(index, (value, color)),
+            None => self.points.push((value, color)),
+        }
+        self
+    }
+
+    /// Removes all control points.
+    pub fn clear_gradient(self) -> GradientRenderer {
+        GradientRenderer { points: Vec::new(), ..self }
+    }
+
+    /// Enables hillshading by a light shining from `direction`, which points
+    /// from the surface towards the light, with _z_ pointing up out of the
+    /// map.
+    ///
+    /// The normal of each pixel is estimated from the differences between its
+    /// neighbours, and its color is scaled by
+    /// `1 - intensity + intensity * max(0, normal · direction)`, so an
+    /// `intensity` of 0 leaves the colors unchanged and an intensity of 1
+    /// turns surfaces facing away from the light black. Alpha is never
+    /// shaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `direction` is the zero vector.
+    pub fn enable_light(self, direction: Vector3<f64>, intensity: f64) -> GradientRenderer {
+        let length = math::dot3(direction, direction).sqrt();
+        assert!(length > 0.0, "the light direction must not be zero");
+
+        GradientRenderer { light: Some((math::mul3(direction, length.recip()), intensity)), ..self }
+    }
+
+    /// Disables hillshading.
+    pub fn disable_light(self) -> GradientRenderer {
+        GradientRenderer { light: None, ..self }
+    }
+
+    /// Makes the pixels of values below `threshold` fully transparent, and
+    /// premultiplies the colors of the other pixels by their alpha, so the
+    /// output can be composited directly.
+    ///
+    /// The alpha of the remaining pixels is taken from the gradient, so a
+    /// gradient that is opaque everywhere cuts out a hard-edged shape.
+    pub fn enable_alpha_threshold(self, threshold: f64) -> GradientRenderer {
+        GradientRenderer { alpha_threshold: Some(threshold), ..self }
+    }
+
+    /// Disables the alpha threshold, and with it the premultiplied output.
+    pub fn disable_alpha_threshold(self) -> GradientRenderer {
+        GradientRenderer { alpha_threshold: None, ..self }
+    }
+
+    /// Returns the color the gradient maps `value` to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gradient has no control points.
+    pub fn get_color(&self, value: f64) -> [u8; 4] {
+        assert!(!self.points.is_empty(), "the gradient has no control points");
+
+        let index = self.points
+            .iter()
+            .position(|&(v, _)| v >= value)
+            .unwrap_or(self.points.len());
+
+        if index == 0 {
+            return self.points[0].1;
+        }
+        if index == self.points.len() {
+            return self.points[index - 1].1;
+        }
+
+        let (value0, color0) = self.points[index - 1];
+        let (value1, color1) = self.points[index];
+        let alpha = (value - value0) / (value1 - value0);
+
+        let mut color = [0; 4];
+        for (channel, (&c0, &c1)) in color.iter_mut().zip(color0.iter().zip(color1.iter())) {
+            *channel = (c0 as f64 + (c1 as f64 - c0 as f64) * alpha).round() as u8;
+        }
+        color
+    }
+
+    /// Samples the module over a `width` × `height` grid spanning `bounds` and
+    /// returns the colors of the samples as RGBA bytes in row-major order.
+    ///
+    /// When hillshading, the slopes are measured in the units of `bounds`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gradient has no control points.
+    pub fn render<M>(&self,
+                     module: &M,
+                     width: usize,
+                     height: usize,
+                     bounds: (Point2<f64>, Point2<f64>))
+                     -> Vec<u8>
+        where M: NoiseModule<Point2<f64>, Output = f64>,
+    {
+        let (lower, upper) = bounds;
+        let spacing = [(upper[0] - lower[0]) / (width.max(2) - 1) as f64,
+                       (upper[1] - lower[1]) / (height.max(2) - 1) as f64];
+
+        self.render_spaced(&NoiseMap::generate(module, bounds, width, height), spacing)
+    }
+
+    /// Samples the module as by `render` and writes the result to `path` as
+    /// an 8-bit RGBA PNG.
+    ///
+    /// Requires the `image` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gradient has no control points.
+    #[cfg(feature = "image")]
+    pub fn write_image<P, M>(&self,
+                             path: P,
+                             module: &M,
+                             width: u32,
+                             height: u32,
+                             bounds: (Point2<f64>, Point2<f64>))
+                             -> io::Result<()>
+        where P: AsRef<Path>,
+              M: NoiseModule<Point2<f64>, Output = f64>,
+    {
+        let pixels = self.render(module, width as usize, height as usize, bounds);
+        image::save_buffer(path.as_ref(), &pixels, width, height, image::RGBA(8))
+    }
+
+    /// Returns the colors of the values in `map` as RGBA bytes in row-major
+    /// order.
+    ///
+    /// When hillshading, neighbouring values are taken to be one unit apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gradient has no control points.
+    pub fn render_map(&self, map: &NoiseMap) -> Vec<u8> {
+        self.render_spaced(map, [1.0, 1.0])
+    }
+
+    fn render_spaced(&self, map: &NoiseMap, spacing: [f64; 2]) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(map.values().len() * 4);
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let value = map.get_value(x, y);
+                if self.alpha_threshold.map_or(false, |threshold| value < threshold) {
+                    pixels.extend_from_slice(&[0; 4]);
+                    continue;
+                }
+
+                let mut color = self.get_color(value);
+                if let Some((direction, intensity)) = self.light {
+                    let normal = surface_normal(map, x, y, spacing);
+                    let lit = math::dot3(normal, direction).max(0.0);
+                    let factor = 1.0 - intensity + intensity * lit;
+                    for channel in color.iter_mut().take(3) {
+                        *channel = (*channel as f64 * factor).round().max(0.0).min(255.0) as u8;
+                    }
+                }
+                if self.alpha_threshold.is_some() {
+                    let alpha = color[3] as f64 / 255.0;
+                    for channel in color.iter_mut().take(3) {
+                        *channel = (*channel as f64 * alpha).round() as u8;
+                    }
+                }
+                pixels.extend_from_slice(&color);
+            }
+        }
+        pixels
+    }
+}
+
+/// Estimates the unit normal of the map at the given position from the
+/// differences between its neighbours, falling back to one-sided differences
+/// at the edges. Maps one value wide or high are flat along that axis.
+fn surface_normal(map: &NoiseMap, x: usize, y: usize, spacing: [f64; 2]) -> Vector3<f64> {
+    let (x0, x1) = (x.saturating_sub(1), (x + 1).min(map.width() - 1));
+    let (y0, y1) = (y.saturating_sub(1), (y + 1).min(map.height() - 1));
+
+    let mut dx = map.get_value(x1, y) - map.get_value(x0, y);
+    if x1 > x0 {
+        dx /= (x1 - x0) as f64 * spacing[0];
+    }
+    let mut dy = map.get_value(x, y1) - map.get_value(x, y0);
+    if y1 > y0 {
+        dy /= (y1 - y0) as f64 * spacing[1];
+    }
+
+    let normal = [-dx, -dy, 1.0];
+    math::mul3(normal, math::dot3(normal, normal).sqrt().recip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GradientRenderer;
+    use modules::Constant;
+    use utils::NoiseMap;
+
+    #[test]
+    fn test_get_color() {
+        let gradient = GradientRenderer::new()
+            .add_gradient_point(1.0, [255, 255, 255, 255])
+            .add_gradient_point(-1.0, [0, 0, 0, 255])
+            .add_gradient_point(0.0, [200, 100, 0, 0]);
+
+        assert_eq!(gradient.get_color(-2.0), [0, 0, 0, 255]);
+        assert_eq!(gradient.get_color(-0.5), [100, 50, 0, 128]);
+        assert_eq!(gradient.get_color(0.0), [200, 100, 0, 0]);
+        assert_eq!(gradient.get_color(0.5), [228, 178, 128, 128]);
+        assert_eq!(gradient.get_color(3.0), [255, 255, 255, 255]);
+
+        let gradient = gradient.add_gradient_point(0.0, [10, 20, 30, 40]);
+        assert_eq!(gradient.get_color(0.0), [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_render() {
+        let gradient = GradientRenderer::new()
+            .add_gradient_point(0.0, [0, 0, 0, 0])
+            .add_gradient_point(1.0, [255, 0, 0, 255]);
+
+        let pixels = gradient.render(&Constant::new(1.0), 3, 2, ([0.0, 0.0], [1.0, 1.0]));
+        assert_eq!(pixels, [255, 0, 0, 255].iter().cycle().take(24).cloned().collect::<Vec<_>>());
+
+        let mut map = NoiseMap::new(2, 1);
+        map.set_value(1, 0, 0.5);
+        assert_eq!(gradient.render_map(&map), vec![0, 0, 0, 0, 128, 0, 0, 128]);
+    }
+
+    #[test]
+    fn test_hillshading() {
+        let white = GradientRenderer::new().add_gradient_point(0.0, [255, 255, 255, 255]);
+
+        // A flat map lit from straight above is unchanged.
+        let overhead = white.clone().enable_light([0.0, 0.0, 2.0], 1.0);
+        let pixels = overhead.render(&Constant::new(0.5), 3, 3, ([0.0, 0.0], [1.0, 1.0]));
+        assert!(pixels.iter().all(|&channel| channel == 255));
+
+        // A plane rising along x with a slope of 1.
+        let mut map = NoiseMap::new(3, 2);
+        for y in 0..2 {
+            for x in 0..3 {
+                map.set_value(x, y, x as f64);
+            }
+        }
+
+        let shaded = overhead.render_map(&map);
+        let expected = (255.0 * 0.5f64.sqrt()).round() as u8;
+        assert_eq!(&shaded[..4], &[expected, expected, expected, 255]);
+
+        let facing = white.clone().enable_light([-1.0, 0.0, 1.0], 1.0).render_map(&map);
+        assert!(facing.iter().all(|&channel| channel == 255));
+
+        let away = white.clone().enable_light([1.0, 0.0, 0.0], 0.5).render_map(&map);
+        assert_eq!(&away[..4], &[128, 128, 128, 255]);
+
+        assert_eq!(overhead.disable_light().render_map(&map), white.render_map(&map));
+    }
+
+    #[test]
+    fn test_alpha_threshold() {
+        let gradient = GradientRenderer::new()
+            .add_gradient_point(0.0, [200, 100, 50, 255])
+            .add_gradient_point(1.0, [200, 100, 50, 128])
+            .enable_alpha_threshold(0.0);
+
+        let mut map = NoiseMap::new(4, 1);
+        map.set_value(0, 0, -1.0);
+        map.set_value(1, 0, -0.01);
+        map.set_value(2, 0, 0.0);
+        map.set_value(3, 0, 1.0);
+        assert_eq!(gradient.render_map(&map),
+                   vec![0, 0, 0, 0, 0, 0, 0, 0, 200, 100, 50, 255, 100, 50, 25, 128]);
+
+        let pixels = gradient.render(&Constant::new(-0.5), 3, 2, ([0.0, 0.0], [1.0, 1.0]));
+        assert!(pixels.iter().all(|&channel| channel == 0));
+
+        let gradient = gradient.disable_alpha_threshold();
+        assert_eq!(&gradient.render_map(&map)[..4], &[200, 100, 50, 255]);
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_write_image() {
+        use std::env;
+        use std::fs;
+
+        let gradient = GradientRenderer::new()
+            .add_gradient_point(0.0, [255, 255, 255, 255])
+            .enable_alpha_threshold(0.0);
+        let path = env::temp_dir().join("noise_test_gradient_write_image.png");
+        gradient.write_image(&path, &Constant::new(0.5), 16, 8, ([0.0, 0.0], [2.0, 1.0])).unwrap();
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_gradient() {
+        GradientRenderer::new().clear_gradient().get_color(0.0);
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use math;
+use math::Point3;
+use NoiseModule;
+
+/// The six tetrahedra that a cube is split into, as indices of its corners.
+/// Bit 0 of a corner index is its x offset, bit 1 its y offset and bit 2 its
+/// z offset. Every tetrahedron shares the diagonal from corner 0 to corner 7,
+/// so the faces of neighbouring cubes line up and the mesh has no cracks.
+const TETRAHEDRA: [[usize; 4]; 6] = [[0, 1, 3, 7],
+                                     [0, 1, 5, 7],
+                                     [0, 2, 3, 7],
+                                     [0, 2, 6, 7],
+                                     [0, 4, 5, 7],
+                                     [0, 4, 6, 7]];
+
+/// Samples the module over a grid spanning the box between the two corners of
+/// `bounds`, and returns the triangles of the surface where the output value
+/// equals `iso_level`.
+///
+/// The box is divided into `resolution` cells along each axis. Each cell is
+/// split into six tetrahedra, and the surface is extracted from each
+/// tetrahedron separately, which needs far smaller case tables than classic
+/// marching cubes and never produces ambiguous cases. Vertices are placed by
+/// linear interpolation along the cell edges. Triangles are wound so that
+/// their normals, by the right-hand rule, point towards higher output values.
+pub fn march_cubes<M, T>(module: &M,
+                         bounds: (Point3<T>, Point3<T>),
+                         resolution: usize,
+                         iso_level: T)
+                         -> Vec<[Point3<f32>; 3]>
+    where M: NoiseModule<Point3<T>, Output = T>,
+          T: Float,
+{
+    let (lower, upper) = bounds;
+    let resolution = resolution.max(1);
+    let cells: T = math::cast(resolution);
+    let grid = Grid {
+        lower: lower,
+        step: math::mul3(math::sub3(upper, lower), T::one() / cells),
+        side: resolution + 1,
+        samples: Vec::new(),
+    };
+
+    let mut samples = Vec::with_capacity(grid.side * grid.side * grid.side);
+    for index in 0..grid.side * grid.side * grid.side {
+        samples.push(module.get(grid.position(index)));
+    }
+    let grid = Grid { samples: samples, ..grid };
+
+    let mut triangles = Vec::new();
+    for z in 0..resolution {
+        for y in 0..resolution {
+            for x in 0..resolution {
+                let mut corners = [0; 8];
+                for (i, corner) in corners.iter_mut().enumerate() {
+                    *corner = grid.index(x + (i & 1), y + ((i >> 1) & 1), z + ((i >> 2) & 1));
+                }
+
+                for tetrahedron in TETRAHEDRA.iter() {
+                    let vertices = [corners[tetrahedron[0]],
+                                    corners[tetrahedron[1]],
+                                    corners[tetrahedron[2]],
+                                    corners[tetrahedron[3]]];
+                    grid.polygonise(vertices, iso_level, &mut triangles);
+                }
+            }
+        }
+    }
+    triangles
+}
+
+/// Output values sampled at the corners of a regular grid of cells.
+struct Grid<T> {
+    lower: Point3<T>,
+    step: Point3<T>,
+    side: usize,
+    samples: Vec<T>,
+}
+
+impl<T: Float> Grid<T> {
+    fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.side + y) * self.side + x
+    }
+
+    fn position(&self, index: usize) -> Point3<T> {
+        let x = index % self.side;
+        let y = index / self.side % self.side;
+        let z = index / (self.side * self.side);
+        [self.lower[0] + self.step[0] * math::cast(x),
+         self.lower[1] + self.step[1] * math::cast(y),
+         self.lower[2] + self.step[2] * math::cast(z)]
+    }
+
+    /// Returns the point where the surface crosses the edge between two grid
+    /// points. The endpoints are put in a fixed order first, so every
+    /// tetrahedron sharing the edge computes exactly the same point.
+    fn edge_vertex(&self, a: usize, b: usize, iso_level: T) -> Point3<f32> {
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        let (value_a, value_b) = (self.samples[a], self.samples[b]);
+        let t = (iso_level - value_a) / (value_b - value_a);
+
+        let position_a = self.position(a);
+        let delta = math::sub3(self.position(b), position_a);
+        math::cast3(math::add3(position_a, math::mul3(delta, t)))
+    }
+
+    /// Appends the triangles of the surface within one tetrahedron.
+    fn polygonise(&self,
+                  vertices: [usize; 4],
+                  iso_level: T,
+                  triangles: &mut Vec<[Point3<f32>; 3]>) {
+        let mut below = Vec::with_capacity(4);
+        let mut above = Vec::with_capacity(4);
+        for &vertex in vertices.iter() {
+            if self.samples[vertex] < iso_level {
+                below.push(vertex);
+            } else {
+                above.push(vertex);
+            }
+        }
+
+        let edge = |a, b| self.edge_vertex(a, b, iso_level);
+        let new_triangles = match below.len() {
+            1 => {
+                let lone = below[0];
+                vec![[edge(lone, above[0]), edge(lone, above[1]), edge(lone, above[2])]]
+            },
+            3 => {
+                let lone = above[0];
+                vec![[edge(lone, below[0]), edge(lone, below[1]), edge(lone, below[2])]]
+            },
+            2 => {
+                let quad = [edge(below[0], above[0]),
+                            edge(below[0], above[1]),
+                            edge(below[1], above[1]),
+                            edge(below[1], above[0])];
+                vec![[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]]
+            },
+            _ => return,
+        };
+
+        // Wind each triangle so its normal points from the corners below the
+        // iso level towards the corners above it.
+        let centroid = |vertices: &[usize]| {
+            let sum = vertices.iter()
+                .map(|&vertex| math::cast3::<T, f32>(self.position(vertex)))
+                .fold([0.0; 3], math::add3);
+            math::mul3(sum, 1.0 / vertices.len() as f32)
+        };
+        let direction = math::sub3(centroid(&above), centroid(&below));
+
+        for triangle in new_triangles {
+            if math::dot3(normal(&triangle), direction) < 0.0 {
+                triangles.push([triangle[0], triangle[2], triangle[1]]);
+            } else {
+                triangles.push(triangle);
+            }
+        }
+    }
+}
+
+fn normal(triangle: &[Point3<f32>; 3]) -> Point3<f32> {
+    let u = math::sub3(triangle[1], triangle[0]);
+    let v = math::sub3(triangle[2], triangle[0]);
+    [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{march_cubes, normal};
+    use math;
+    use std::collections::HashMap;
+    use NoiseModule;
+
+    /// Outputs the distance from the origin, so each iso-surface is a sphere.
+    struct SphereDensity;
+
+    impl NoiseModule<[f64; 3]> for SphereDensity {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 3]) -> f64 {
+            math::dot3(point, point).sqrt()
+        }
+    }
+
+    #[test]
+    fn test_sphere() {
+        let triangles = march_cubes(&SphereDensity, ([-1.0; 3], [1.0; 3]), 16, 0.6);
+        assert!(triangles.len() > 500 && triangles.len() < 20000);
+
+        let mut edges = HashMap::new();
+        for triangle in triangles.iter() {
+            for vertex in triangle.iter() {
+                let radius = math::dot3(*vertex, *vertex).sqrt();
+                assert!((radius - 0.6).abs() < 0.05);
+            }
+
+            // Normals point outwards, towards higher distances.
+            let sum = math::add3(math::add3(triangle[0], triangle[1]), triangle[2]);
+            let centroid = math::mul3(sum, 1.0 / 3.0);
+            assert!(math::dot3(normal(triangle), centroid) > 0.0);
+
+            for i in 0..3 {
+                let a = math::map3(triangle[i], f32::to_bits);
+                let b = math::map3(triangle[(i + 1) % 3], f32::to_bits);
+                *edges.entry(if a < b { (a, b) } else { (b, a) }).or_insert(0) += 1;
+            }
+        }
+
+        // Every edge is shared by exactly two triangles, so the mesh is closed.
+        assert!(edges.values().all(|&count| count == 2));
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Utilities for inspecting and working with noise modules.
+
+pub use self::arc_length::*;
+pub use self::calibrate::*;
+pub use self::contour::*;
+pub use self::cylinder_map::*;
+#[cfg(feature = "disk_cache")]
+pub use self::disk_cache::*;
+pub use self::fold::*;
+pub use self::gradient_renderer::*;
+pub use self::march_cubes::*;
+pub use self::noise_map::*;
+#[cfg(feature = "image")]
+pub use self::render::*;
+pub use self::seed_argmax::*;
+pub use self::sphere_map::*;
+pub use self::spectrum::*;
+
+mod arc_length;
+mod calibrate;
+mod contour;
+mod cylinder_map;
+#[cfg(feature = "disk_cache")]
+mod disk_cache;
+mod fold;
+mod gradient_renderer;
+mod march_cubes;
+mod noise_map;
+#[cfg(feature = "image")]
+mod render;
+mod seed_argmax;
+mod sphere_map;
+mod spectrum;
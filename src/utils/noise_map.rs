@@ -0,0 +1,232 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::Point2;
+use NoiseModule;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::slice::{Chunks, ChunksMut};
+
+/// A grid of output values sampled from a noise module.
+///
+/// Values are stored in row-major order, starting from the row at the lower
+/// _y_ bound.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NoiseMap {
+    width: usize,
+    height: usize,
+    values: Vec<f64>,
+}
+
+impl NoiseMap {
+    /// Creates a map of the given size, filled with zeros.
+    pub fn new(width: usize, height: usize) -> NoiseMap {
+        NoiseMap {
+            width: width,
+            height: height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    /// Samples the module over a `width` × `height` grid spanning the region
+    /// between the two corners of `bounds`.
+    ///
+    /// The outermost rows and columns sample the bounds themselves, so the
+    /// map of a module whose period matches the size of the bounds has
+    /// identical opposite edges.
+    pub fn generate<M>(module: &M,
+                       bounds: (Point2<f64>, Point2<f64>),
+                       width: usize,
+                       height: usize)
+                       -> NoiseMap
+        where M: NoiseModule<Point2<f64>, Output = f64>,
+    {
+        let mut map = NoiseMap::new(width, height);
+        for (y, row) in map.rows_mut().enumerate() {
+            generate_row(module, bounds, width, height, y, row);
+        }
+        map
+    }
+
+    /// Like `generate`, but samples the rows in parallel on the rayon thread
+    /// pool. The output is identical to that of `generate`.
+    ///
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn generate_parallel<M>(module: &M,
+                                bounds: (Point2<f64>, Point2<f64>),
+                                width: usize,
+                                height: usize)
+                                -> NoiseMap
+        where M: NoiseModule<Point2<f64>, Output = f64> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let mut map = NoiseMap::new(width, height);
+        map.values
+            .par_chunks_mut(width.max(1))
+            .enumerate()
+            .for_each(|(y, row)| generate_row(module, bounds, width, height, y, row));
+        map
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value at the given column and row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the map.
+    pub fn get_value(&self, x: usize, y: usize) -> f64 {
+        assert!(x < self.width && y < self.height, "position outside the map");
+        self.values[y * self.width + x]
+    }
+
+    /// Sets the value at the given column and row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the map.
+    pub fn set_value(&mut self, x: usize, y: usize, value: f64) {
+        assert!(x < self.width && y < self.height, "position outside the map");
+        self.values[y * self.width + x] = value;
+    }
+
+    /// Returns all the values in row-major order.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an iterator over the rows of the map, each a slice of `width`
+    /// values.
+    pub fn rows<'a>(&'a self) -> Chunks<'a, f64> {
+        self.values.chunks(self.width.max(1))
+    }
+
+    /// Returns an iterator over mutable rows of the map, each a slice of
+    /// `width` values.
+    pub fn rows_mut<'a>(&'a mut self) -> ChunksMut<'a, f64> {
+        self.values.chunks_mut(self.width.max(1))
+    }
+
+    /// Returns the largest absolute difference between opposite edges of the
+    /// map, comparing the left column with the right and the top row with the
+    /// bottom.
+    ///
+    /// A map generated from a seamlessly tiling module, over bounds matching
+    /// its period, reports an error near zero. Returns 0.0 for an empty map.
+    pub fn tiling_error(&self) -> f64 {
+        if self.width == 0 || self.height == 0 {
+            return 0.0;
+        }
+
+        let last_column = self.width - 1;
+        let last_row = self.height - 1;
+        let horizontal = (0..self.height)
+            .map(|y| (self.get_value(0, y) - self.get_value(last_column, y)).abs());
+        let vertical = (0..self.width)
+            .map(|x| (self.get_value(x, 0) - self.get_value(x, last_row)).abs());
+
+        horizontal.chain(vertical).fold(0.0, f64::max)
+    }
+}
+
+/// Fills one row of a map with samples from the module. Shared by the serial
+/// and parallel paths so they produce identical output.
+fn generate_row<M>(module: &M,
+                   bounds: (Point2<f64>, Point2<f64>),
+                   width: usize,
+                   height: usize,
+                   y: usize,
+                   row: &mut [f64])
+    where M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    let (lower, upper) = bounds;
+    let step = [(upper[0] - lower[0]) / (width.max(2) - 1) as f64,
+                (upper[1] - lower[1]) / (height.max(2) - 1) as f64];
+
+    let points: Vec<_> = (0..row.len())
+        .map(|x| [lower[0] + step[0] * x as f64, lower[1] + step[1] * y as f64])
+        .collect();
+    module.get_many(&points, row);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoiseMap;
+    use modules::{Perlin, Seamless};
+    use NoiseModule;
+
+    #[test]
+    fn test_generate_and_rows() {
+        let module = Perlin::new(0);
+        let mut map = NoiseMap::generate(&module, ([-1.0, 0.0], [1.0, 3.0]), 5, 4);
+        assert_eq!(map.rows().count(), 4);
+        assert_eq!(map.get_value(1, 2), module.get([-0.5, 2.0]));
+
+        for (y, row) in map.rows().enumerate() {
+            assert_eq!(row.len(), 5);
+            assert_eq!(row[3], map.get_value(3, y));
+        }
+
+        map.set_value(4, 3, 0.5);
+        for row in map.rows_mut() {
+            row[0] = -0.5;
+        }
+        assert_eq!(map.get_value(4, 3), 0.5);
+        assert_eq!(map.get_value(0, 3), -0.5);
+        assert_eq!(map.values().len(), 20);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_generate_parallel_matches_serial() {
+        use modules::RidgedMulti;
+
+        let module = RidgedMulti::new();
+        let bounds = ([-2.0, -1.0], [3.0, 4.0]);
+        assert_eq!(NoiseMap::generate_parallel(&module, bounds, 64, 48),
+                   NoiseMap::generate(&module, bounds, 64, 48));
+    }
+
+    #[test]
+    fn test_generate_uses_get_many() {
+        use test_utils::{Counting, Sum};
+
+        let counter = Counting::new(Sum);
+        let map = NoiseMap::generate(&counter, ([0.0, 0.0], [3.0, 3.0]), 3, 4);
+        assert_eq!(counter.batches(), 4);
+        assert_eq!(counter.samples(), 12);
+        assert_eq!(map.get_value(1, 2), 3.5);
+    }
+
+    #[test]
+    fn test_tiling_error() {
+        let bounds = ([0.0, 0.0], [1.0, 1.0]);
+        let periodic = NoiseMap::generate(&Seamless::new(Perlin::new(0)), bounds, 33, 17);
+        assert!(periodic.tiling_error() < 1e-9);
+
+        let bounds = ([0.1, 0.2], [3.4, 2.7]);
+        let plain = NoiseMap::generate(&Perlin::new(0), bounds, 33, 17);
+        assert!(plain.tiling_error() > 0.1);
+    }
+}
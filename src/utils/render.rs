@@ -0,0 +1,108 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::Point2;
+use std::io;
+use std::path::Path;
+use super::NoiseMap;
+use NoiseModule;
+
+use image;
+
+/// Samples the module over a `width` × `height` grid spanning `bounds` and
+/// writes the result to `path` as an 8-bit grayscale PNG.
+///
+/// Output values in [-1, 1] are mapped linearly from black to white, and
+/// values outside that range are clamped.
+///
+/// Requires the `image` feature.
+pub fn write_image<P, M>(path: P,
+                         module: &M,
+                         width: u32,
+                         height: u32,
+                         bounds: (Point2<f64>, Point2<f64>))
+                         -> io::Result<()>
+    where P: AsRef<Path>,
+          M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    write_image_with_gamma(path, module, width, height, bounds, 1.0)
+}
+
+/// Like `write_image`, but gamma-encodes the brightness of each pixel by
+/// raising it to the power of `1 / gamma`. A `gamma` of 2.2 brightens the
+/// midtones for display on a typical monitor.
+///
+/// Requires the `image` feature.
+///
+/// # Panics
+///
+/// Panics if `gamma` is not positive.
+pub fn write_image_with_gamma<P, M>(path: P,
+                                    module: &M,
+                                    width: u32,
+                                    height: u32,
+                                    bounds: (Point2<f64>, Point2<f64>),
+                                    gamma: f64)
+                                    -> io::Result<()>
+    where P: AsRef<Path>,
+          M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    let map = NoiseMap::generate(module, bounds, width as usize, height as usize);
+    let pixels = grayscale_pixels(&map, gamma);
+
+    image::save_buffer(path.as_ref(), &pixels, width, height, image::Gray(8))
+}
+
+/// Converts the values of a map into 8-bit grayscale pixels.
+fn grayscale_pixels(map: &NoiseMap, gamma: f64) -> Vec<u8> {
+    assert!(gamma > 0.0, "gamma must be positive");
+
+    map.values()
+        .iter()
+        .map(|&value| {
+            let brightness = (value * 0.5 + 0.5).max(0.0).min(1.0);
+            (brightness.powf(1.0 / gamma) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::grayscale_pixels;
+    use utils::NoiseMap;
+
+    #[test]
+    fn test_grayscale_pixels() {
+        let mut map = NoiseMap::new(4, 1);
+        map.set_value(0, 0, -2.0);
+        map.set_value(1, 0, -0.5);
+        map.set_value(2, 0, 1.0);
+        map.set_value(3, 0, 3.0);
+        assert_eq!(grayscale_pixels(&map, 1.0), vec![0, 64, 255, 255]);
+        assert_eq!(grayscale_pixels(&map, 2.0), vec![0, 128, 255, 255]);
+    }
+
+    #[test]
+    fn test_write_image() {
+        use modules::Perlin;
+        use std::env;
+        use std::fs;
+
+        let path = env::temp_dir().join("noise_test_write_image.png");
+        super::write_image(&path, &Perlin::new(0), 16, 8, ([0.0, 0.0], [2.0, 1.0])).unwrap();
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+        fs::remove_file(&path).unwrap();
+    }
+}
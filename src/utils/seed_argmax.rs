@@ -0,0 +1,60 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use num_traits::Float;
+use NoiseModule;
+
+/// Constructs a module for each seed with `module_fn`, samples it at `point`,
+/// and returns the seed whose module outputs the largest value.
+///
+/// This is useful for deterministically picking a winner among candidate
+/// seeds, such as where to place the single best feature in a region. Ties
+/// go to the seed that comes first. Returns `None` if there are no seeds.
+pub fn seed_argmax<F, M, P, T, I>(module_fn: F, point: P, seeds: I) -> Option<usize>
+    where F: Fn(usize) -> M,
+          M: NoiseModule<P, Output = T>,
+          P: Copy,
+          T: Float,
+          I: IntoIterator<Item = usize>,
+{
+    let mut best: Option<(usize, T)> = None;
+    for seed in seeds {
+        let value = module_fn(seed).get(point);
+        match best {
+            Some((_, best_value)) if best_value >= value => {},
+            _ => best = Some((seed, value)),
+        }
+    }
+    best.map(|(seed, _)| seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::seed_argmax;
+    use modules::Perlin;
+    use NoiseModule;
+
+    #[test]
+    fn test_returns_seed_with_max_value() {
+        let point = [0.3, 1.7, -2.2];
+        let winner = seed_argmax(Perlin::new, point, 10..42).unwrap();
+
+        let best: f64 = Perlin::new(winner).get(point);
+        for seed in 10..42 {
+            assert!(Perlin::new(seed).get(point) <= best);
+        }
+        assert!(seed_argmax(Perlin::new, point, 0..0).is_none());
+    }
+}
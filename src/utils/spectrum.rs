@@ -0,0 +1,179 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::f64::consts::PI;
+use math::Point2;
+use NoiseModule;
+
+/// Computes the radially averaged power spectrum of a module's output.
+///
+/// The module is sampled over a `size` × `size` grid spanning the region
+/// between the two corners of `bounds`, the mean is removed, and the 2D
+/// discrete Fourier transform of the samples is computed. The power of each
+/// frequency is then averaged over rings of equal radius.
+///
+/// The returned `Vec` has `size / 2 + 1` entries, where entry _r_ is the
+/// average power at a radius of _r_ cycles across the grid. Entry 0 is always
+/// zero, since the mean is removed. Plotting the spectrum on a log-log scale
+/// reveals the spectral slope of the module, for example to compare fBm with
+/// ridged or Worley noise.
+///
+/// # Panics
+///
+/// Panics if `size` is not a power of two.
+pub fn power_spectrum_2d<M>(module: &M, size: usize, bounds: (Point2<f64>, Point2<f64>)) -> Vec<f64>
+    where M: NoiseModule<Point2<f64>, Output = f64>,
+{
+    assert!(size.is_power_of_two(), "size must be a power of two");
+
+    let (lower, upper) = bounds;
+    let step = [(upper[0] - lower[0]) / size as f64, (upper[1] - lower[1]) / size as f64];
+
+    let mut samples = Vec::with_capacity(size * size);
+    for y in 0..size {
+        for x in 0..size {
+            let point = [lower[0] + step[0] * x as f64, lower[1] + step[1] * y as f64];
+            samples.push(module.get(point));
+        }
+    }
+
+    let mean = samples.iter().fold(0.0, |a, b| a + b) / samples.len() as f64;
+    let mut grid: Vec<(f64, f64)> = samples.iter().map(|&v| (v - mean, 0.0)).collect();
+
+    // Transform the rows, then the columns.
+    let mut line = vec![(0.0, 0.0); size];
+    for y in 0..size {
+        fft(&mut grid[y * size..(y + 1) * size]);
+    }
+    for x in 0..size {
+        for y in 0..size {
+            line[y] = grid[y * size + x];
+        }
+        fft(&mut line);
+        for y in 0..size {
+            grid[y * size + x] = line[y];
+        }
+    }
+
+    let bins = size / 2 + 1;
+    let mut power = vec![0.0; bins];
+    let mut counts = vec![0usize; bins];
+    let normalization = (size * size) as f64;
+    for y in 0..size {
+        for x in 0..size {
+            let kx = signed_frequency(x, size);
+            let ky = signed_frequency(y, size);
+            let radius = (kx * kx + ky * ky).sqrt().round() as usize;
+            if radius < bins {
+                let (re, im) = grid[y * size + x];
+                power[radius] += (re * re + im * im) / normalization;
+                counts[radius] += 1;
+            }
+        }
+    }
+
+    for (p, &count) in power.iter_mut().zip(counts.iter()) {
+        if count > 0 {
+            *p /= count as f64;
+        }
+    }
+    power
+}
+
+/// Maps an index of the transform to its signed frequency.
+fn signed_frequency(index: usize, size: usize) -> f64 {
+    if index > size / 2 {
+        index as f64 - size as f64
+    } else {
+        index as f64
+    }
+}
+
+/// In-place iterative radix-2 fast Fourier transform. The length of `data`
+/// must be a power of two.
+fn fft(data: &mut [(f64, f64)]) {
+    let n = data.len();
+
+    // Reorder the elements by bit-reversed index.
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let angle = -2.0 * PI / length as f64;
+        for start in (0..n).filter(|i| i % length == 0) {
+            for k in 0..length / 2 {
+                let (wr, wi) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                let (ar, ai) = data[start + k];
+                let (br, bi) = data[start + k + length / 2];
+                let (tr, ti) = (br * wr - bi * wi, br * wi + bi * wr);
+                data[start + k] = (ar + tr, ai + ti);
+                data[start + k + length / 2] = (ar - tr, ai - ti);
+            }
+        }
+        length <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::power_spectrum_2d;
+    use math;
+    use modules::Fbm;
+    use NoiseModule;
+
+    /// Outputs an independent random value for each point.
+    struct WhiteNoise;
+
+    impl NoiseModule<[f64; 2]> for WhiteNoise {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 2]) -> f64 {
+            let hash = math::splitmix64(point[0].to_bits() ^ math::splitmix64(point[1].to_bits()));
+            (hash >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        }
+    }
+
+    fn band_average(spectrum: &[f64], start: usize, end: usize) -> f64 {
+        spectrum[start..end].iter().fold(0.0, |a, b| a + b) / (end - start) as f64
+    }
+
+    #[test]
+    fn test_white_noise_is_flat() {
+        let spectrum = power_spectrum_2d(&WhiteNoise, 64, ([0.0, 0.0], [64.0, 64.0]));
+        let low = band_average(&spectrum, 2, 8);
+        let high = band_average(&spectrum, 16, 32);
+        assert!(low / high > 0.5 && low / high < 2.0);
+    }
+
+    #[test]
+    fn test_fbm_is_sloped() {
+        let fbm = Fbm::new();
+        let spectrum = power_spectrum_2d(&fbm, 64, ([0.0, 0.0], [8.0, 8.0]));
+        let low = band_average(&spectrum, 2, 8);
+        let high = band_average(&spectrum, 16, 32);
+        assert!(low / high > 10.0);
+    }
+}
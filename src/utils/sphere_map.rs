@@ -0,0 +1,112 @@
+// Copyright 2016 The Noise-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use math::Point3;
+use std::f64::consts::PI;
+use super::NoiseMap;
+use NoiseModule;
+
+/// Generates noise maps for texturing a sphere.
+///
+/// Each pixel of the map is converted to a longitude and latitude and the
+/// module is sampled at the matching point on the unit sphere, so the texture
+/// has no seam between its left and right edges and no pinching at the poles
+/// when it is wrapped onto a sphere.
+#[derive(Clone, Copy, Debug)]
+pub struct SphereMap;
+
+impl SphereMap {
+    /// Samples the module over the unit sphere into a `width` × `height` map
+    /// in the equirectangular projection.
+    ///
+    /// Columns span longitudes from -180° to 180° and rows span latitudes
+    /// from -90° to 90°, with each value sampled at the center of its pixel.
+    /// The point at longitude _lon_ and latitude _lat_ is
+    /// `[cos(lat) * cos(lon), sin(lat), cos(lat) * sin(lon)]`, so the poles
+    /// lie on the _y_ axis.
+    pub fn generate<M>(module: &M, width: usize, height: usize) -> NoiseMap
+        where M: NoiseModule<Point3<f64>, Output = f64>,
+    {
+        let mut map = NoiseMap::new(width, height);
+        for (y, row) in map.rows_mut().enumerate() {
+            let lat = ((y as f64 + 0.5) / height as f64 - 0.5) * PI;
+            for (x, value) in row.iter_mut().enumerate() {
+                let lon = ((x as f64 + 0.5) / width as f64 - 0.5) * 2.0 * PI;
+                *value = module.get(lat_lon_to_point(lat, lon));
+            }
+        }
+        map
+    }
+}
+
+/// Converts a latitude and longitude, in radians, to a point on the unit
+/// sphere.
+fn lat_lon_to_point(lat: f64, lon: f64) -> Point3<f64> {
+    let r = lat.cos();
+    [r * lon.cos(), lat.sin(), r * lon.sin()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SphereMap;
+    use modules::Perlin;
+    use NoiseModule;
+
+    struct Axis(usize);
+
+    impl NoiseModule<[f64; 3]> for Axis {
+        type Output = f64;
+
+        fn get(&self, point: [f64; 3]) -> f64 {
+            point[self.0]
+        }
+    }
+
+    #[test]
+    fn test_points_on_sphere() {
+        let height = SphereMap::generate(&Axis(1), 8, 4);
+        for (y, row) in height.rows().enumerate() {
+            let expected = ((y as f64 + 0.5) / 4.0 - 0.5) * ::std::f64::consts::PI;
+            assert!(row.iter().all(|&value| (value - expected.sin()).abs() < 1e-12));
+        }
+
+        // Opposite columns of a row lie on opposite sides of the sphere.
+        let x = SphereMap::generate(&Axis(0), 8, 4);
+        for row in x.rows() {
+            for i in 0..4 {
+                assert!((row[i] + row[i + 4]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_poles_and_seam_are_continuous() {
+        let map = SphereMap::generate(&Perlin::new(0), 256, 128);
+
+        // The rows next to the poles are small circles, so their values vary
+        // little compared with those at the equator.
+        let spread = |y: usize| {
+            let row = map.rows().nth(y).unwrap();
+            row.iter().cloned().fold(-1.0 / 0.0, f64::max) -
+            row.iter().cloned().fold(1.0 / 0.0, f64::min)
+        };
+        assert!(spread(0) < 0.1 && spread(127) < 0.1);
+        assert!(spread(64) > 0.5);
+
+        for y in 0..128 {
+            assert!((map.get_value(0, y) - map.get_value(255, y)).abs() < 0.1);
+        }
+    }
+}
@@ -122,3 +122,25 @@ pub fn value4<T: Float>(perm_table: &PermutationTable, point: &math::Point4<T>)
 
     d * math::cast(2) - math::cast(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{value2, value3, value4};
+    use PermutationTable;
+
+    // The quintic S-curve used to weight the corners overshot [0, 1], which
+    // pushed these functions outside [-1, 1] between lattice points.
+    #[test]
+    fn test_range() {
+        let perm_table = PermutationTable::new(0);
+        for i in 0..512 {
+            let x = i as f64 * 0.173 - 40.0;
+            let y = i as f64 * 0.311 + 3.0;
+            for &value in &[value2(&perm_table, &[x, y]),
+                            value3(&perm_table, &[x, y, -x]),
+                            value4(&perm_table, &[x, y, -x, 0.5 * y])] {
+                assert!(value >= -1.0 && value <= 1.0, "{} out of range", value);
+            }
+        }
+    }
+}